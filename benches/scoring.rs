@@ -0,0 +1,98 @@
+// Benchmarks for the paths the bots and simulations spend their time in, run with `cargo bench`
+// Explicit returns are the style used throughout
+#![allow(clippy::needless_return)]
+
+use criterion::{ black_box, criterion_group, criterion_main, Criterion };
+
+use yahtzee::bot::{ GameView, Strategy };
+use yahtzee::die::Die;
+use yahtzee::engine::EngineState;
+use yahtzee::expected::ExpectedValueStrategy;
+use yahtzee::game::Game;
+use yahtzee::sections::create_scorecard;
+use yahtzee::simulate::play_games;
+
+// Hands of every shape: a Yahtzee, four and three of a kind, a full house, straights, and nothing
+static HANDS: [[u8; 5]; 8] = [
+    [6, 6, 6, 6, 6],
+    [4, 4, 4, 4, 1],
+    [3, 3, 3, 5, 2],
+    [2, 2, 5, 5, 5],
+    [1, 2, 3, 4, 6],
+    [2, 3, 4, 5, 6],
+    [1, 2, 3, 4, 5],
+    [1, 1, 3, 5, 6],
+];
+
+// The seed of the game the simulation benchmark plays
+static SEED: u64 = 1;
+
+// The dice showing the hand's numbers
+fn hand_dice(hand: &[u8]) -> Vec<Die> {
+    return hand
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+// A new game with the hand just rolled and the given rolls left
+fn game_with(hand: &[u8], rolls: u8) -> Game {
+    let mut game = Game::new(SEED);
+    game.set_dice(&hand_dice(hand), rolls).expect("every hand is a roll of the game's dice");
+    return game;
+}
+
+// Scoring every hand in every standard section
+fn score_sections(c: &mut Criterion) {
+    let scorecard = create_scorecard();
+    let hands: Vec<Vec<Die>> = HANDS.iter().map(|hand| hand_dice(hand)).collect();
+    c.bench_function("score every standard section", |b| {
+        b.iter(|| {
+            for dice in &hands {
+                for section in &scorecard {
+                    black_box(section.calc_score(black_box(dice)));
+                }
+            }
+        })
+    });
+}
+
+// What every open section would score for each hand, as listed for the player's best plays
+fn preview_scores(c: &mut Criterion) {
+    let games: Vec<Game> = HANDS.iter().map(|hand| game_with(hand, 0)).collect();
+    c.bench_function("preview open sections", |b| {
+        b.iter(|| {
+            for game in &games {
+                black_box(game.preview_scores());
+            }
+        })
+    });
+}
+
+// A whole game played alone by the greedy strategy
+fn greedy_game(c: &mut Criterion) {
+    c.bench_function("greedy solitaire game", |b| {
+        b.iter(|| play_games("greedy", 1, black_box(SEED)).expect("greedy is a strategy"))
+    });
+}
+
+// The expected value strategy picking which dice to hold, with two rolls left
+fn expected_hold(c: &mut Criterion) {
+    let views: Vec<GameView> = HANDS.iter()
+        .map(|hand| GameView::new(EngineState::new(&game_with(hand, 2)), Vec::new()))
+        .collect();
+    let mut strategy = ExpectedValueStrategy::default();
+
+    // The tables are worked out once, before timing
+    strategy.choose_hold(&views[0]);
+    c.bench_function("expected value hold", |b| {
+        b.iter(|| {
+            for view in &views {
+                black_box(strategy.choose_hold(view));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, score_sections, preview_scores, greedy_game, expected_hold);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+// Build a scorecard with a section of its own, then let the greedy bot play a game on it.
+// Run with `cargo run --example custom_card`
+// Explicit returns are the style used throughout
+#![allow(clippy::needless_return)]
+
+use yahtzee::bot::{ bot_by_name, play_solitaire, LocalTable };
+use yahtzee::die::Die;
+use yahtzee::game::Game;
+use yahtzee::sections::{ create_scorecard, Points, PointSection, Score, Section, SectionKind };
+
+// The points for a low hand
+static LOW_HAND_POINTS: i32 = 25;
+
+// The highest total that's a low hand
+static LOW_HAND_MAX: i32 = 12;
+
+// A fixed score for five rolled dice totalling 12 or less
+struct LowHand {
+    score: Score,
+}
+
+impl Section for LowHand {
+    fn is_filled(&self) -> bool {
+        return self.score.is_filled();
+    }
+    fn get_points(&self) -> i32 {
+        return self.score.get_points();
+    }
+    fn get_name(&self) -> &'static str {
+        return self.score.get_name();
+    }
+    fn is_handicap(&self) -> bool {
+        return self.score.is_handicap();
+    }
+    fn print(&self, name_width: usize) {
+        self.score.print(name_width);
+    }
+}
+
+impl Points for LowHand {
+    fn calc_score(&self, dice: &[Die]) -> i32 {
+        let rolled = dice.iter().all(|die| die.num > 0);
+        let total: i32 = dice
+            .iter()
+            .map(|die| i32::from(die.num))
+            .sum();
+        return if rolled & (total <= LOW_HAND_MAX) { LOW_HAND_POINTS } else { 0 };
+    }
+    fn set_score(&mut self, score: i32) -> Result<(), String> {
+        if self.score.filled {
+            return Err(format!("{} was already scored", self.score.name));
+        }
+        self.score.filled = true;
+        self.score.points = score;
+        return Ok(());
+    }
+    fn average_score(&self) -> i32 {
+        return 5;
+    }
+    fn max_possible_score(&self) -> i32 {
+        return LOW_HAND_POINTS;
+    }
+    fn set_handicap(&mut self, score: i32) -> Result<(), String> {
+        self.set_score(score)?;
+        self.score.handicap = true;
+        return Ok(());
+    }
+    fn clear_score(&mut self) {
+        self.score.clear();
+    }
+}
+
+impl PointSection for LowHand {
+    // Anything scoring any dice is closest to Chance
+    fn kind(&self) -> (SectionKind, u8) {
+        return (SectionKind::Chance, 0);
+    }
+}
+
+fn main() -> Result<(), String> {
+    let mut game = Game::new(1);
+    game.scorecard = create_scorecard();
+    game.scorecard.push(Box::new(LowHand { score: Score::new("14. Low Hand") }));
+
+    let mut bot = bot_by_name("greedy", 1).ok_or("There's no greedy bot")?;
+    let mut table = LocalTable { game };
+    play_solitaire(bot.as_mut(), &mut table)?;
+
+    for section in &table.game.scorecard {
+        println!("{: <20} {: >3}", section.get_name(), section.get_points());
+    }
+    println!("Total                {: >3}", table.game.total());
+    return Ok(());
+}
@@ -0,0 +1,36 @@
+// Score a hand in every section there is: the standard scorecard, the odd/even variant's sections,
+// and the straights as the sum straights rule scores them.
+// Run with `cargo run --example evaluate -- 3 3 3 5 5`
+// Explicit returns are the style used throughout
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::Die;
+use yahtzee::game::NUM_DICE;
+use yahtzee::sections::{ create_scorecard, odd_even_sections, sum_straight, Scorecard };
+
+fn main() -> Result<(), String> {
+    let dice: Vec<Die> = std::env
+        ::args()
+        .skip(1)
+        .map(|arg| {
+            match arg.parse::<u8>() {
+                Ok(num) if (1..=6).contains(&num) => Ok(Die { num, ..Die::default() }),
+                _ => Err(format!("'{}' isn't a number from 1 to 6", arg)),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    if dice.len() != NUM_DICE {
+        return Err(format!("Give the numbers of {} dice, ex: 3 3 3 5 5", NUM_DICE));
+    }
+
+    let sections: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+    for section in &sections {
+        println!("{: <20} {: >3}", section.get_name(), section.calc_score(&dice));
+    }
+
+    println!("\nWith sum straights:");
+    for section in sections.iter().filter_map(|section| sum_straight(section.as_ref())) {
+        println!("{: <20} {: >3}", section.get_name(), section.calc_score(&dice));
+    }
+    return Ok(());
+}
@@ -0,0 +1,29 @@
+// Let the expected value bot play one game on its own, then print its scorecard.
+// Run with `cargo run --example solitaire`, or `cargo run --example solitaire -- <seed>`
+// Explicit returns are the style used throughout
+#![allow(clippy::needless_return)]
+
+use yahtzee::bot::{ bot_by_name, play_solitaire, LocalTable };
+use yahtzee::game::Game;
+
+fn main() -> Result<(), String> {
+    let seed = match std::env::args().nth(1) {
+        Some(seed) => seed.parse::<u64>().map_err(|_| format!("'{}' isn't a seed", seed))?,
+        None => 1,
+    };
+
+    let mut bot = bot_by_name("ev", seed).ok_or("There's no expected value bot")?;
+    let mut table = LocalTable { game: Game::new(seed) };
+    play_solitaire(bot.as_mut(), &mut table)?;
+
+    let game = &table.game;
+    println!("Seed {}", seed);
+    for section in &game.scorecard {
+        println!("{: <20} {: >3}", section.get_name(), section.get_points());
+    }
+    let totals = game.totals();
+    println!("Upper bonus          {: >3}", totals.upper_bonus);
+    println!("Yahtzee bonus        {: >3}", totals.yahtzee_bonus);
+    println!("Total                {: >3}", game.total());
+    return Ok(());
+}
@@ -0,0 +1,175 @@
+/*
+    Author: Ashley DeMott
+    Project: Yahtzee
+    Description: The scoring rules on their own: counting the dice, scoring each kind of section, and
+    adding up the totals. Without the std feature it only needs core, for running without an operating system
+*/
+#![cfg_attr(not(feature = "std"), no_std)]
+// Explicit returns are the style used throughout
+#![allow(clippy::needless_return)]
+
+use serde::{ Deserialize, Serialize };
+
+// The highest number on the game's dice
+pub static MAX_FACE: usize = 6;
+
+// The upper subtotal that earns the upper bonus, and the points it earns (by default, house rules and
+// other editions can use others)
+pub static UPPER_BONUS_THRESHOLD: i32 = 63;
+pub static UPPER_BONUS: i32 = 35;
+
+// The upper subtotal that earns the upper bonus, and the points it earns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpperBonus {
+    pub threshold: i32,
+    pub points: i32,
+}
+impl Default for UpperBonus {
+    fn default() -> UpperBonus {
+        return UpperBonus {
+            threshold: UPPER_BONUS_THRESHOLD,
+            points: UPPER_BONUS,
+        };
+    }
+}
+
+// The upper bonus earned by the upper subtotal
+pub fn upper_bonus(upper: i32, bonus: UpperBonus) -> i32 {
+    return if upper >= bonus.threshold { bonus.points } else { 0 };
+}
+
+// The fixed points for a Yahtzee, every die showing the same number
+pub static YAHTZEE_POINTS: i32 = 50;
+
+// The points for every Yahtzee scored after the YAHTZEE section was filled with points (by default,
+// some rule sets give a different amount)
+pub static YAHTZEE_BONUS: i32 = 100;
+
+// How many dice show each number, and their total, counted once for the sections to share
+pub struct FaceCounts {
+    pub counts: [u8; MAX_FACE + 1], // By number (index 0 counts unrolled dice)
+    pub total: i32, // The total value of the dice
+}
+impl FaceCounts {
+    // Count the numbers the dice show (numbers above MAX_FACE are only added to the total)
+    pub fn new(faces: impl IntoIterator<Item = u8>) -> FaceCounts {
+        let mut counts = [0; MAX_FACE + 1];
+        let mut total = 0;
+        for face in faces {
+            if let Some(count) = counts.get_mut(usize::from(face)) {
+                *count += 1;
+            }
+            total += face as i32;
+        }
+        return FaceCounts { counts, total };
+    }
+
+    // The number of dice showing the number
+    pub fn count(&self, num: u8) -> u8 {
+        return self.counts.get(usize::from(num)).copied().unwrap_or(0);
+    }
+
+    // The most dice showing the same number
+    pub fn most_of_a_kind(&self) -> u8 {
+        return self.counts.iter().copied().max().unwrap_or(0);
+    }
+
+    // A bit for each number shown, ex: bit 3 is set if a die shows a 3
+    pub fn mask(&self) -> u8 {
+        let mut mask = 0;
+        for num in 1..=MAX_FACE {
+            if self.counts[num] > 0 {
+                mask |= 1 << num;
+            }
+        }
+        return mask;
+    }
+
+    // If the dice show a run of this many numbers in a row, ex: 2, 3, 4 for a run of 3.
+    // Shifting the mask down and keeping the bits set in both leaves a bit for every run start
+    pub fn has_run(&self, length: u8) -> bool {
+        let mut starts = self.mask();
+        for _ in 1..length {
+            starts &= starts >> 1;
+        }
+        return (length > 0) & (starts != 0);
+    }
+}
+
+// Points for the dice showing the number (the upper sections)
+pub fn face_score(counts: &FaceCounts, face: u8) -> i32 {
+    return i32::from(counts.count(face)) * i32::from(face);
+}
+
+// The dice total, if enough of them show the same number [Hasbro Yahtzee rules].
+// Chance is 0 of a kind, so it always scores
+pub fn of_a_kind_score(counts: &FaceCounts, kind: u8) -> i32 {
+    return if counts.most_of_a_kind() >= kind { counts.total } else { 0 };
+}
+
+// The fixed Yahtzee points, if all the dice (this many) show the same number (unrolled dice don't count)
+pub fn yahtzee_score(counts: &FaceCounts, dice: u8) -> i32 {
+    return if counts.counts[1..].iter().any(|count| *count >= dice) { YAHTZEE_POINTS } else { 0 };
+}
+
+// Points for a run of this length: 10 for each number in it, or the dice total
+pub fn straight_score(counts: &FaceCounts, length: u8, sum_dice: bool) -> i32 {
+    if !counts.has_run(length) {
+        return 0;
+    }
+    return if sum_dice { counts.total } else { i32::from(length) * 10 };
+}
+
+// The total of the odd (parity 1) or even (parity 0) numbers shown, unrolled dice add nothing
+pub fn parity_score(faces: impl IntoIterator<Item = u8>, parity: u8) -> i32 {
+    return faces
+        .into_iter()
+        .filter(|face| face % 2 == parity)
+        .map(i32::from)
+        .sum();
+}
+
+// The scorecard's totals, kept up to date as each section is scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ScoreSummary {
+    pub upper: i32, // The upper subtotal
+    pub upper_bonus: i32, // Earned once the upper subtotal reaches the threshold
+    pub lower: i32, // The lower subtotal
+    pub yahtzee_bonus: i32, // Every Yahtzee bonus earned
+    #[serde(default)]
+    pub yahtzee_bonuses: u32, // The number of Yahtzee bonuses earned
+    pub total: i32, // The grand total, everything above
+}
+impl ScoreSummary {
+    // Add the points scored in an upper or lower section, earning the upper bonus if they reach its threshold
+    pub fn add(&mut self, upper: bool, points: i32, bonus: UpperBonus) {
+        if upper {
+            self.upper += points;
+            if self.upper_bonus == 0 {
+                self.upper_bonus = upper_bonus(self.upper, bonus);
+                self.total += self.upper_bonus;
+            }
+        } else {
+            self.lower += points;
+        }
+        self.total += points;
+    }
+
+    // Add a Yahtzee bonus worth these points
+    pub fn add_yahtzee_bonus(&mut self, points: i32) {
+        self.yahtzee_bonuses += 1;
+        self.yahtzee_bonus += points;
+        self.total += points;
+    }
+
+    // Each total with its name, in the order a score sheet lists them
+    pub fn rows(&self) -> [(&'static str, i32); 5] {
+        return [
+            ("Upper Subtotal", self.upper),
+            ("Upper Bonus", self.upper_bonus),
+            ("Lower Subtotal", self.lower),
+            ("Yahtzee Bonus", self.yahtzee_bonus),
+            ("Grand Total", self.total),
+        ];
+    }
+}
@@ -0,0 +1,126 @@
+// Counting dice, scoring each kind of section, and adding up the totals
+#![allow(clippy::needless_return)]
+
+use std::process::Command;
+
+use yahtzee_scoring::{
+    face_score,
+    of_a_kind_score,
+    parity_score,
+    straight_score,
+    upper_bonus,
+    yahtzee_score,
+    FaceCounts,
+    ScoreSummary,
+    UpperBonus,
+    YAHTZEE_BONUS,
+    YAHTZEE_POINTS,
+};
+
+#[test]
+fn faces_are_counted_with_their_total() {
+    let counts = FaceCounts::new([6, 2, 6, 0, 5]);
+    assert_eq!(counts.counts, [1, 0, 1, 0, 0, 1, 2]);
+    assert_eq!(counts.total, 19);
+    assert_eq!((counts.count(6), counts.count(3), counts.count(9)), (2, 0, 0));
+    assert_eq!(counts.most_of_a_kind(), 2);
+    assert_eq!(counts.mask(), 0b1100100);
+
+    // Numbers above the highest face are only added to the total
+    let counts = FaceCounts::new([8, 1]);
+    assert_eq!(counts.counts, [0, 1, 0, 0, 0, 0, 0]);
+    assert_eq!(counts.total, 9);
+}
+
+#[test]
+fn runs_are_found_anywhere_in_the_dice() {
+    let counts = FaceCounts::new([4, 2, 3, 3, 6]);
+    assert!(counts.has_run(1) & counts.has_run(3));
+    assert!(!counts.has_run(4));
+    assert!(!counts.has_run(0));
+
+    assert!(FaceCounts::new([5, 3, 1, 4, 2]).has_run(5));
+    assert!(FaceCounts::new([6, 5, 4, 3, 1]).has_run(4));
+    assert!(!FaceCounts::new([1, 2, 4, 5, 6]).has_run(4));
+    assert!(!FaceCounts::new([0, 0, 0, 0, 0]).has_run(1));
+}
+
+#[test]
+fn the_upper_sections_count_their_number() {
+    let counts = FaceCounts::new([4, 4, 1, 4, 6]);
+    assert_eq!(face_score(&counts, 4), 12);
+    assert_eq!(face_score(&counts, 1), 1);
+    assert_eq!(face_score(&counts, 2), 0);
+}
+
+#[test]
+fn of_a_kind_scores_the_dice_total() {
+    let counts = FaceCounts::new([3, 3, 3, 3, 5]);
+    assert_eq!(of_a_kind_score(&counts, 3), 17);
+    assert_eq!(of_a_kind_score(&counts, 4), 17);
+    assert_eq!(of_a_kind_score(&counts, 5), 0);
+
+    // Chance is 0 of a kind, so it always scores
+    assert_eq!(of_a_kind_score(&FaceCounts::new([1, 2, 4, 5, 6]), 0), 18);
+}
+
+#[test]
+fn a_yahtzee_needs_every_rolled_die_the_same() {
+    assert_eq!(yahtzee_score(&FaceCounts::new([2; 5]), 5), YAHTZEE_POINTS);
+    assert_eq!(yahtzee_score(&FaceCounts::new([2, 2, 2, 2, 3]), 5), 0);
+
+    // Unrolled dice showing 0 aren't a Yahtzee
+    assert_eq!(yahtzee_score(&FaceCounts::new([0; 5]), 5), 0);
+}
+
+#[test]
+fn straights_score_fixed_points_or_the_dice_total() {
+    let counts = FaceCounts::new([2, 3, 4, 5, 5]);
+    assert_eq!(straight_score(&counts, 3, false), 30);
+    assert_eq!(straight_score(&counts, 4, false), 40);
+    assert_eq!(straight_score(&counts, 4, true), 19);
+    assert_eq!(straight_score(&counts, 5, false), 0);
+    assert_eq!(straight_score(&counts, 5, true), 0);
+}
+
+#[test]
+fn evens_and_odds_total_their_numbers() {
+    assert_eq!(parity_score([1, 2, 3, 4, 6], 0), 12);
+    assert_eq!(parity_score([1, 2, 3, 4, 6], 1), 4);
+    assert_eq!(parity_score([0, 0, 5, 0, 0], 0), 0);
+}
+
+#[test]
+fn the_summary_earns_the_upper_bonus_once() {
+    let bonus = UpperBonus::default();
+    assert_eq!(upper_bonus(62, bonus), 0);
+    assert_eq!(upper_bonus(63, bonus), 35);
+    assert_eq!(upper_bonus(70, UpperBonus { threshold: 70, points: 50 }), 50);
+
+    // Reaching the threshold adds the bonus to the total, and more upper points don't add it again
+    let mut summary = ScoreSummary::default();
+    summary.add(true, 60, bonus);
+    summary.add(false, 25, bonus);
+    assert_eq!(summary.upper_bonus, 0);
+    summary.add(true, 5, bonus);
+    summary.add(true, 10, bonus);
+    summary.add_yahtzee_bonus(YAHTZEE_BONUS);
+    assert_eq!(
+        summary.rows(),
+        [("Upper Subtotal", 75), ("Upper Bonus", 35), ("Lower Subtotal", 25), ("Yahtzee Bonus", 100), ("Grand Total", 235)]
+    );
+    assert_eq!(summary.yahtzee_bonuses, 1);
+}
+
+#[test]
+fn builds_without_the_standard_library() {
+    // A separate target directory, so the build doesn't wait on the one running the tests
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["build", "--quiet", "--package", "yahtzee-scoring", "--no-default-features", "--target-dir"])
+        .arg(std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("no-std"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,29 @@
+// Notable things done in a game, celebrated when they happen and listed when the game ends
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    NaturalYahtzee, // A Yahtzee from a single roll, scored in the YAHTZEE section
+    UpperBonus, // The upper sections reached the bonus
+    LargeStraight, // The Large Straight was scored with points
+}
+
+impl Achievement {
+    // The achievement's name, as listed at the end of the game
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Achievement::NaturalYahtzee => "Natural Yahtzee",
+            Achievement::UpperBonus => "Upper Bonus",
+            Achievement::LargeStraight => "Large Straight",
+        };
+    }
+
+    // The message shown when it's earned
+    pub fn celebration(&self) -> &'static str {
+        return match self {
+            Achievement::NaturalYahtzee => "*** NATURAL YAHTZEE! Five of a kind in a single roll! ***",
+            Achievement::UpperBonus => "*** Upper bonus reached, the upper sections are worth extra points! ***",
+            Achievement::LargeStraight => "*** Large Straight! ***",
+        };
+    }
+}
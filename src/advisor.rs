@@ -0,0 +1,74 @@
+// Advice on which dice to freeze and which sections to aim for, from the expected value strategy
+use crate::bot::{ GameView, HoldMask, Strategy };
+use crate::die::Die;
+use crate::engine::EngineState;
+use crate::expected::{ tables, ExpectedValueStrategy };
+use crate::game::Game;
+use crate::sections::PointSection;
+
+// The most sections advised as targets
+static MAX_TARGETS: usize = 3;
+
+// What the advisor recommends for the dice as they are
+pub struct Advice {
+    pub hold: HoldMask, // The dice to freeze before rolling again
+    pub score_now: Option<usize>, // The section to score in, when rolling again isn't worth it (or allowed)
+    pub targets: Vec<(usize, f64)>, // The best open sections to aim for, with the points each is expected to score
+}
+
+// The points the section is expected to score by the end of the turn: the next roll keeps the held
+// dice, and any rolls after it keep whichever dice are best for the section
+fn expected_points(section: &dyn PointSection, dice: &[Die], hold: &HoldMask, rolls_left: u8) -> f64 {
+    if rolls_left == 0 {
+        return f64::from(section.calc_score(dice));
+    }
+
+    let tables = tables();
+    let mut hand_points: Vec<f64> = tables
+        .hand_scores(section)
+        .iter()
+        .map(|points| f64::from(*points))
+        .collect();
+    for _ in 1..rolls_left {
+        hand_points = tables.hand_values(&tables.kept_values(&hand_points));
+    }
+
+    let held: Vec<Die> = dice
+        .iter()
+        .zip(hold)
+        .map(|(die, held)| Die { frozen: *held, ..die.clone() })
+        .collect();
+    return tables.roll_value(&held, &hand_points);
+}
+
+// Advise the player on the game's dice and open sections, without changing the game
+pub fn advise(game: &Game) -> Advice {
+    let view = GameView::new(EngineState::new(game), Vec::new());
+    let mut strategy = ExpectedValueStrategy::default();
+
+    // With no rolls left, every die is kept
+    let hold = if game.rolls == 0 { vec![true; game.dice.len()] } else { strategy.choose_hold(&view) };
+    let score_now = if hold.iter().all(|held| *held) { Some(strategy.choose_section(&view)) } else { None };
+
+    // The sections scoring the most above what they usually score are the best to aim for
+    let mut targets: Vec<(usize, f64, f64)> = game.scorecard
+        .iter()
+        .enumerate()
+        .filter(|(section_i, _)| game.can_score(*section_i))
+        .map(|(section_i, section)| {
+            let points = expected_points(section.as_ref(), &game.dice, &hold, game.rolls);
+            (section_i, points, points - f64::from(section.average_score()))
+        })
+        .collect();
+    targets.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    return Advice {
+        hold,
+        score_now,
+        targets: targets
+            .into_iter()
+            .take(MAX_TARGETS)
+            .map(|(section_i, points, _)| (section_i, points))
+            .collect(),
+    };
+}
@@ -0,0 +1,115 @@
+// Simple strategies: picking at random, keeping the most common value for the most points, and keeping
+// the most common value for any points
+use rand::rngs::StdRng;
+use rand::{ Rng, SeedableRng };
+
+use crate::bot::{ GameView, HoldMask, Strategy };
+use crate::die::Die;
+use crate::engine::SectionView;
+
+// Which dice to keep: every die showing the most common value (higher values win ties)
+pub fn choose_freezes(dice: &[Die]) -> Vec<bool> {
+    let mut best_num = 0;
+    let mut best_count = 0;
+
+    // Find the most common value
+    for die in dice {
+        let count = dice.iter().filter(|d| d.num == die.num).count();
+        if (count > best_count) | ((count == best_count) & (die.num > best_num)) {
+            best_num = die.num;
+            best_count = count;
+        }
+    }
+
+    // Keep only the dice showing that value
+    return dice.iter().map(|die| die.num == best_num).collect();
+}
+
+// The index of the open section worth the most points with the current dice
+pub fn choose_section(scorecard: &Vec<SectionView>) -> usize {
+    let mut best_i = None;
+    let mut best_score = 0;
+
+    for (i, section) in scorecard.iter().enumerate() {
+        // Only sections that can be filled have potential points
+        let score = match section.potential {
+            Some(score) => score,
+            None => continue,
+        };
+
+        // The first open section is picked unless a later one is worth more
+        if best_i.is_none() | (score > best_score) {
+            best_i = Some(i);
+            best_score = score;
+        }
+    }
+
+    return best_i.expect("no open section to pick");
+}
+
+// Keeps the most common value until out of rolls, then scores the section worth the most
+pub struct GreedyStrategy;
+impl Strategy for GreedyStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        return choose_freezes(&view.dice);
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        return choose_section(&view.scorecard);
+    }
+}
+
+// Keeps the most common value like the greedy strategy, but scores any open section worth points, each
+// equally likely (or any open section, when none is worth points), for a beginner's opponent
+pub struct EasyStrategy {
+    rng: StdRng, // Makes the choices, separate from the game's dice rolls
+}
+impl EasyStrategy {
+    pub fn new(seed: u64) -> EasyStrategy {
+        return EasyStrategy {
+            rng: StdRng::seed_from_u64(seed),
+        };
+    }
+}
+impl Strategy for EasyStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        return choose_freezes(&view.dice);
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        let open: Vec<usize> = (0..view.scorecard.len())
+            .filter(|i| view.scorecard[*i].potential.is_some())
+            .collect();
+        let scoring: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|i| view.scorecard[*i].potential.unwrap_or(0) > 0)
+            .collect();
+        let choices = if scoring.is_empty() { open } else { scoring };
+        return choices[self.rng.gen_range(0..choices.len())];
+    }
+}
+
+// Keeps any of the dice and scores any open section, each equally likely (a baseline to compare to)
+pub struct RandomStrategy {
+    rng: StdRng, // Makes the choices, separate from the game's dice rolls
+}
+impl RandomStrategy {
+    pub fn new(seed: u64) -> RandomStrategy {
+        return RandomStrategy {
+            rng: StdRng::seed_from_u64(seed),
+        };
+    }
+}
+impl Strategy for RandomStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        return view.dice
+            .iter()
+            .map(|_| self.rng.gen())
+            .collect();
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        let open: Vec<usize> = (0..view.scorecard.len())
+            .filter(|i| view.scorecard[*i].potential.is_some())
+            .collect();
+        return open[self.rng.gen_range(0..open.len())];
+    }
+}
@@ -0,0 +1,510 @@
+// The terminal game's command line: every option, grouped by what it's for, and reading them
+use std::path::PathBuf;
+
+use crate::bot::AiLevel;
+use crate::game::{ MAX_ROLLS, NUM_DICE };
+use crate::save;
+use crate::sections::{ UpperBonus, YAHTZEE_BONUS };
+use crate::stats::LIFETIME_FILE;
+
+// Settings chosen on the command line
+pub struct Options {
+    pub player: String, // The player's name
+    pub seed: Option<u64>, // The seed for the dice rolls, random if not given
+    pub rules: RuleOptions,
+    pub play: PlayOptions,
+    pub files: FileOptions,
+    pub bots: BotOptions,
+    pub replay: ReplayOptions,
+    pub dice: DiceOptions,
+    pub commands: CommandOptions,
+}
+
+// The rules a new game is played by
+pub struct RuleOptions {
+    pub handicap: usize, // The number of low-value sections filled in before the game starts
+    pub joker: bool, // Play with joker rules, a Yahtzee must be scored in its open upper section
+    pub max_holds: Option<usize>, // The most dice that can be frozen at once
+    pub max_zeros: Option<u32>, // The most sections that can be scored at 0
+    pub nudge: bool, // Allow one nudge each game
+    pub yahtzee_bonus: i32, // The points for each Yahtzee bonus
+    pub upper_bonus: UpperBonus, // The upper total that earns the upper bonus, and its points
+    pub sections: Option<Vec<u8>>, // Play with only these standard sections, by their numbers
+    pub odd_even: bool, // Add the Evens and Odds sections to the scorecard
+    pub sum_straights: bool, // Score straights by the total of their dice
+}
+
+// How the player plays: the help they get, and what they play through
+pub struct PlayOptions {
+    pub tutorial: bool, // Show what the next roll would be before rolling
+    pub practice: bool, // Allow taking a snapshot of the turn and going back to it
+    pub grouped: bool, // Show the scorecard in upper and lower sections, with their totals
+    pub freeplay: bool, // Keep playing new scorecards, adding up a running total across them
+    pub advisor: bool, // If the player can ask the advisor what to do
+    pub coach: bool, // Warn before rolling away a strong hand
+    pub careful: bool, // Confirm before the final roll of a turn
+    pub json_events: bool, // Write the game state as JSON to stderr whenever it changes
+    pub engine: bool, // Play through JSON commands instead of the menu
+    pub protocol: bool, // Play through plain text commands instead of the menu
+    pub script: Option<PathBuf>, // Play the moves in this file instead of the menu
+    pub demo: bool, // Let the computer play until the player presses Enter
+}
+
+// Where games are read from and written to
+pub struct FileOptions {
+    pub data_dir: PathBuf, // Where saves, replays, high scores, and statistics are kept (the working directory if empty)
+    pub load: Option<PathBuf>, // A saved game to continue
+    pub stats_file: Option<PathBuf>, // Where to append the results of finished games
+    pub lifetime_file: PathBuf, // Where the lifetime statistics are kept
+    pub export_csv: Option<PathBuf>, // Export each finished game's scorecard here, without asking
+    pub transcript: Option<PathBuf>, // Write a Markdown transcript of every game here
+    pub solution_file: Option<PathBuf>, // Where perfect play is kept once it's worked out, if not the data directory
+}
+
+// Games the computer plays: analyzing a seed, bot matches, simulations, and tournaments
+pub struct BotOptions {
+    pub analyze_seed: Option<u64>, // Let the computer play a game with this seed instead
+    pub ai_level: Option<AiLevel>, // How well the computer plays (the demo and analyzing a seed), if chosen
+    pub botmatch: Option<(String, String)>, // Names of two bots to play against each other
+    pub remote: bool, // Play bot games on engine processes instead of in this one
+    pub record_bots: bool, // Count bot games (analyzing a seed, botmatch) in the lifetime statistics
+    pub games: u32, // The number of games to simulate
+    pub strategy: String, // The bot that plays the simulated games
+    pub strategies: Vec<String>, // The strategies in the tournament
+    pub json: bool, // Print the simulation's (or a quiet game's) results as JSON
+    pub quiet: bool, // Only print the final total of a game played without prompts
+    pub bucket_width: i32, // The points covered by each bar of the simulation's histogram
+    pub histogram_csv: Option<PathBuf>, // Write the simulation's histogram buckets here
+}
+
+// Playing back and checking recorded games
+#[derive(Default)]
+pub struct ReplayOptions {
+    pub file: Option<PathBuf>, // A replay to play back instead of playing
+    pub step: bool, // Wait for Enter between the replay's turns
+    pub from_moves: bool, // Replay a seed and a move list instead of a replay file
+    pub moves: Option<PathBuf>, // The move list to replay
+    pub verify: Option<PathBuf>, // A replay to check the final score of instead of playing
+}
+
+// Dice given on the command line, to show the odds for or score as a puzzle
+pub struct DiceOptions {
+    pub prob: Option<Vec<u8>>, // Dice to show the odds for instead of playing
+    pub puzzle: Option<Vec<u8>>, // Dice to score the best from, instead of playing
+    pub held: Vec<usize>, // The dice (counting from 1) frozen when showing the odds
+    pub rolls_left: u8, // The rolls left when showing the odds
+}
+
+// Something to do instead of playing
+#[derive(Default)]
+pub struct CommandOptions {
+    pub show_stats: bool, // Show the lifetime statistics
+    pub high_scores: bool, // Show the high score table
+    pub list_saves: bool, // List the saved games
+    pub delete_save: Option<String>, // The name of a save to delete
+    pub simulate: bool, // Let a bot play many games and show how the scores are spread
+    pub tournament: bool, // Let strategies play each other and rank them
+    pub solve: bool, // Show the expected score with perfect play
+    pub selfcheck: bool, // Check this build scores correctly
+    pub bench: bool, // Time the scoring (with the bench feature)
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        return Options {
+            player: "Player".to_string(),
+            seed: None,
+            rules: RuleOptions::default(),
+            play: PlayOptions::default(),
+            files: FileOptions::default(),
+            bots: BotOptions::default(),
+            replay: ReplayOptions::default(),
+            dice: DiceOptions::default(),
+            commands: CommandOptions::default(),
+        };
+    }
+}
+impl Default for RuleOptions {
+    fn default() -> RuleOptions {
+        return RuleOptions {
+            handicap: 0,
+            joker: false,
+            max_holds: None,
+            max_zeros: None,
+            nudge: false,
+            yahtzee_bonus: YAHTZEE_BONUS,
+            upper_bonus: UpperBonus::default(),
+            sections: None,
+            odd_even: false,
+            sum_straights: false,
+        };
+    }
+}
+impl Default for PlayOptions {
+    fn default() -> PlayOptions {
+        return PlayOptions {
+            tutorial: false,
+            practice: false,
+            grouped: false,
+            freeplay: false,
+            advisor: true,
+            coach: false,
+            careful: false,
+            json_events: false,
+            engine: false,
+            protocol: false,
+            script: None,
+            demo: false,
+        };
+    }
+}
+impl Default for FileOptions {
+    fn default() -> FileOptions {
+        return FileOptions {
+            data_dir: PathBuf::new(),
+            load: None,
+            stats_file: None,
+            lifetime_file: PathBuf::from(LIFETIME_FILE),
+            export_csv: None,
+            transcript: None,
+            solution_file: None,
+        };
+    }
+}
+impl Default for BotOptions {
+    fn default() -> BotOptions {
+        return BotOptions {
+            analyze_seed: None,
+            ai_level: None,
+            botmatch: None,
+            remote: false,
+            record_bots: false,
+            games: 1000,
+            strategy: "greedy".to_string(),
+            strategies: vec!["greedy".to_string(), "expected".to_string(), "random".to_string()],
+            json: false,
+            quiet: false,
+            bucket_width: 10,
+            histogram_csv: None,
+        };
+    }
+}
+impl Default for DiceOptions {
+    fn default() -> DiceOptions {
+        return DiceOptions {
+            prob: None,
+            puzzle: None,
+            held: Vec::new(),
+            rolls_left: MAX_ROLLS - 1,
+        };
+    }
+}
+
+// Read the value following an option as a seed
+fn parse_seed(option: &str, value: Option<String>) -> Result<u64, String> {
+    let value = value.ok_or(format!("{} needs a seed", option))?;
+    return value.parse::<u64>().map_err(|_| format!("{} needs a positive integer seed", option));
+}
+
+// Read a comma separated list of numbers, each from 1 to max
+fn parse_numbers(option: &str, value: Option<String>, max: u8) -> Result<Vec<u8>, String> {
+    let value = value.ok_or(format!("{} needs a list of numbers, ex: 1,3,3,4,6", option))?;
+    return value
+        .split(',')
+        .map(|num| {
+            num.trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|num| (1..=max).contains(num))
+                .ok_or(format!("{} needs numbers from 1 to {}, not '{}'", option, max, num))
+        })
+        .collect();
+}
+
+// Read the command line arguments into Options
+pub fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut load = None; // The save to load, found once the data directory is known
+    let mut lifetime_file = None;
+
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => {
+                options.player = args.next().ok_or("--name needs a player name")?;
+            }
+            "--stats-file" => {
+                let path = args.next().ok_or("--stats-file needs a file path")?;
+                options.files.stats_file = Some(PathBuf::from(path));
+            }
+            "--seed" => {
+                options.seed = Some(parse_seed(&arg, args.next())?);
+            }
+            "--analyze-seed" => {
+                options.bots.analyze_seed = Some(parse_seed(&arg, args.next())?);
+            }
+            "--ai-level" => {
+                let level = args.next().ok_or("--ai-level needs a level: easy, medium, or hard")?;
+                options.bots.ai_level = Some(
+                    AiLevel::from_name(&level).ok_or(format!("Unknown AI level '{}', pick easy, medium, or hard", level))?
+                );
+            }
+            "--json-events" => {
+                options.play.json_events = true;
+            }
+            "--handicap" => {
+                let count = args.next().ok_or("--handicap needs a number of sections")?;
+                options.rules.handicap = count
+                    .parse::<usize>()
+                    .map_err(|_| "--handicap needs a positive number of sections")?;
+            }
+            "--joker" => {
+                options.rules.joker = true;
+            }
+            "--odd-even" => {
+                options.rules.odd_even = true;
+            }
+            "--sum-straights" => {
+                options.rules.sum_straights = true;
+            }
+            "--tutorial" => {
+                options.play.tutorial = true;
+            }
+            "--practice" => {
+                options.play.practice = true;
+            }
+            "--grouped" => {
+                options.play.grouped = true;
+            }
+            "--freeplay" => {
+                options.play.freeplay = true;
+            }
+            "--no-advisor" => {
+                options.play.advisor = false;
+            }
+            "--coach" => {
+                options.play.coach = true;
+            }
+            "--careful" => {
+                options.play.careful = true;
+            }
+            "--max-holds" => {
+                let count = args.next().ok_or("--max-holds needs a number of dice")?;
+                options.rules.max_holds = Some(
+                    count.parse::<usize>().map_err(|_| "--max-holds needs a positive number of dice")?
+                );
+            }
+            "--nudge" => {
+                options.rules.nudge = true;
+            }
+            "--yahtzee-bonus" => {
+                let points = args.next().ok_or("--yahtzee-bonus needs a number of points")?;
+                options.rules.yahtzee_bonus = i32::from(
+                    points.parse::<u16>().map_err(|_| "--yahtzee-bonus needs a positive number of points")?
+                );
+            }
+            "--bonus-threshold" => {
+                let points = args.next().ok_or("--bonus-threshold needs a number of points")?;
+                options.rules.upper_bonus.threshold = i32::from(
+                    points.parse::<u16>().map_err(|_| "--bonus-threshold needs a positive number of points")?
+                );
+            }
+            "--bonus-amount" => {
+                let points = args.next().ok_or("--bonus-amount needs a number of points")?;
+                options.rules.upper_bonus.points = i32::from(
+                    points.parse::<u16>().map_err(|_| "--bonus-amount needs a positive number of points")?
+                );
+            }
+            "--sections" => {
+                options.rules.sections = Some(parse_numbers("--sections", args.next(), 13)?);
+            }
+            "--max-zeros" => {
+                let count = args.next().ok_or("--max-zeros needs a number of sections")?;
+                options.rules.max_zeros = Some(
+                    count.parse::<u32>().map_err(|_| "--max-zeros needs a positive number of sections")?
+                );
+            }
+            "--engine" => {
+                options.play.engine = true;
+            }
+            "--protocol" => {
+                options.play.protocol = true;
+            }
+            "--script" => {
+                let path = args.next().ok_or("--script needs a file of moves")?;
+                options.play.script = Some(PathBuf::from(path));
+            }
+            "botmatch" => {
+                let bot_a = args.next().ok_or("botmatch needs two bot names")?;
+                let bot_b = args.next().ok_or("botmatch needs two bot names")?;
+                options.bots.botmatch = Some((bot_a, bot_b));
+            }
+            "--remote" => {
+                options.bots.remote = true;
+            }
+            "--load" => {
+                load = Some(args.next().ok_or("--load needs a file path or save name")?);
+            }
+            "--demo" => {
+                options.play.demo = true;
+            }
+            "--lifetime-file" => {
+                let path = args.next().ok_or("--lifetime-file needs a file path")?;
+                lifetime_file = Some(PathBuf::from(path));
+            }
+            "--data-dir" => {
+                let path = args.next().ok_or("--data-dir needs a directory path")?;
+                options.files.data_dir = PathBuf::from(path);
+            }
+            "--transcript" => {
+                let path = args.next().ok_or("--transcript needs a file path")?;
+                options.files.transcript = Some(PathBuf::from(path));
+            }
+            "--export-csv" => {
+                let path = args.next().ok_or("--export-csv needs a file path")?;
+                options.files.export_csv = Some(PathBuf::from(path));
+            }
+            "--record-bots" => {
+                options.bots.record_bots = true;
+            }
+            "stats" => {
+                options.commands.show_stats = true;
+            }
+            "replay" => {
+                // Without a replay file, the game is replayed from --seed and --moves
+                match args.next_if(|next| !next.starts_with("--")) {
+                    Some(path) => {
+                        options.replay.file = Some(PathBuf::from(path));
+                    }
+                    None => {
+                        options.replay.from_moves = true;
+                    }
+                }
+            }
+            "--moves" => {
+                let path = args.next().ok_or("--moves needs a file path")?;
+                options.replay.moves = Some(PathBuf::from(path));
+            }
+            "--step" => {
+                options.replay.step = true;
+            }
+            "verify" => {
+                let path = args.next().ok_or("verify needs a replay file")?;
+                options.replay.verify = Some(PathBuf::from(path));
+            }
+            "simulate" => {
+                options.commands.simulate = true;
+            }
+            "prob" => {
+                let dice = parse_numbers("prob", args.next(), 6)?;
+                if dice.len() != NUM_DICE {
+                    return Err(format!("prob needs {} dice", NUM_DICE));
+                }
+                options.dice.prob = Some(dice);
+            }
+            "puzzle" => {
+                let dice = parse_numbers("puzzle", args.next(), 6)?;
+                if dice.len() != NUM_DICE {
+                    return Err(format!("puzzle needs {} dice", NUM_DICE));
+                }
+                options.dice.puzzle = Some(dice);
+            }
+            "--held" => {
+                let held = parse_numbers("--held", args.next(), NUM_DICE as u8)?;
+                options.dice.held = held
+                    .iter()
+                    .map(|die| usize::from(*die))
+                    .collect();
+            }
+            "--rolls" => {
+                let rolls = args.next().ok_or("--rolls needs a number of rolls")?;
+                options.dice.rolls_left = rolls
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|rolls| *rolls < MAX_ROLLS)
+                    .ok_or(format!("--rolls needs a number of rolls from 0 to {}", MAX_ROLLS - 1))?;
+            }
+            "--games" => {
+                let count = args.next().ok_or("--games needs a number of games")?;
+                options.bots.games = count
+                    .parse::<u32>()
+                    .map_err(|_| "--games needs a positive number of games")?;
+            }
+            "--strategy" => {
+                options.bots.strategy = args.next().ok_or("--strategy needs a bot name")?;
+            }
+            "--json" => {
+                options.bots.json = true;
+            }
+            "--quiet" => {
+                options.bots.quiet = true;
+            }
+            "--bucket-width" => {
+                let width = args.next().ok_or("--bucket-width needs a number of points")?;
+                options.bots.bucket_width = width
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|width| *width > 0)
+                    .ok_or("--bucket-width needs a positive number of points")?;
+            }
+            "--histogram-csv" => {
+                let path = args.next().ok_or("--histogram-csv needs a file path")?;
+                options.bots.histogram_csv = Some(PathBuf::from(path));
+            }
+            "solve" => {
+                options.commands.solve = true;
+            }
+            "--solution-file" => {
+                let path = args.next().ok_or("--solution-file needs a file path")?;
+                options.files.solution_file = Some(PathBuf::from(path));
+            }
+            "selfcheck" => {
+                options.commands.selfcheck = true;
+            }
+            #[cfg(feature = "bench")]
+            "bench" => {
+                options.commands.bench = true;
+            }
+            "tournament" => {
+                options.commands.tournament = true;
+            }
+            "--strategies" => {
+                let names = args.next().ok_or("--strategies needs a list of bot names")?;
+                options.bots.strategies = names
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect();
+            }
+            "highscores" => {
+                options.commands.high_scores = true;
+            }
+            "saves" | "--list-saves" => {
+                options.commands.list_saves = true;
+            }
+            "delete-save" => {
+                options.commands.delete_save = Some(args.next().ok_or("delete-save needs a save name")?);
+            }
+            _ => {
+                return Err(format!("Unknown argument '{}'", arg));
+            }
+        }
+    }
+
+    // Saves and the statistics are found in the data directory, unless given as paths
+    options.files.load = load.map(|path_or_name| save::find_save(&options.files.data_dir, &path_or_name));
+    options.files.lifetime_file = lifetime_file.unwrap_or(options.files.data_dir.join(LIFETIME_FILE));
+
+    // A move list is replayed from the seed it was played with
+    if options.replay.from_moves & (options.replay.moves.is_none() | options.seed.is_none()) {
+        return Err("replay needs a replay file, or --seed and --moves".to_string());
+    }
+    if options.replay.moves.is_some() & !options.replay.from_moves {
+        return Err("--moves is only used by replay".to_string());
+    }
+    let without_prompts = options.bots.analyze_seed.is_some() | options.play.script.is_some() | options.replay.from_moves;
+    if options.bots.quiet & !without_prompts {
+        return Err("--quiet is for games played without prompts: --analyze-seed, --script, or replay --moves".to_string());
+    }
+    return Ok(options);
+}
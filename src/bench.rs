@@ -0,0 +1,119 @@
+// Timing how long scoring takes, since the bots score every hand many times over
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::hint::black_box;
+use std::time::Instant;
+
+use crate::die::{ Die, Random };
+use crate::expected::tables;
+use crate::sections::{ counts, create_scorecard, odd_even_sections, sum_straight, PointSection, Scorecard };
+
+// The numbers of dice scored, the game's five and a larger handful
+static DICE_COUNTS: [usize; 2] = [5, 10];
+
+// The number of different hands scored for each number of dice
+static HANDS: usize = 10_000;
+
+// How many times every hand is scored in each section
+static ROUNDS: usize = 100;
+
+// Random hands of the given number of dice, the same every run
+fn random_hands(dice_count: usize) -> Vec<Vec<Die>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    return (0..HANDS)
+        .map(|_| {
+            let mut dice = vec![Die::default(); dice_count];
+            for die in &mut dice {
+                die.roll(&mut rng);
+            }
+            dice
+        })
+        .collect();
+}
+
+// Every kind of section, named: the scorecard's, the odd/even variant's, and the sum straights
+fn every_section() -> Vec<(String, Box<dyn PointSection>)> {
+    let sections: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+    let straights: Vec<(String, Box<dyn PointSection>)> = sections
+        .iter()
+        .filter_map(|section| sum_straight(section.as_ref()))
+        .map(|straight| (format!("{} (sum)", straight.get_name()), straight))
+        .collect();
+    return sections
+        .into_iter()
+        .map(|section| (section.get_name().to_string(), section))
+        .chain(straights)
+        .collect();
+}
+
+// The lengths of straight compared between the two ways of finding them
+static RUN_LENGTHS: [u8; 3] = [3, 4, 5];
+
+// Finding a straight the way it used to be found, collecting the numbers shown and searching them
+// for every run, to compare with the mask of numbers shown that's used now
+fn vec_has_run(dice: &[Die], length: u8) -> bool {
+    let present: Vec<u8> = dice
+        .iter()
+        .map(|die| die.num)
+        .collect();
+    return (1..=7 - length).any(|start| (start..start + length).all(|num| present.contains(&num)));
+}
+
+// The nanoseconds checking every hand for a straight takes, per hand
+fn time_runs(hands: &[Vec<Die>], has_run: impl Fn(&[Die]) -> bool) -> u128 {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for dice in hands {
+            black_box(has_run(black_box(dice)));
+        }
+    }
+    return start.elapsed().as_nanos() / ((HANDS * ROUNDS) as u128);
+}
+
+// Time scoring every section, printing the time each score takes
+pub fn run() {
+    let sections = every_section();
+    let name_width = sections
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    for dice_count in DICE_COUNTS {
+        let hands = random_hands(dice_count);
+        println!("{} dice ({} hands, {} rounds)", dice_count, HANDS, ROUNDS);
+
+        for (name, section) in &sections {
+            let start = Instant::now();
+            for _ in 0..ROUNDS {
+                for dice in &hands {
+                    black_box(section.calc_score(black_box(dice)));
+                }
+            }
+            let per_score = start.elapsed().as_nanos() / ((HANDS * ROUNDS) as u128);
+            println!("  {: <width$}  {:>5} ns", name, per_score, width = name_width);
+        }
+    }
+
+    // Finding straights in five dice with the mask, and the way they used to be found
+    let hands = random_hands(5);
+    for length in RUN_LENGTHS {
+        let mask = time_runs(&hands, |dice| counts(dice).has_run(length));
+        let vec = time_runs(&hands, |dice| vec_has_run(dice, length));
+        println!("Finding a straight of {}: {} ns with the mask, {} ns searching a Vec", length, mask, vec);
+    }
+
+    // Looking a five dice hand's score up in the expected value tables (here 3 of a Kind's, the
+    // seventh section) instead of working it out
+    let tables = tables();
+    let hands = random_hands(5);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for dice in &hands {
+            let hand_i = tables.hand_of(black_box(dice)).expect("every hand is rolled");
+            black_box(tables.scores[6][hand_i]);
+        }
+    }
+    let per_lookup = start.elapsed().as_nanos() / ((HANDS * ROUNDS) as u128);
+    println!("Looking up a score in the tables: {} ns", per_lookup);
+}
@@ -0,0 +1,285 @@
+// Computer players (bots), and the tables they play their games on
+use std::io::{ BufRead, BufReader, Write };
+use std::path::Path;
+use std::process::{ Child, ChildStdin, ChildStdout, Command as Process, Stdio };
+
+use crate::ai::{ EasyStrategy, GreedyStrategy, RandomStrategy };
+use crate::engine::{ Command, EngineState, Response, SectionView };
+use crate::expected::ExpectedValueStrategy;
+use crate::game::{ Dice, Game, Move, MAX_ROLLS };
+use crate::lookahead::LookaheadStrategy;
+use crate::solver::OptimalStrategy;
+
+// The most moves a bot can make in a turn before it's stopped for not scoring
+static MAX_TURN_MOVES: usize = 32;
+
+// Everything a player can see when making a move
+pub struct GameView {
+    pub dice: Dice, // The bot's dice
+    pub rolls_left: u8, // Rolls left this turn
+    pub scorecard: Vec<SectionView>, // The bot's scorecard, with potential points for the dice
+    #[allow(unused)] // Not every bot looks at the other players
+    pub opponents: Vec<Vec<SectionView>>, // Every other player's scorecard
+}
+impl GameView {
+    pub fn new(state: EngineState, opponents: Vec<Vec<SectionView>>) -> GameView {
+        return GameView {
+            dice: state.dice,
+            rolls_left: state.rolls_left,
+            scorecard: state.scorecard,
+            opponents,
+        };
+    }
+}
+
+// A computer player, picking a move based on what it can see
+pub trait Bot {
+    fn choose(&mut self, view: &GameView) -> Move;
+}
+
+// Which dice to keep before the next roll, true for each die kept
+pub type HoldMask = Vec<bool>;
+
+// A way of playing, deciding only which dice to keep and which section to score.
+// Strategies are played as bots by StrategyBot, and like bots only see the GameView
+pub trait Strategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask;
+    fn choose_section(&mut self, view: &GameView) -> usize;
+}
+
+// Plays a strategy's decisions as moves: rolling the dice it doesn't keep until it keeps them
+// all or runs out of rolls, then scoring
+pub struct StrategyBot<S: Strategy> {
+    pub strategy: S,
+    held: bool, // If the last move held the kept dice, so the next one rolls the rest
+}
+impl<S: Strategy> StrategyBot<S> {
+    pub fn new(strategy: S) -> StrategyBot<S> {
+        return StrategyBot {
+            strategy,
+            held: false,
+        };
+    }
+}
+impl<S: Strategy> Bot for StrategyBot<S> {
+    fn choose(&mut self, view: &GameView) -> Move {
+        // Always roll at the start of the turn
+        if view.rolls_left == MAX_ROLLS {
+            return Move::Roll;
+        }
+
+        if view.rolls_left > 0 {
+            // The kept dice were just held, roll the others
+            if self.held {
+                self.held = false;
+                return Move::Roll;
+            }
+
+            // If every die is worth keeping, there's nothing to roll for
+            let kept = self.strategy.choose_hold(view);
+            if !kept.iter().all(|keep| *keep) {
+                // Freeze the kept dice before rolling the others
+                let frozen: Vec<bool> = view.dice.iter().map(|die| die.frozen).collect();
+                if frozen != kept {
+                    self.held = true;
+                    let held = (0..kept.len()).filter(|i| kept[*i]).collect();
+                    return Move::Hold(held);
+                }
+                return Move::Roll;
+            }
+        }
+
+        self.held = false;
+        return Move::Score(self.strategy.choose_section(view));
+    }
+}
+
+// Somewhere a bot's game is played
+pub trait Table {
+    fn state(&mut self) -> Result<EngineState, String>;
+    fn play(&mut self, player_move: Move) -> Result<(), String>;
+}
+
+// A game played in this process
+pub struct LocalTable {
+    pub game: Game,
+}
+impl Table for LocalTable {
+    fn state(&mut self) -> Result<EngineState, String> {
+        return Ok(EngineState::new(&self.game));
+    }
+    fn play(&mut self, player_move: Move) -> Result<(), String> {
+        return self.game.play(player_move).map_err(|e| e.to_string());
+    }
+}
+
+// A game played by an engine in another process, through its JSON commands
+pub struct RemoteTable {
+    child: Child, // The engine process
+    input: ChildStdin, // Commands sent to the engine
+    output: BufReader<ChildStdout>, // Responses from the engine
+    state: EngineState, // The game as of the last response
+}
+impl RemoteTable {
+    // Start the engine program and begin a game with the seed
+    pub fn spawn(program: &Path, args: &[&str], seed: u64) -> Result<RemoteTable, String> {
+        let mut child = Process::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not start engine {}: {}", program.display(), e))?;
+
+        // Both pipes were requested above
+        let input = child.stdin.take().expect("engine stdin is piped");
+        let output = BufReader::new(child.stdout.take().expect("engine stdout is piped"));
+
+        // Start the game, the response replaces the placeholder state
+        let mut table = RemoteTable {
+            child,
+            input,
+            output,
+            state: EngineState::new(&Game::new(seed)),
+        };
+        table.send(&Command::Seed { seed })?;
+        return Ok(table);
+    }
+
+    // Send a command, keeping the state from the engine's response
+    fn send(&mut self, command: &Command) -> Result<(), String> {
+        let line = serde_json::to_string(command).map_err(|e| e.to_string())?;
+        writeln!(self.input, "{}", line).map_err(|e| format!("Engine closed: {}", e))?;
+        self.input.flush().map_err(|e| format!("Engine closed: {}", e))?;
+
+        let mut reply = String::new();
+        self.output.read_line(&mut reply).map_err(|e| format!("Engine closed: {}", e))?;
+        let response: Response = serde_json::from_str(&reply)
+            .map_err(|e| format!("Invalid engine response: {}", e))?;
+
+        self.state = response.state;
+        return match response.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        };
+    }
+}
+impl Table for RemoteTable {
+    fn state(&mut self) -> Result<EngineState, String> {
+        return Ok(self.state.clone());
+    }
+    fn play(&mut self, player_move: Move) -> Result<(), String> {
+        let command = match player_move {
+            Move::Roll => Command::Roll,
+            Move::Hold(dice) => Command::Hold { dice },
+            Move::Score(section) => Command::Score { section },
+            Move::Nudge(die, up) => Command::Nudge { die, up },
+        };
+        return self.send(&command);
+    }
+}
+impl Drop for RemoteTable {
+    fn drop(&mut self) {
+        // The engine only stops at the end of its input, so stop it directly
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// Find a bot by the name of its strategy (or its difficulty), strategies that use randomness are seeded
+pub fn bot_by_name(name: &str, seed: u64) -> Option<Box<dyn Bot>> {
+    return match name {
+        "random" => Some(Box::new(StrategyBot::new(RandomStrategy::new(seed)))),
+        "greedy" => Some(Box::new(StrategyBot::new(GreedyStrategy))),
+        "lookahead" => Some(Box::new(StrategyBot::new(LookaheadStrategy::new(seed)))),
+        "expected" | "ev" => Some(Box::new(StrategyBot::new(ExpectedValueStrategy::default()))),
+        "optimal" => Some(Box::new(StrategyBot::new(OptimalStrategy::default()))),
+        _ => AiLevel::from_name(name).map(|level| level.bot(seed)),
+    };
+}
+
+// How well the computer plays, for players who want an easier or harder game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiLevel {
+    Easy, // Scores any section worth points
+    Medium, // Scores the section worth the most points now (the greedy strategy)
+    Hard, // Works out what every choice is worth over the rest of the turn (the expected value strategy)
+}
+
+impl AiLevel {
+    // The level with this name (easy, medium, or hard)
+    pub fn from_name(name: &str) -> Option<AiLevel> {
+        return match name.to_lowercase().as_str() {
+            "easy" => Some(AiLevel::Easy),
+            "medium" => Some(AiLevel::Medium),
+            "hard" => Some(AiLevel::Hard),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            AiLevel::Easy => "easy",
+            AiLevel::Medium => "medium",
+            AiLevel::Hard => "hard",
+        };
+    }
+
+    // A bot playing at this level, seeded if it uses randomness
+    pub fn bot(&self, seed: u64) -> Box<dyn Bot> {
+        return match self {
+            AiLevel::Easy => Box::new(StrategyBot::new(EasyStrategy::new(seed))),
+            AiLevel::Medium => Box::new(StrategyBot::new(GreedyStrategy)),
+            AiLevel::Hard => Box::new(StrategyBot::new(ExpectedValueStrategy::default())),
+        };
+    }
+}
+
+// Let the bot play one turn, until it picks a section to score
+pub fn play_turn(
+    bot: &mut dyn Bot,
+    table: &mut dyn Table,
+    opponents: Vec<Vec<SectionView>>
+) -> Result<(), String> {
+    for _ in 0..MAX_TURN_MOVES {
+        let view = GameView::new(table.state()?, opponents.clone());
+
+        // The turn is over once a section is scored
+        let player_move = bot.choose(&view);
+        let scored = matches!(player_move, Move::Score(_));
+        table.play(player_move)?;
+        if scored {
+            return Ok(());
+        }
+    }
+    return Err(format!("No section was scored after {} moves", MAX_TURN_MOVES));
+}
+
+// Let the bot play turns until its game is over
+pub fn play_solitaire(bot: &mut dyn Bot, table: &mut dyn Table) -> Result<(), String> {
+    while !table.state()?.game_over {
+        play_turn(bot, table, Vec::new())?;
+    }
+    return Ok(());
+}
+
+// Let two bots take turns playing, each on its own table, returning both final totals
+pub fn play_match(
+    bots: [&mut dyn Bot; 2],
+    tables: [&mut dyn Table; 2]
+) -> Result<[i32; 2], String> {
+    let [bot_a, bot_b] = bots;
+    let [table_a, table_b] = tables;
+
+    while !table_a.state()?.game_over | !table_b.state()?.game_over {
+        // Each bot sees the other's scorecard
+        if !table_a.state()?.game_over {
+            let opponent = table_b.state()?.scorecard;
+            play_turn(bot_a, table_a, vec![opponent]).map_err(|e| format!("Bot A: {}", e))?;
+        }
+        if !table_b.state()?.game_over {
+            let opponent = table_a.state()?.scorecard;
+            play_turn(bot_b, table_b, vec![opponent]).map_err(|e| format!("Bot B: {}", e))?;
+        }
+    }
+    return Ok([table_a.state()?.total, table_b.state()?.total]);
+}
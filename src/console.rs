@@ -0,0 +1,112 @@
+// Where the terminal game reads its input and writes its output. It's the terminal unless something
+// else is used instead, ex: a script of input lines and a capture of the output, to play a game in a test
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::input;
+
+// Somewhere to read lines of input from
+pub trait Input {
+    // Read a line of input, None at the end of input
+    fn read_line(&mut self) -> std::io::Result<Option<String>>;
+}
+
+// Somewhere to write output to
+pub trait Output {
+    fn print(&mut self, text: &str);
+}
+
+// The terminal, reading stdin (in the background, once it's being read there) and writing stdout
+pub struct StdIo;
+impl Input for StdIo {
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        return input::read_line();
+    }
+}
+impl Output for StdIo {
+    fn print(&mut self, text: &str) {
+        print!("{}", text);
+        // Flush the buffer so prompts show before waiting for input
+        let _ = std::io::stdout().flush();
+    }
+}
+
+// Lines of input given ahead of time, then the end of input
+pub struct ScriptedInput {
+    lines: VecDeque<String>,
+}
+impl ScriptedInput {
+    pub fn new(lines: &[&str]) -> ScriptedInput {
+        return ScriptedInput {
+            lines: lines
+                .iter()
+                .map(|line| format!("{}\n", line))
+                .collect(),
+        };
+    }
+}
+impl Input for ScriptedInput {
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        return Ok(self.lines.pop_front());
+    }
+}
+
+// Everything written, kept to look at afterwards. Clones share the same text, so a clone can be kept
+// to read it while the other is being written to
+#[derive(Clone, Default)]
+pub struct CapturedOutput {
+    text: Rc<RefCell<String>>,
+}
+impl CapturedOutput {
+    // Everything written so far
+    pub fn text(&self) -> String {
+        return self.text.borrow().clone();
+    }
+}
+impl Output for CapturedOutput {
+    fn print(&mut self, text: &str) {
+        self.text.borrow_mut().push_str(text);
+    }
+}
+
+thread_local! {
+    static INPUT: RefCell<Box<dyn Input>> = RefCell::new(Box::new(StdIo));
+    static OUTPUT: RefCell<Box<dyn Output>> = RefCell::new(Box::new(StdIo));
+}
+
+// Read and write through these instead of the terminal, for the rest of this thread
+pub fn use_console(input: impl Input + 'static, output: impl Output + 'static) {
+    INPUT.with(|current| *current.borrow_mut() = Box::new(input));
+    OUTPUT.with(|current| *current.borrow_mut() = Box::new(output));
+}
+
+// Read a line of input, None at the end of input
+pub fn read_line() -> std::io::Result<Option<String>> {
+    return INPUT.with(|input| input.borrow_mut().read_line());
+}
+
+// Write the text, as is
+pub fn print(text: &str) {
+    OUTPUT.with(|output| output.borrow_mut().print(text));
+}
+
+// Like print!, but through the console
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        $crate::console::print(&format!($($arg)*))
+    };
+}
+
+// Like println!, but through the console
+#[macro_export]
+macro_rules! sayln {
+    () => {
+        $crate::console::print("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::console::print(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
@@ -0,0 +1,97 @@
+// Plain English descriptions of what a roll contains, to help learn the patterns by name
+use crate::die::Die;
+use crate::sections::{ counts, FaceCounts };
+
+// Numbers written as words, for counting dice
+static NUMBER_WORDS: [&str; 6] = ["no", "one", "two", "three", "four", "five"];
+
+// A count of dice as a word, ex: "three" for 3
+fn number_word(count: u8) -> String {
+    return NUMBER_WORDS.get(usize::from(count)).map_or(count.to_string(), |word| word.to_string());
+}
+
+// The items as an English list, ex: "a, b, and c"
+fn english_list(items: &[String]) -> String {
+    return match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    };
+}
+
+// The numbers shown by more than one die, most of a kind first (higher numbers first in a tie)
+fn matches(counts: &FaceCounts) -> Vec<(u8, u8)> {
+    let mut matches: Vec<(u8, u8)> = counts.counts
+        .iter()
+        .enumerate()
+        .skip(1) // Unrolled dice don't match anything
+        .map(|(face, count)| (face as u8, *count))
+        .filter(|(_, count)| *count > 1)
+        .collect();
+    matches.sort_by_key(|(face, count)| std::cmp::Reverse((*count, *face)));
+    return matches;
+}
+
+// Every pattern the dice contain, the rarest first
+pub fn patterns(dice: &[Die]) -> Vec<String> {
+    let counts = counts(dice);
+    let matches = matches(&counts);
+    let most = matches.first().map_or(1, |(_, count)| *count);
+    let pairs = matches.iter().filter(|(_, count)| *count == 2).count();
+
+    let mut patterns = Vec::new();
+    if most == 5 {
+        patterns.push("a Yahtzee".to_string());
+    }
+    if most >= 4 {
+        patterns.push("four of a kind".to_string());
+    }
+    if (most == 3) & (pairs == 1) {
+        patterns.push("a full house".to_string());
+    }
+    if most >= 3 {
+        patterns.push("three of a kind".to_string());
+    }
+    if pairs == 2 {
+        patterns.push("two pairs".to_string());
+    } else if pairs == 1 {
+        patterns.push("a pair".to_string());
+    }
+
+    // Only the longest run is worth naming, it contains the shorter ones
+    if let Some(length) = (3..=5).rev().find(|length| counts.has_run(*length)) {
+        patterns.push(format!("a run of {} numbers in a row", number_word(length)));
+    }
+    return patterns;
+}
+
+// The dice as a sentence, ex: "You have three 6s and a pair of 2s, that's a full house, three of a kind,
+// and a pair."
+pub fn describe(dice: &[Die]) -> String {
+    let counts = counts(dice);
+    let groups: Vec<String> = matches(&counts)
+        .iter()
+        .map(|(face, count)| {
+            if *count == 2 { format!("a pair of {}s", face) } else { format!("{} {}s", number_word(*count), face) }
+        })
+        .collect();
+
+    // Without any matching dice, the numbers themselves are the best description
+    let have = if groups.is_empty() {
+        let mut nums: Vec<String> = dice
+            .iter()
+            .map(|die| die.num.to_string())
+            .collect();
+        nums.sort();
+        format!("You have {}", english_list(&nums))
+    } else {
+        format!("You have {}", english_list(&groups))
+    };
+
+    let patterns = patterns(dice);
+    if patterns.is_empty() {
+        return format!("{}, nothing that makes a pattern.", have);
+    }
+    return format!("{}, that's {}.", have, english_list(&patterns));
+}
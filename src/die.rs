@@ -0,0 +1,91 @@
+use rand::{ Rng, RngCore };
+use serde::{ Deserialize, Serialize };
+
+use crate::invariant::Strictness;
+
+// Where the numbers rolled come from
+pub trait DiceSource {
+    // The number a die with this many sides rolls next, from 1 - sides (inclusive)
+    fn next_face(&mut self, sides: u8) -> u8;
+}
+
+// Any random number generator rolls dice, ex: the seeded one each game rolls with
+impl<R: RngCore> DiceSource for R {
+    fn next_face(&mut self, sides: u8) -> u8 {
+        return self.gen_range(1..=sides);
+    }
+}
+
+// Numbers given ahead of time, rolled in order and then again from the start, ex: to know what a test rolls
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptedDice {
+    faces: Vec<u8>,
+    next: usize, // The index of the next face to roll
+    pub strictness: Strictness, // How a face the die can't show is handled, the game's when it rolls them
+    pub warnings: Vec<String>, // Faces that couldn't be rolled, recovered from by rolling the nearest side
+}
+impl ScriptedDice {
+    pub fn new(faces: &[u8]) -> ScriptedDice {
+        // Without any numbers, the dice only roll 1s
+        let faces = if faces.is_empty() { vec![1] } else { faces.to_vec() };
+        return ScriptedDice { faces, next: 0, strictness: Strictness::default(), warnings: Vec::new() };
+    }
+}
+impl DiceSource for ScriptedDice {
+    fn next_face(&mut self, sides: u8) -> u8 {
+        let face = self.faces[self.next];
+        self.next = (self.next + 1) % self.faces.len();
+        if let Err(warning) = self.strictness.check((1..=sides).contains(&face), || format!("A {}-sided die can't roll {}", sides, face)) {
+            self.warnings.push(warning);
+            return face.clamp(1, sides.max(1));
+        }
+        return face;
+    }
+}
+
+// The ability to roll a random value
+pub trait Random {
+    fn roll(&mut self, source: &mut dyn DiceSource);
+}
+
+// Allow cloning of Die, used with vec![]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Die {
+    pub num: u8, // The Die's number
+    pub frozen: bool, // If the Die cannot be rolled
+    pub sides: u8, // The number of sides (highest value that can be rolled)
+}
+// Implement the default values for a DIe
+impl Default for Die {
+    fn default() -> Die {
+        // Create a default (six-sided) Die
+        return Die::new(6);
+    }
+}
+impl Die {
+    // Create an unrolled Die with the given number of sides
+    pub fn new(sides: u8) -> Die {
+        // A die without sides couldn't roll anything, so it's made an ordinary one
+        let sides = if sides > 0 { sides } else { 6 };
+        return Die {
+            num: 0,
+            frozen: false,
+            sides,
+        };
+    }
+
+    // Unfreeze and clear the Die's number, keeping its number of sides
+    pub fn reset(&mut self) {
+        self.num = 0;
+        self.frozen = false;
+    }
+}
+// Implement the functions of Random (can roll a Die)
+impl Random for Die {
+    fn roll(&mut self, source: &mut dyn DiceSource) {
+        // Randomize the die value if it isn't frozen
+        if !self.frozen {
+            self.num = source.next_face(self.sides); // 1 - sides (inclusive)
+        }
+    }
+}
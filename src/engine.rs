@@ -0,0 +1,153 @@
+// A headless engine, playing through JSON commands on stdin with JSON responses on stdout
+use serde::{ Deserialize, Serialize };
+use std::io::{ BufRead, Write };
+
+use crate::game::{ Dice, Game, Move };
+use crate::sections::ScoreSummary;
+
+// A command read from a line of input
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "lowercase")]
+pub enum Command {
+    Roll, // {"cmd":"roll"}
+    Hold { dice: Vec<usize> }, // {"cmd":"hold","dice":[0,2]}
+    Score { section: usize }, // {"cmd":"score","section":8}
+    Turn { keep: Vec<usize> }, // {"cmd":"turn","keep":[0,1]}, holds exactly these dice then rolls
+    Nudge { die: usize, up: bool }, // {"cmd":"nudge","die":2,"up":true}, under the nudge rule
+    State, // {"cmd":"state"}
+    Seed { seed: u64 }, // {"cmd":"seed","seed":42}, starts a new game
+}
+
+// A section of the scorecard, with what it would score for the current dice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionView {
+    pub name: String,
+    pub filled: bool,
+    pub points: i32,
+    pub potential: Option<i32>, // Points for the current dice, if it can be scored
+}
+
+// The game as the engine reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineState {
+    pub seed: u64,
+    pub dice: Dice,
+    pub rolls_left: u8,
+    pub scorecard: Vec<SectionView>,
+    pub total: i32,
+    pub game_over: bool,
+    #[serde(default)]
+    pub yahtzees: u32, // Yahtzees rolled this game
+    #[serde(default)]
+    pub totals: ScoreSummary, // The subtotals and bonuses making up the total
+}
+
+// The reply to every command
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub seq: u64, // Counts up by one with every response
+    pub error: Option<String>, // Why the command failed, if it did
+    pub state: EngineState,
+}
+
+impl EngineState {
+    // Describe the game's current state
+    pub fn new(game: &Game) -> EngineState {
+        // Nothing can be scored before the first roll
+        let rolled = game.dice.iter().all(|die| die.num != 0);
+
+        let scorecard = game.scorecard
+            .iter()
+            .enumerate()
+            .map(|(i, section)| SectionView {
+                name: section.get_name().to_string(),
+                filled: section.is_filled(),
+                points: section.get_points(),
+                potential: if rolled & game.can_score(i) {
+                    Some(game.potential(i))
+                } else {
+                    None
+                },
+            })
+            .collect();
+
+        return EngineState {
+            seed: game.seed,
+            dice: game.dice.clone(),
+            rolls_left: game.rolls,
+            scorecard,
+            total: game.total(),
+            game_over: !game.in_progress(),
+            yahtzees: game.yahtzees,
+            totals: game.totals(),
+        };
+    }
+}
+
+// Carry out a single line of input
+fn run_command(game: &mut Game, line: &str) -> Result<(), String> {
+    let command: Command = serde_json::from_str(line).map_err(|e| format!("Invalid command: {}", e))?;
+
+    let player_move = match command {
+        Command::Roll => Move::Roll,
+        Command::Hold { dice } => Move::Hold(dice),
+        Command::Score { section } => Move::Score(section),
+        Command::Nudge { die, up } => Move::Nudge(die, up),
+        Command::Turn { keep } => {
+            return game.turn(&keep).map_err(|e| e.to_string());
+        }
+        Command::State => {
+            return Ok(());
+        }
+        Command::Seed { seed } => {
+            // The new game keeps the same rules
+            let (joker, max_holds, odd_even) = (game.joker, game.max_holds, game.odd_even);
+            let (sum_straights, max_zeros, nudges) = (game.sum_straights, game.max_zeros, game.nudges);
+            let (sections, yahtzee_bonus, upper_bonus) = (game.sections.clone(), game.yahtzee_bonus, game.upper_bonus);
+            *game = Game::new(seed);
+            game.yahtzee_bonus = yahtzee_bonus;
+            game.upper_bonus = upper_bonus;
+            if let Some(sections) = sections {
+                game.enable_sections(&sections).map_err(|e| e.to_string())?;
+            }
+            game.joker = joker;
+            game.max_holds = max_holds;
+            game.max_zeros = max_zeros;
+            game.set_nudges(nudges);
+            if odd_even {
+                game.add_odd_even();
+            }
+            if sum_straights {
+                game.add_sum_straights();
+            }
+            return Ok(());
+        }
+    };
+    return game.play(player_move).map_err(|e| e.to_string());
+}
+
+// Answer commands until the input ends
+pub fn run_engine(mut game: Game, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    let mut seq = 0;
+
+    for line in input.lines() {
+        let line = line?;
+
+        // Skip blank lines
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let error = run_command(&mut game, &line).err();
+        let response = Response {
+            seq,
+            error,
+            state: EngineState::new(&game),
+        };
+        writeln!(output, "{}", serde_json::to_string(&response)?)?;
+        output.flush()?;
+
+        seq += 1;
+    }
+    return Ok(());
+}
@@ -0,0 +1,28 @@
+// The ways playing the game can fail, so they can be reported instead of stopping the program
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum GameError {
+    Io(io::Error), // Reading input or writing output failed
+    EndOfInput, // There was no more input to read
+    InvalidMove(String), // The move isn't allowed in the game's current state
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            GameError::Io(e) => write!(f, "Input/output failed: {}", e),
+            GameError::EndOfInput => write!(f, "There was no more input"),
+            GameError::InvalidMove(message) => write!(f, "{}", message),
+        };
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<io::Error> for GameError {
+    fn from(e: io::Error) -> GameError {
+        return GameError::Io(e);
+    }
+}
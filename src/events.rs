@@ -0,0 +1,61 @@
+// JSON snapshots of the game state, so another program can follow along
+use serde::Serialize;
+
+use crate::game::{ Dice, Game };
+use crate::sections::SectionKind;
+
+// A section of the scorecard as it currently stands
+#[derive(Serialize)]
+pub struct SectionState {
+    pub name: String,
+    pub filled: bool,
+    pub points: i32,
+    pub kind: SectionKind, // How the section is scored, with its value
+    pub value: u8,
+}
+
+// The full state of the game after something changed
+#[derive(Serialize)]
+pub struct StateEvent<'a> {
+    pub event: &'a str, // What changed ("start", "roll", "freeze", or "score")
+    pub dice: &'a Dice,
+    pub rolls_left: u8,
+    pub scorecard: Vec<SectionState>,
+    pub total: i32,
+}
+
+impl StateEvent<'_> {
+    // Take a snapshot of the game after the given event
+    pub fn new<'a>(event: &'a str, game: &'a Game) -> StateEvent<'a> {
+        let scorecard = game.scorecard
+            .iter()
+            .map(|section| {
+                let snapshot = section.snapshot();
+                SectionState {
+                    name: snapshot.0,
+                    filled: snapshot.1,
+                    points: snapshot.2,
+                    kind: snapshot.3,
+                    value: snapshot.4,
+                }
+            })
+            .collect();
+
+        return StateEvent {
+            event,
+            dice: &game.dice,
+            rolls_left: game.rolls,
+            scorecard,
+            total: game.total(),
+        };
+    }
+}
+
+// Write the game's state as a single line of JSON to stderr, keeping stdout for the player
+pub fn emit_state(event: &str, game: &Game) {
+    let state = StateEvent::new(event, game);
+    match serde_json::to_string(&state) {
+        Ok(json) => eprintln!("{}", json),
+        Err(e) => eprintln!("Could not write game state: {}", e),
+    }
+}
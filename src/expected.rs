@@ -0,0 +1,398 @@
+// A computer player that works out exactly what every way of keeping dice is worth
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::ai::{ choose_freezes, choose_section };
+use crate::bot::{ GameView, HoldMask, Strategy };
+use crate::die::Die;
+use crate::game::NUM_DICE;
+use crate::sections::{ create_scorecard, odd_even_sections, PointSection, Scorecard };
+
+// The number of sides on every die
+static SIDES: u8 = 6;
+
+// How much each point above (or below) three of a number in an upper section is worth,
+// for getting closer to the upper bonus
+static UPPER_BONUS_WEIGHT: f64 = 0.25;
+
+// Every outcome of keeping some dice and rolling the rest, worked out once and shared
+pub struct Tables {
+    hands: Vec<Vec<u8>>, // Every different hand, as its sorted numbers
+    hand_index: HashMap<Vec<u8>, usize>, // The index of each hand, by its sorted numbers
+    kept_sets: Vec<Vec<u8>>, // Every sorted set of numbers that can be kept (0 to 5 dice)
+    kept_index: HashMap<Vec<u8>, usize>, // The index of each set of kept numbers
+    outcomes: Vec<Vec<(usize, f64)>>, // For each set kept, every hand it can end in and how likely it is
+    keeps: Vec<Vec<usize>>, // For each hand, every different set of its dice that can be kept
+    names: Vec<&'static str>, // The name of every section that can be on a scorecard
+    averages: Vec<i32>, // What each section usually scores, for every section that can be on a scorecard
+    faces: Vec<Option<u8>>, // The number each upper section counts
+    pub scores: Vec<Vec<i32>>, // For each section, the points each hand scores in it
+    section_values: Vec<Vec<f64>>, // For each section, what scoring each hand in it is worth
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+// The shared tables, worked out the first time they're needed
+pub fn tables() -> &'static Tables {
+    return TABLES.get_or_init(Tables::new);
+}
+
+// Every sorted set of count numbers, each from 1 to SIDES
+fn sorted_sets(count: usize) -> Vec<Vec<u8>> {
+    let mut sets = vec![Vec::new()];
+    for _ in 0..count {
+        sets = sets
+            .into_iter()
+            .flat_map(|set: Vec<u8>| {
+                // Numbers are added in order, so each set is only made once
+                let lowest = set.last().copied().unwrap_or(1);
+                (lowest..=SIDES).map(move |num| {
+                    let mut longer = set.clone();
+                    longer.push(num);
+                    longer
+                })
+            })
+            .collect();
+    }
+    return sets;
+}
+
+// The chance of rolling exactly this sorted set of numbers, in any order
+fn chance(set: &[u8]) -> f64 {
+    // The number of orders the set can be rolled in, out of every roll of that many dice
+    let mut orders: f64 = (1..=set.len()).map(|n| n as f64).product();
+    for num in 1..=SIDES {
+        let count = set
+            .iter()
+            .filter(|n| **n == num)
+            .count();
+        orders /= (1..=count).map(|n| n as f64).product::<f64>();
+    }
+    return orders / f64::from(SIDES).powi(set.len() as i32);
+}
+
+// The dice showing a hand's numbers
+fn hand_dice(hand: &[u8]) -> Vec<Die> {
+    return hand
+        .iter()
+        .map(|num| Die { num: *num, frozen: false, sides: SIDES })
+        .collect();
+}
+
+// Every different sorted set of numbers that can be kept from a sorted hand
+fn subsets(hand: &[u8]) -> Vec<Vec<u8>> {
+    let mut sets: Vec<Vec<u8>> = (0..1 << hand.len())
+        .map(|mask: usize| {
+            (0..hand.len())
+                .filter(|i| (mask & (1 << i)) != 0)
+                .map(|i| hand[i])
+                .collect()
+        })
+        .collect();
+    sets.sort();
+    sets.dedup();
+    return sets;
+}
+
+impl Tables {
+    fn new() -> Tables {
+        let sorted_hands = sorted_sets(NUM_DICE);
+        let hand_index: HashMap<Vec<u8>, usize> = sorted_hands
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| (hand.clone(), i))
+            .collect();
+
+        // Keeping any number of dice, then rolling the rest
+        let mut kept_sets = Vec::new();
+        let mut kept_index = HashMap::new();
+        let mut outcomes = Vec::new();
+        for kept_count in 0..=NUM_DICE {
+            let rolls = sorted_sets(NUM_DICE - kept_count);
+            for kept in sorted_sets(kept_count) {
+                let ends = rolls
+                    .iter()
+                    .map(|rolled| {
+                        let mut hand: Vec<u8> = kept.iter().chain(rolled).copied().collect();
+                        hand.sort();
+                        (hand_index[&hand], chance(rolled))
+                    })
+                    .collect();
+                kept_index.insert(kept.clone(), kept_sets.len());
+                kept_sets.push(kept);
+                outcomes.push(ends);
+            }
+        }
+
+        let keeps = sorted_hands
+            .iter()
+            .map(|hand| {
+                subsets(hand)
+                    .iter()
+                    .map(|kept| kept_index[kept])
+                    .collect()
+            })
+            .collect();
+
+        // Score every hand in every section
+        let rules: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+        let mut tables = Tables {
+            hand_index,
+            kept_sets,
+            kept_index,
+            outcomes,
+            keeps,
+            names: rules
+                .iter()
+                .map(|section| section.get_name())
+                .collect(),
+            averages: rules
+                .iter()
+                .map(|section| section.average_score())
+                .collect(),
+            faces: rules
+                .iter()
+                .map(|section| section.face_value())
+                .collect(),
+            scores: rules
+                .iter()
+                .map(|section| {
+                    sorted_hands
+                        .iter()
+                        .map(|hand| section.calc_score(&hand_dice(hand)))
+                        .collect()
+                })
+                .collect(),
+            section_values: Vec::new(),
+            hands: sorted_hands,
+        };
+        tables.section_values = tables.scores
+            .iter()
+            .enumerate()
+            .map(|(section_i, scores)| {
+                scores
+                    .iter()
+                    .map(|points| tables.section_value(section_i, *points))
+                    .collect()
+            })
+            .collect();
+        return tables;
+    }
+
+    // The number of different hands of dice
+    pub fn hand_count(&self) -> usize {
+        return self.keeps.len();
+    }
+
+    // The sorted numbers of a hand, by its index
+    pub fn hand(&self, hand_i: usize) -> &[u8] {
+        return &self.hands[hand_i];
+    }
+
+    // The points each hand scores in the section, for sections that aren't in the tables
+    pub fn hand_scores(&self, section: &dyn PointSection) -> Vec<i32> {
+        return self.hands
+            .iter()
+            .map(|hand| section.calc_score(&hand_dice(hand)))
+            .collect();
+    }
+
+    // The index of the dice's hand (in any order), None if they aren't a rolled hand of NUM_DICE dice
+    pub fn hand_of(&self, dice: &[Die]) -> Option<usize> {
+        let mut hand: Vec<u8> = dice.iter().map(|die| die.num).collect();
+        hand.sort();
+        return self.hand_index.get(&hand).copied();
+    }
+
+    // The number of sections the tables have scores for
+    pub fn section_count(&self) -> usize {
+        return self.scores.len();
+    }
+
+    // The index of the section with this name in the tables, None if it isn't one of them
+    pub fn section_index(&self, name: &str) -> Option<usize> {
+        return self.names.iter().position(|section| *section == name);
+    }
+
+    // What scoring the points in the section is worth, compared to what the section usually scores
+    fn section_value(&self, section_i: usize, points: i32) -> f64 {
+        let mut value = f64::from(points - self.averages[section_i]);
+
+        // Upper sections are worth more for every point over three of their number
+        if let Some(face) = self.faces[section_i] {
+            value += UPPER_BONUS_WEIGHT * f64::from(points - 3 * i32::from(face));
+        }
+        return value;
+    }
+
+    // What each set of kept dice is worth on average, given what every hand is worth after the roll
+    pub fn kept_values(&self, hand_values: &[f64]) -> Vec<f64> {
+        return self.outcomes
+            .iter()
+            .map(|ends| {
+                ends.iter()
+                    .map(|(hand_i, chance)| chance * hand_values[*hand_i])
+                    .sum()
+            })
+            .collect();
+    }
+
+    // What each hand is worth with one more roll, keeping whichever dice are worth the most
+    pub fn hand_values(&self, kept_values: &[f64]) -> Vec<f64> {
+        return self.keeps
+            .iter()
+            .map(|keeps| {
+                keeps
+                    .iter()
+                    .map(|kept_i| kept_values[*kept_i])
+                    .fold(f64::MIN, f64::max)
+            })
+            .collect();
+    }
+
+    // What each set of kept dice is worth with one roll left, and with two, given what every
+    // hand is worth once the rolling is done
+    pub fn turn_values(&self, final_values: &[f64]) -> [Vec<f64>; 2] {
+        let one_roll = self.kept_values(final_values);
+        let two_rolls = self.kept_values(&self.hand_values(&one_roll));
+        return [one_roll, two_rolls];
+    }
+
+    // What rolling the dice is worth on average, keeping the frozen ones, given what every hand
+    // is worth after the roll
+    pub fn roll_value(&self, dice: &[Die], hand_values: &[f64]) -> f64 {
+        let mut kept: Vec<u8> = dice
+            .iter()
+            .filter(|die| die.frozen)
+            .map(|die| die.num)
+            .collect();
+        kept.sort();
+        return self.outcomes[self.kept_index[&kept]]
+            .iter()
+            .map(|(hand_i, chance)| chance * hand_values[*hand_i])
+            .sum();
+    }
+
+    // What a new turn is worth, rolling every die first
+    pub fn start_value(&self, two_rolls: &[f64]) -> f64 {
+        let hands = self.hand_values(two_rolls);
+        return self.kept_values(&hands)[self.kept_index[&Vec::new()]];
+    }
+
+    // Which of the dice to keep, given what each set kept is worth (keeping everything on a tie)
+    pub fn best_hold(&self, dice: &[Die], kept_values: &[f64]) -> HoldMask {
+        let hand_i = self.hand_of(dice).expect("the dice are a rolled hand");
+        let keeps = &self.keeps[hand_i];
+        let everything = self.kept_index[&self.hands[hand_i]];
+        let mut best = (everything, kept_values[everything]);
+        for kept_i in keeps {
+            if kept_values[*kept_i] > best.1 {
+                best = (*kept_i, kept_values[*kept_i]);
+            }
+        }
+
+        // Hold the first die showing each kept number
+        let mut kept = self.kept_sets[best.0].clone();
+        return dice
+            .iter()
+            .map(|die| {
+                match kept.iter().position(|num| *num == die.num) {
+                    Some(i) => {
+                        kept.remove(i);
+                        true
+                    }
+                    None => false,
+                }
+            })
+            .collect();
+    }
+}
+
+// What keeping each set of dice is worth, for the open sections it was worked out for
+pub struct TurnValues {
+    pub open: Vec<bool>, // Which sections were open
+    pub kept: [Vec<f64>; 2], // What each set kept is worth with one roll left, and with two
+}
+
+// Keeps the dice with the best expected score over the rolls left, and scores the open section
+// worth the most compared to what it usually scores
+#[derive(Default)]
+pub struct ExpectedValueStrategy {
+    turn: Option<TurnValues>, // Worked out once for each set of open sections
+}
+impl ExpectedValueStrategy {
+    // Work out the values for the open sections, if they haven't been already
+    fn turn_values(&mut self, open: Vec<bool>) -> &TurnValues {
+        let tables = tables();
+        let worked_out = match &self.turn {
+            Some(turn) => turn.open == open,
+            None => false,
+        };
+        if !worked_out {
+            // Each hand is worth its best open section
+            let scored: Vec<f64> = (0..tables.hand_count())
+                .map(|hand_i| {
+                    (0..open.len())
+                        .filter(|section_i| open[*section_i])
+                        .map(|section_i| tables.section_values[section_i][hand_i])
+                        .fold(f64::MIN, f64::max)
+                })
+                .collect();
+
+            self.turn = Some(TurnValues { kept: tables.turn_values(&scored), open });
+        }
+        return self.turn.as_ref().expect("the turn's values were just worked out");
+    }
+}
+// The index in the tables of each of the view's sections (found by name, so a scorecard can leave
+// some out), None if any of them aren't in the tables
+fn table_sections(view: &GameView) -> Option<Vec<usize>> {
+    return view.scorecard
+        .iter()
+        .map(|section| tables().section_index(&section.name))
+        .collect();
+}
+
+impl Strategy for ExpectedValueStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        // Without the rules for every section, fall back to keeping the most common number
+        let table_sections = match table_sections(view) {
+            Some(table_sections) => table_sections,
+            None => {
+                return choose_freezes(&view.dice);
+            }
+        };
+
+        // Sections left off the scorecard are never open
+        let mut open = vec![false; tables().section_count()];
+        for (section, table_i) in view.scorecard.iter().zip(table_sections) {
+            open[table_i] = !section.filled;
+        }
+        let rolls_left = usize::from(view.rolls_left.clamp(1, 2));
+        let kept_values = &self.turn_values(open).kept[rolls_left - 1];
+        return tables().best_hold(&view.dice, kept_values);
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        // Without the rules for every section, fall back to the most points
+        let tables = tables();
+        let table_sections = match table_sections(view) {
+            Some(table_sections) => table_sections,
+            None => {
+                return choose_section(&view.scorecard);
+            }
+        };
+
+        let mut best_i = None;
+        let mut best_value = f64::MIN;
+        for (section_i, section) in view.scorecard.iter().enumerate() {
+            if let Some(points) = section.potential {
+                let value = tables.section_value(table_sections[section_i], points);
+                if best_i.is_none() | (value > best_value) {
+                    best_i = Some(section_i);
+                    best_value = value;
+                }
+            }
+        }
+        return best_i.expect("no open section to pick");
+    }
+}
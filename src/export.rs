@@ -0,0 +1,55 @@
+// Exporting finished scorecards as CSV, one column per player
+use crate::game::Game;
+use crate::sections::ScoreSummary;
+
+// Quote a field if it contains anything CSV treats specially
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    return field.to_string();
+}
+
+// Join fields into a line of CSV
+pub fn csv_line(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect();
+    return fields.join(",") + "\n";
+}
+
+// The scorecards as CSV: a row for every section (with if it was scratched), then the totals
+pub fn scorecard_csv(players: &[(&str, &Game)]) -> String {
+    let mut header = vec!["Section".to_string()];
+    for (player, _) in players {
+        header.push(player.to_string());
+        header.push(format!("{} scratched", player));
+    }
+    let mut csv = csv_line(&header);
+
+    // Every player has the same sections, in the same order
+    let sections = players.first().map_or(0, |(_, game)| game.scorecard.len());
+    for section_i in 0..sections {
+        let mut row = vec![players[0].1.scorecard[section_i].get_name().to_string()];
+        for (_, game) in players {
+            // A section filled with nothing was scratched
+            let section = &game.scorecard[section_i];
+            row.push(section.get_points().to_string());
+            row.push((section.is_filled() & (section.get_points() == 0)).to_string());
+        }
+        csv += &csv_line(&row);
+    }
+
+    // The totals, which leave the scratched column empty
+    let names = ScoreSummary::default().rows().map(|(name, _)| name);
+    for (total_i, name) in names.iter().enumerate() {
+        let mut row = vec![name.to_string()];
+        for (_, game) in players {
+            row.push(game.totals().rows()[total_i].1.to_string());
+            row.push(String::new());
+        }
+        csv += &csv_line(&row);
+    }
+    return csv;
+}
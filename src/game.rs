@@ -0,0 +1,752 @@
+use rand::{ Rng, SeedableRng };
+use rand_chacha::ChaCha12Rng;
+use serde::{ Deserialize, Serialize };
+use std::cell::RefCell;
+
+use crate::achievements::Achievement;
+use crate::die::{ DiceSource, Die, Random, ScriptedDice };
+use crate::invariant::Strictness;
+use crate::error::GameError;
+use crate::sections::{
+    build_scorecard,
+    create_scorecard,
+    empty_section,
+    odd_even_sections,
+    sum_straight,
+    summarize,
+    Scorecard,
+    ScoreSummary,
+    SectionKind,
+    UpperBonus,
+    YAHTZEE_BONUS,
+};
+
+// The number of rolls the player starts each round with
+pub static MAX_ROLLS: u8 = 3;
+
+// The number of dice rolled each turn (a const, so it can size the dice)
+pub const NUM_DICE: usize = 5;
+
+// The dice of a game, always exactly NUM_DICE of them
+pub type Dice = [Die; NUM_DICE];
+
+// A hand is strong in a section when it scores at least this fraction of the section's most points
+static STRONG_FRACTION: f64 = 0.8;
+
+// Only sections worth at least this many points at most are worth warning about losing
+static STRONG_MIN_POINTS: i32 = 25;
+
+/// Something a player can do on their turn
+///
+/// ```
+/// use yahtzee::game::{ Game, Move };
+///
+/// let mut game = Game::new(7);
+/// game.play(Move::Roll).unwrap();
+/// game.play(Move::Hold(vec![0, 1])).unwrap();
+/// game.play(Move::Roll).unwrap();
+/// game.play(Move::Score(12)).unwrap();
+/// assert_eq!(game.turn, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Move {
+    Roll, // Roll the unfrozen dice
+    Hold(Vec<usize>), // Freeze exactly the dice at these indices, unfreezing the rest
+    Score(usize), // Fill the section at this index, ending the turn
+    Nudge(usize, bool), // Change the die at this index up (true) or down (false) by one, using up a nudge
+}
+
+// Something that happened in the game, kept in order in the game's log
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    Rolled(Dice), // The dice after a roll
+    Froze(usize, bool), // A die was frozen (true) or unfrozen (false)
+    Held(Vec<usize>), // Exactly these dice were frozen
+    Scored(usize, i32), // A section was filled with the points, ending the turn
+    Nudged(usize, bool), // A die was nudged up (true) or down (false) by one
+}
+
+// The dice and rolls partway through a turn, to go back to after trying some rolls (practice mode)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnSnapshot {
+    pub dice: Dice, // The dice, with their frozen states
+    pub rolls: u8, // Rolls left in the turn
+    pub turn: u32, // The turn it was taken in
+    yahtzees: u32, // Yahtzees rolled before it was taken
+    rolls_used: u32, // Rolls made this game before it was taken
+    nudges_remaining: u32, // Nudges left when it was taken
+    log_len: usize, // The events before it was taken
+}
+
+/// The state of a game, independent of how it is displayed or played
+///
+/// ```
+/// use yahtzee::game::Game;
+///
+/// // Roll once every turn and score the first open section
+/// let mut game = Game::new(42);
+/// while game.in_progress() {
+///     game.roll().unwrap();
+///     let open = game.scorecard.iter().position(|section| !section.is_filled()).unwrap();
+///     game.score(open).unwrap();
+/// }
+/// assert_eq!(game.rolls_used, 13);
+/// ```
+pub struct Game {
+    pub scorecard: Scorecard, // Every section the player can fill
+    pub dice: Dice, // The dice rolled each turn
+    pub rolls: u8, // The number of rolls the player has left
+    pub seed: u64, // The seed the game's dice rolls come from
+    pub joker: bool, // If a Yahtzee must be scored in its open upper section (joker rules)
+    pub max_holds: Option<usize>, // The most dice that can be frozen at once (limited holds rule)
+    pub max_zeros: Option<u32>, // The most sections that can be scored at 0 (zero limit rule)
+    pub nudges: u32, // The nudges allowed each game (nudge rule)
+    pub nudges_remaining: u32, // The nudges left this game
+    pub yahtzee_bonus: i32, // The points for each Yahtzee bonus
+    pub upper_bonus: UpperBonus, // The upper subtotal that earns the upper bonus, and its points
+    pub sections: Option<Vec<u8>>, // The numbers of the standard sections on the scorecard, if not all of them
+    pub odd_even: bool, // If the Evens and Odds sections are on the scorecard
+    pub sum_straights: bool, // If straights score the total of their dice instead of a fixed amount
+    pub strictness: Strictness, // If a broken invariant (a bug) panics, or is warned about and recovered from
+    pub warnings: Vec<String>, // The broken invariants recovered from, until they're taken to be reported
+    pub yahtzees: u32, // The number of Yahtzees rolled this game
+    pub rolls_used: u32, // The number of rolls made this game, fewer is a more efficient game
+    pub achievements: Vec<(u32, Achievement)>, // Everything achieved this game, with the turn it was in
+    pub turn: u32, // The current turn, counting from 1
+    pub log: Vec<GameEvent>, // Everything that has happened this game
+    summary: ScoreSummary, // The scorecard's totals, kept up to date as sections are scored
+    potential: RefCell<Vec<Option<i32>>>, // What each section would score for the dice, worked out when first needed
+    rng: ChaCha12Rng, // Rolls the dice, the same seed always gives the same rolls
+    scripted: Option<ScriptedDice>, // Rolls the dice instead of the seed, if given
+}
+
+impl Game {
+    /// Create a new game, with dice rolls determined by the seed
+    ///
+    /// ```
+    /// use yahtzee::game::Game;
+    ///
+    /// let (mut a, mut b) = (Game::new(3), Game::new(3));
+    /// a.roll().unwrap();
+    /// b.roll().unwrap();
+    /// assert_eq!(a.dice, b.dice);
+    /// ```
+    pub fn new(seed: u64) -> Game {
+        return Game {
+            scorecard: create_scorecard(),
+            dice: std::array::from_fn(|_| Die::default()),
+            rolls: MAX_ROLLS,
+            seed,
+            joker: false,
+            max_holds: None,
+            max_zeros: None,
+            nudges: 0,
+            nudges_remaining: 0,
+            yahtzee_bonus: YAHTZEE_BONUS,
+            upper_bonus: UpperBonus::default(),
+            sections: None,
+            odd_even: false,
+            sum_straights: false,
+            strictness: Strictness::default(),
+            warnings: Vec::new(),
+            yahtzees: 0,
+            rolls_used: 0,
+            achievements: Vec::new(),
+            turn: 1,
+            log: Vec::new(),
+            summary: ScoreSummary::default(),
+            potential: RefCell::new(Vec::new()),
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            scripted: None,
+        };
+    }
+
+    // Roll these numbers instead of the seed's rolls, ex: to know what a test rolls. Saves and replays
+    // only know the seed, so they won't roll the same
+    pub fn script_dice(&mut self, mut dice: ScriptedDice) {
+        dice.strictness = self.strictness;
+        self.scripted = Some(dice);
+    }
+
+    // The broken invariants recovered from since they were last taken, to be reported
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        return std::mem::take(&mut self.warnings);
+    }
+
+    // Create a new game with a random seed
+    pub fn random() -> Game {
+        return Game::new(rand::thread_rng().gen());
+    }
+
+    // Fill the given number of lowest-value sections with their average scores
+    pub fn handicap(&mut self, count: usize) -> Result<(), GameError> {
+        // At least one section must be left to play
+        if count >= self.scorecard.len() {
+            return Err(
+                GameError::InvalidMove(
+                    format!("The handicap must be less than {} sections", self.scorecard.len())
+                )
+            );
+        }
+
+        // Order the sections by their average score, lowest first (ties in scorecard order)
+        let mut order: Vec<usize> = (0..self.scorecard.len()).collect();
+        order.sort_by_key(|i| self.scorecard[*i].average_score());
+
+        for section_i in order.into_iter().take(count) {
+            let average = self.scorecard[section_i].average_score();
+            self.scorecard[section_i].set_handicap(average).map_err(GameError::InvalidMove)?;
+            self.summary.add(self.scorecard[section_i].face_value().is_some(), average, self.upper_bonus);
+        }
+        return Ok(());
+    }
+
+    // Play with only these standard sections, by their numbers (ex: 1 to 6 for only the upper
+    // sections), before any other rules change the scorecard
+    pub fn enable_sections(&mut self, numbers: &[u8]) -> Result<(), GameError> {
+        let scorecard = build_scorecard(numbers);
+        if scorecard.is_empty() {
+            return Err(GameError::InvalidMove("At least one section must be on the scorecard".to_string()));
+        }
+
+        let mut numbers = numbers.to_vec();
+        numbers.sort();
+        numbers.dedup();
+        self.scorecard = scorecard;
+        self.sections = Some(numbers);
+        self.dice_changed();
+        return Ok(());
+    }
+
+    // Add the Evens and Odds sections to the end of the scorecard (the odd/even variant)
+    pub fn add_odd_even(&mut self) {
+        if !self.odd_even {
+            self.scorecard.extend(odd_even_sections());
+            self.odd_even = true;
+            self.dice_changed();
+        }
+    }
+
+    // Score the straights by the total of their dice (the sum straights house rule), before any are filled
+    pub fn add_sum_straights(&mut self) {
+        for section in &mut self.scorecard {
+            if let Some(straight) = sum_straight(section.as_ref()) {
+                *section = straight;
+            }
+        }
+        self.sum_straights = true;
+        self.dice_changed();
+    }
+
+    // How far through the seed's rolls the game is, for saving
+    pub fn rng_position(&self) -> u128 {
+        return self.rng.get_word_pos();
+    }
+
+    // Continue the seed's rolls from a saved position
+    pub fn set_rng_position(&mut self, position: u128) {
+        self.rng.set_word_pos(position);
+    }
+
+    // Set the dice to the given faces and frozen states, as if they were just rolled with the given
+    // rolls left. For puzzles and tests, where the dice are chosen instead of rolled from the seed
+    pub fn set_dice(&mut self, dice: &[Die], rolls: u8) -> Result<(), GameError> {
+        if dice.len() != NUM_DICE {
+            return Err(GameError::InvalidMove(format!("There must be {} dice", NUM_DICE)));
+        }
+        if let Some(die) = dice.iter().find(|die| !(1..=die.sides).contains(&die.num)) {
+            return Err(
+                GameError::InvalidMove(format!("A die can't show {} (1 to {})", die.num, die.sides))
+            );
+        }
+        if rolls >= MAX_ROLLS {
+            return Err(
+                GameError::InvalidMove(format!("Set dice have been rolled, so at most {} rolls are left", MAX_ROLLS - 1))
+            );
+        }
+
+        self.dice.clone_from_slice(dice);
+        self.rolls = rolls;
+        self.dice_changed();
+        return Ok(());
+    }
+
+    /// Roll every unfrozen die, using up one of the turn's rolls
+    ///
+    /// ```
+    /// use yahtzee::game::{ Game, MAX_ROLLS };
+    ///
+    /// let mut game = Game::new(1);
+    /// game.roll().unwrap();
+    /// assert_eq!(game.rolls, MAX_ROLLS - 1);
+    /// assert!(game.dice.iter().all(|die| (1..=6).contains(&die.num)));
+    /// ```
+    pub fn roll(&mut self) -> Result<(), GameError> {
+        // More rolls than a turn has would never run out, so they're cut back to a full turn's
+        if let Err(warning) = self.strictness.check(self.rolls <= MAX_ROLLS, || format!("{} rolls left, a turn only has {}", self.rolls, MAX_ROLLS)) {
+            self.warnings.push(warning);
+            self.rolls = MAX_ROLLS;
+        }
+        if self.rolls == 0 {
+            return Err(GameError::InvalidMove("No rolls left, pick a score section".to_string()));
+        }
+        // Rolling with every die frozen would change nothing, so it doesn't use up the roll
+        if self.frozen_count() == self.dice.len() {
+            return Err(GameError::InvalidMove("Every die is frozen, unfreeze some to roll".to_string()));
+        }
+        self.rolls -= 1;
+        self.rolls_used += 1;
+        let was_yahtzee = self.is_yahtzee();
+
+        // For every die in the vector,
+        let source: &mut dyn DiceSource = match &mut self.scripted {
+            Some(scripted) => scripted,
+            None => &mut self.rng,
+        };
+        for die in &mut self.dice {
+            die.roll(source);
+        }
+        if let Some(scripted) = &mut self.scripted {
+            self.warnings.append(&mut scripted.warnings);
+        }
+        self.dice_changed();
+
+        // Rolling a Yahtzee counts it, but only once: rolling the unfrozen dice of one to the same
+        // numbers doesn't roll another
+        if self.is_yahtzee() & !was_yahtzee {
+            self.yahtzees += 1;
+        }
+        self.log.push(GameEvent::Rolled(self.dice.clone()));
+        return Ok(());
+    }
+
+    // Unfreeze every die and roll them all, using up one of the turn's rolls
+    pub fn reroll_all(&mut self) -> Result<(), GameError> {
+        if self.rolls == 0 {
+            return Err(GameError::InvalidMove("No rolls left, pick a score section".to_string()));
+        }
+
+        // Log the unfreezing as holding no dice, so it can be replayed
+        if self.frozen_count() > 0 {
+            for die in &mut self.dice {
+                die.frozen = false;
+            }
+            self.log.push(GameEvent::Held(Vec::new()));
+        }
+        return self.roll();
+    }
+
+    // The dice as the next roll would leave them, without using up the roll or the seed's rolls
+    pub fn peek_roll(&self) -> Dice {
+        let mut rng = self.rng.clone();
+        let mut scripted = self.scripted.clone();
+        let source: &mut dyn DiceSource = match &mut scripted {
+            Some(scripted) => scripted,
+            None => &mut rng,
+        };
+        let mut dice = self.dice.clone();
+        for die in &mut dice {
+            die.roll(source);
+        }
+        return dice;
+    }
+
+    // Allow this many nudges every game, starting with this one
+    pub fn set_nudges(&mut self, count: u32) {
+        self.nudges = count;
+        self.nudges_remaining = count;
+    }
+
+    // Change a rolled die's number up or down by one, using up one of the game's nudges
+    pub fn nudge(&mut self, die_i: usize, up: bool) -> Result<(), GameError> {
+        if self.nudges_remaining == 0 {
+            return Err(GameError::InvalidMove("There are no nudges left this game".to_string()));
+        }
+        if self.rolls == MAX_ROLLS {
+            return Err(GameError::InvalidMove("Roll before nudging a die".to_string()));
+        }
+        let die = self.dice
+            .get_mut(die_i)
+            .ok_or(GameError::InvalidMove(format!("There is no die {}", die_i)))?;
+
+        // The die's number can't go past its lowest or highest side
+        if up & (die.num == die.sides) {
+            return Err(GameError::InvalidMove(format!("A die showing {} can't go any higher", die.num)));
+        }
+        if !up & (die.num == 1) {
+            return Err(GameError::InvalidMove("A die showing 1 can't go any lower".to_string()));
+        }
+        die.num = if up { die.num + 1 } else { die.num - 1 };
+        self.dice_changed();
+
+        self.nudges_remaining -= 1;
+        self.log.push(GameEvent::Nudged(die_i, up));
+        return Ok(());
+    }
+
+    // The number of dice currently frozen
+    pub fn frozen_count(&self) -> usize {
+        return self.dice
+            .iter()
+            .filter(|die| die.frozen)
+            .count();
+    }
+
+    // Check the number of dice can be frozen under the limited holds rule
+    fn check_holds(&self, count: usize) -> Result<(), GameError> {
+        return match self.max_holds {
+            Some(max) if count > max => {
+                Err(GameError::InvalidMove(format!("Only {} dice can be frozen at once", max)))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    // Invert the frozen state of the Die at the given index
+    pub fn toggle_freeze(&mut self, die_i: usize) -> Result<(), GameError> {
+        if die_i >= self.dice.len() {
+            return Err(GameError::InvalidMove(format!("There is no die {}", die_i)));
+        }
+
+        // Unfreezing always works, freezing has to stay within the limit
+        if !self.dice[die_i].frozen {
+            self.check_holds(self.frozen_count() + 1)?;
+        }
+        self.dice[die_i].frozen = !self.dice[die_i].frozen;
+        self.log.push(GameEvent::Froze(die_i, self.dice[die_i].frozen));
+        return Ok(());
+    }
+
+    // If every die shows the same (rolled) number
+    pub fn is_yahtzee(&self) -> bool {
+        let first = self.dice[0].num;
+        return (first != 0) & self.dice.iter().all(|die| die.num == first);
+    }
+
+    // Under joker rules, the upper section a Yahtzee must be scored in (if still open)
+    pub fn forced_section(&self) -> Option<usize> {
+        if !self.joker | !self.is_yahtzee() {
+            return None;
+        }
+
+        // Find the open upper section for the Yahtzee's number
+        let face = self.dice[0].num;
+        return self.scorecard
+            .iter()
+            .position(|section| (section.face_value() == Some(face)) & !section.is_filled());
+    }
+
+    // The number of sections the player has scored at 0 (not counting the handicap)
+    pub fn zeros_scored(&self) -> u32 {
+        let zeros = self.scorecard
+            .iter()
+            .filter(|section| section.is_filled() & !section.is_handicap() & (section.get_points() == 0))
+            .count();
+        return zeros as u32;
+    }
+
+    // Under the zero limit rule, if scoring 0 in the section would be one zero too many
+    pub fn zero_blocked(&self, section_i: usize) -> bool {
+        let at_limit = match self.max_zeros {
+            Some(max) => self.zeros_scored() >= max,
+            None => false,
+        };
+        if !at_limit | (self.potential(section_i) != 0) {
+            return false;
+        }
+
+        // Another zero is allowed when no open section would score any points
+        return (0..self.scorecard.len()).any(|i| !self.scorecard[i].is_filled() & (self.potential(i) > 0));
+    }
+
+    // The open sections the rolled dice already score close to the most possible in, strongest first.
+    // Rolling again risks losing these, which coaching warns about
+    pub fn strong_sections(&self) -> Vec<usize> {
+        if self.rolls == MAX_ROLLS {
+            return Vec::new();
+        }
+
+        let mut strong: Vec<(usize, f64)> = self.scorecard
+            .iter()
+            .enumerate()
+            .filter(|(section_i, section)| {
+                (section.max_possible_score() >= STRONG_MIN_POINTS) & self.can_score(*section_i)
+            })
+            .map(|(section_i, section)| {
+                let points = self.potential(section_i);
+                (section_i, f64::from(points) / f64::from(section.max_possible_score()))
+            })
+            .filter(|(_, fraction)| *fraction >= STRONG_FRACTION)
+            .collect();
+        strong.sort_by(|a, b| b.1.total_cmp(&a.1));
+        return strong
+            .into_iter()
+            .map(|(section_i, _)| section_i)
+            .collect();
+    }
+
+    // What the section at the given index would score for the current dice, worked out once for
+    // each roll and shared by everything that shows or weighs it
+    pub fn potential(&self, section_i: usize) -> i32 {
+        let mut potential = self.potential.borrow_mut();
+        if potential.len() != self.scorecard.len() {
+            potential.clear();
+            potential.resize(self.scorecard.len(), None);
+        }
+        return *potential[section_i].get_or_insert_with(|| self.scorecard[section_i].calc_score(&self.dice));
+    }
+
+    // What every section would score for the current dice, in scorecard order
+    pub fn potential_scores(&self) -> Vec<i32> {
+        return (0..self.scorecard.len()).map(|section_i| self.potential(section_i)).collect();
+    }
+
+    // Forget the potential scores, whenever the dice (or how sections are scored) change
+    fn dice_changed(&mut self) {
+        self.potential.get_mut().clear();
+    }
+
+    // The points the dice would score in each section that can be filled, by index
+    pub fn preview_scores(&self) -> Vec<(usize, i32)> {
+        return (0..self.scorecard.len())
+            .filter(|section_i| self.can_score(*section_i))
+            .map(|section_i| (section_i, self.potential(section_i)))
+            .collect();
+    }
+
+    // If the section at the given index can be filled with the current dice
+    pub fn can_score(&self, section_i: usize) -> bool {
+        if self.scorecard[section_i].is_filled() | self.zero_blocked(section_i) {
+            return false;
+        }
+
+        // A forced section must be picked over all others
+        return match self.forced_section() {
+            Some(forced_i) => forced_i == section_i,
+            None => true,
+        };
+    }
+
+    /// Fill the section at the given index with the current dice, ending the turn
+    ///
+    /// ```
+    /// use yahtzee::game::Game;
+    ///
+    /// let mut game = Game::new(1);
+    /// game.roll().unwrap();
+    /// let points = game.potential(12);
+    /// game.score(12).unwrap();
+    /// assert_eq!(game.scorecard[12].get_points(), points);
+    /// assert!(game.score(12).is_err());
+    /// ```
+    pub fn score(&mut self, section_i: usize) -> Result<(), GameError> {
+        // Check the section can be filled
+        if section_i >= self.scorecard.len() {
+            return Err(GameError::InvalidMove(format!("There is no section {}", section_i)));
+        }
+        if !self.can_score(section_i) {
+            let name = self.scorecard[section_i].get_name();
+            return Err(GameError::InvalidMove(format!("{} can't be scored", name)));
+        }
+
+        // Another Yahtzee, once the YAHTZEE section has been filled with points, earns a bonus
+        let yahtzee_filled = self.scorecard
+            .iter()
+            .any(|section| {
+                (section.kind() == (SectionKind::OfAKind, NUM_DICE as u8)) &
+                    section.is_filled() &
+                    !section.is_handicap() &
+                    (section.get_points() > 0)
+            });
+        if self.is_yahtzee() & yahtzee_filled {
+            self.summary.add_yahtzee_bonus(self.yahtzee_bonus);
+        }
+
+        // A Yahtzee is natural when the turn's only roll gave it, without nudging any dice
+        let nudged = self.log
+            .iter()
+            .rev()
+            .take_while(|event| !matches!(event, GameEvent::Scored(..)))
+            .any(|event| matches!(event, GameEvent::Nudged(..)));
+        let natural = self.is_yahtzee() & (self.rolls == MAX_ROLLS - 1) & !nudged;
+        let had_upper_bonus = self.summary.upper_bonus > 0;
+
+        // Calculate and set the score
+        let points = self.scorecard[section_i].score_section(&self.dice).map_err(GameError::InvalidMove)?;
+        self.summary.add(self.scorecard[section_i].face_value().is_some(), points, self.upper_bonus);
+        self.log.push(GameEvent::Scored(section_i, points));
+
+        // Anything it achieved, in the order it happened
+        let kind = self.scorecard[section_i].kind();
+        let mut achieved = Vec::new();
+        if natural & (kind == (SectionKind::OfAKind, NUM_DICE as u8)) & (points > 0) {
+            achieved.push(Achievement::NaturalYahtzee);
+        }
+        if matches!(kind, (SectionKind::Straight | SectionKind::SumStraight, 4)) & (points > 0) {
+            achieved.push(Achievement::LargeStraight);
+        }
+        if !had_upper_bonus & (self.summary.upper_bonus > 0) {
+            achieved.push(Achievement::UpperBonus);
+        }
+        for achievement in achieved {
+            self.achievements.push((self.turn, achievement));
+        }
+
+        // Reset for next turn
+        self.reset_turn();
+        return Ok(());
+    }
+
+    // Remember the dice and rolls as they are now, to come back to later in the turn
+    pub fn snapshot_turn(&self) -> TurnSnapshot {
+        return TurnSnapshot {
+            dice: self.dice.clone(),
+            rolls: self.rolls,
+            turn: self.turn,
+            yahtzees: self.yahtzees,
+            rolls_used: self.rolls_used,
+            nudges_remaining: self.nudges_remaining,
+            log_len: self.log.len(),
+        };
+    }
+
+    // Put the dice and rolls back as they were in the snapshot, undoing everything since it was
+    // taken. The seed's rolls carry on, so rolling again doesn't roll the same dice
+    pub fn restore_turn(&mut self, snapshot: &TurnSnapshot) -> Result<(), GameError> {
+        if snapshot.turn != self.turn {
+            return Err(GameError::InvalidMove(format!("The snapshot was taken on turn {}", snapshot.turn)));
+        }
+        self.dice = snapshot.dice.clone();
+        self.rolls = snapshot.rolls;
+        self.yahtzees = snapshot.yahtzees;
+        self.rolls_used = snapshot.rolls_used;
+        self.nudges_remaining = snapshot.nudges_remaining;
+        self.log.truncate(snapshot.log_len);
+        self.dice_changed();
+        return Ok(());
+    }
+
+    // The number of sections left to fill
+    pub fn sections_left(&self) -> usize {
+        return self.scorecard
+            .iter()
+            .filter(|section| !section.is_filled())
+            .count();
+    }
+
+    // If there are still sections left to fill
+    pub fn in_progress(&self) -> bool {
+        return empty_section(&self.scorecard);
+    }
+
+    // The total points, from every scorecard section and bonus
+    pub fn total(&self) -> i32 {
+        return self.summary.total;
+    }
+
+    // The subtotals, bonuses, and total
+    pub fn totals(&self) -> ScoreSummary {
+        return self.summary;
+    }
+
+    // Work the totals out again after the scorecard was filled some other way (ex: loaded from a save)
+    pub fn recount_totals(&mut self, yahtzee_bonuses: u32) {
+        self.summary = summarize(&self.scorecard, yahtzee_bonuses, self.yahtzee_bonus, self.upper_bonus);
+    }
+
+    /// Check the move is allowed right now, then make it
+    ///
+    /// ```
+    /// use yahtzee::game::{ Game, Move };
+    ///
+    /// // Dice can't be held before they're rolled
+    /// let mut game = Game::new(1);
+    /// assert!(game.play(Move::Hold(vec![0])).is_err());
+    /// ```
+    pub fn play(&mut self, player_move: Move) -> Result<(), GameError> {
+        if !self.in_progress() {
+            return Err(GameError::InvalidMove("The game is over".to_string()));
+        }
+
+        match player_move {
+            Move::Roll => {
+                self.roll()?;
+            }
+            Move::Hold(held) => {
+                if self.rolls == MAX_ROLLS {
+                    return Err(GameError::InvalidMove("Roll before freezing dice".to_string()));
+                }
+                if let Some(die_i) = held.iter().find(|die_i| **die_i >= self.dice.len()) {
+                    return Err(GameError::InvalidMove(format!("There is no die {}", die_i)));
+                }
+                let mut dice_held = held.clone();
+                dice_held.sort();
+                dice_held.dedup();
+                self.check_holds(dice_held.len())?;
+
+                // Freeze only the held dice
+                for (die_i, die) in self.dice.iter_mut().enumerate() {
+                    die.frozen = held.contains(&die_i);
+                }
+                self.log.push(GameEvent::Held(dice_held));
+            }
+            Move::Score(section_i) => {
+                if self.rolls == MAX_ROLLS {
+                    return Err(
+                        GameError::InvalidMove("Roll before picking a score section".to_string())
+                    );
+                }
+                self.score(section_i)?;
+            }
+            Move::Nudge(die_i, up) => {
+                self.nudge(die_i, up)?;
+            }
+        }
+        return Ok(());
+    }
+
+    // Freeze exactly the dice at these indices (unfreezing the rest) and roll, as a single move
+    pub fn turn(&mut self, keep: &[usize]) -> Result<(), GameError> {
+        // Check the roll can be made before changing which dice are frozen
+        if self.rolls == 0 {
+            return Err(GameError::InvalidMove("No rolls left, pick a score section".to_string()));
+        }
+
+        // Nothing can be kept before the first roll
+        if self.rolls < MAX_ROLLS {
+            self.play(Move::Hold(keep.to_vec()))?;
+        } else if !keep.is_empty() {
+            return Err(GameError::InvalidMove("Roll before freezing dice".to_string()));
+        }
+        return self.play(Move::Roll);
+    }
+
+    // Empty the scorecard for a new game, rolling from the next seed
+    pub fn restart(&mut self) {
+        for section in &mut self.scorecard {
+            section.clear_score();
+        }
+        self.yahtzees = 0;
+        self.rolls_used = 0;
+        self.achievements.clear();
+        self.nudges_remaining = self.nudges;
+        self.summary = ScoreSummary::default();
+        self.log.clear();
+        self.seed = self.seed.wrapping_add(1);
+        self.rng = ChaCha12Rng::seed_from_u64(self.seed);
+        self.reset_turn();
+        self.turn = 1;
+    }
+
+    // Reset for the next turn
+    fn reset_turn(&mut self) {
+        self.rolls = MAX_ROLLS;
+        self.turn += 1;
+
+        // Reset all the Die (unfreeze and set to 0)
+        for die in &mut self.dice {
+            die.reset();
+        }
+        self.dice_changed();
+    }
+}
@@ -0,0 +1,219 @@
+// The best finished games, kept in a versioned JSON file
+use serde::{ Deserialize, Serialize };
+use std::path::{ Path, PathBuf };
+
+use crate::game::Game;
+use crate::results::timestamp_now;
+use crate::sections::{ UpperBonus, YAHTZEE_BONUS };
+
+// Where the high scores are kept
+pub static HIGHSCORE_FILE: &str = "highscores.json";
+
+// The version of the file format written by this version of the game
+static VERSION: u32 = 2;
+
+// The number of games kept in the table
+static MAX_ENTRIES: usize = 10;
+
+// A single finished game in the table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: i32, // The final game score
+    pub variant: String, // The rules the game was played with
+    pub player: String, // Name of the player
+    pub timestamp: u64, // When the game finished (seconds since the Unix epoch)
+    pub seed: u64, // The seed the game's dice rolls came from
+    #[serde(default)]
+    pub rolls_used: u32, // The rolls made in the game (missing from version 1 tables)
+}
+
+impl HighScore {
+    // Create an entry for a game finishing now
+    pub fn new(game: &Game, player: &str) -> HighScore {
+        return HighScore {
+            score: game.total(),
+            variant: variant(game),
+            player: player.to_string(),
+            timestamp: timestamp_now(),
+            seed: game.seed,
+            rolls_used: game.rolls_used,
+        };
+    }
+}
+
+// The tables of the best games: the highest scores, and the most efficient games
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub version: u32, // The file format's version
+    pub entries: Vec<HighScore>, // Highest score first
+    pub efficient: Vec<HighScore>, // Fewest rolls first, then highest score
+}
+
+impl Default for HighScores {
+    fn default() -> HighScores {
+        return HighScores {
+            version: VERSION,
+            entries: Vec::new(),
+            efficient: Vec::new(),
+        };
+    }
+}
+
+impl HighScores {
+    // Add the game if it makes the table, returning its place (from 0)
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        // Earlier games keep their place on a tie
+        let place = self.entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        if place >= MAX_ENTRIES {
+            return None;
+        }
+
+        self.entries.insert(place, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        return Some(place);
+    }
+
+    // Add the game if it's one of the most efficient (fewest rolls, then highest score), returning its place (from 0)
+    pub fn insert_efficient(&mut self, entry: HighScore) -> Option<usize> {
+        // Earlier games keep their place on a tie
+        let place = self.efficient
+            .iter()
+            .position(|other| (entry.rolls_used, -entry.score) < (other.rolls_used, -other.score))
+            .unwrap_or(self.efficient.len());
+        if place >= MAX_ENTRIES {
+            return None;
+        }
+
+        self.efficient.insert(place, entry);
+        self.efficient.truncate(MAX_ENTRIES);
+        return Some(place);
+    }
+}
+
+// The rules a game is played with, as shown in the table
+pub fn variant(game: &Game) -> String {
+    let mut rules = Vec::new();
+    if game.joker {
+        rules.push("joker".to_string());
+    }
+
+    if let Some(sections) = &game.sections {
+        let numbers: Vec<String> = sections
+            .iter()
+            .map(|number| number.to_string())
+            .collect();
+        rules.push(format!("sections {}", numbers.join(",")));
+    }
+    if game.odd_even {
+        rules.push("odd/even".to_string());
+    }
+    if game.sum_straights {
+        rules.push("sum straights".to_string());
+    }
+    if let Some(max) = game.max_holds {
+        rules.push(format!("max holds {}", max));
+    }
+    if let Some(max) = game.max_zeros {
+        rules.push(format!("max zeros {}", max));
+    }
+    if game.nudges > 0 {
+        rules.push(format!("nudges {}", game.nudges));
+    }
+    if game.yahtzee_bonus != YAHTZEE_BONUS {
+        rules.push(format!("Yahtzee bonus {}", game.yahtzee_bonus));
+    }
+    if game.upper_bonus != UpperBonus::default() {
+        rules.push(format!("upper bonus {} at {}", game.upper_bonus.points, game.upper_bonus.threshold));
+    }
+
+    let handicap = game.scorecard
+        .iter()
+        .filter(|section| section.is_handicap())
+        .count();
+    if handicap > 0 {
+        rules.push(format!("handicap {}", handicap));
+    }
+
+    return if rules.is_empty() { "standard".to_string() } else { rules.join(", ") };
+}
+
+// Why the high scores couldn't be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadError {
+    NewerVersion(String), // Written by a newer version of the game, so it's left alone
+    Unreadable(String), // Missing or damaged, so a new table can replace it
+}
+
+impl std::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return match self {
+            ReadError::NewerVersion(message) | ReadError::Unreadable(message) => {
+                write!(f, "{}", message)
+            }
+        };
+    }
+}
+
+// Read the high scores, an empty table if there's no file yet
+pub fn read_high_scores(path: &Path) -> Result<HighScores, ReadError> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(HighScores::default());
+        }
+        Err(e) => {
+            return Err(ReadError::Unreadable(e.to_string()));
+        }
+    };
+
+    let mut table: serde_json::Value = serde_json
+        ::from_str(&json)
+        .map_err(|e| ReadError::Unreadable(format!("Not a high score table: {}", e)))?;
+
+    // Tables are upgraded from older versions here as the format changes
+    let version = table
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or(ReadError::Unreadable("Not a high score table: no version".to_string()))?;
+    if version > u64::from(VERSION) {
+        return Err(
+            ReadError::NewerVersion(
+                format!(
+                    "This table was made by a newer version of the game (format {}, this version reads up to {})",
+                    version,
+                    VERSION
+                )
+            )
+        );
+    }
+
+    // Version 1 didn't keep the most efficient games, or count the rolls of the games it kept
+    if version < 2 {
+        table["efficient"] = serde_json::json!([]);
+    }
+    table["version"] = serde_json::json!(VERSION);
+
+    return serde_json
+        ::from_value(table)
+        .map_err(|e| ReadError::Unreadable(format!("Not a high score table: {}", e)));
+}
+
+// Write the high scores, replacing the file
+pub fn write_high_scores(path: &Path, scores: &HighScores) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(scores).map_err(|e| e.to_string())?;
+
+    // Write to a temporary file first, so a crash never leaves half a table behind
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json).map_err(|e| e.to_string())?;
+    return std::fs::rename(&temp_path, path).map_err(|e| e.to_string());
+}
+
+// Move an unreadable high score file aside (keeping it) so a new table can be started
+pub fn move_aside(path: &Path) -> Result<PathBuf, String> {
+    let aside_path = path.with_extension(format!("corrupt-{}.json", timestamp_now()));
+    std::fs::rename(path, &aside_path).map_err(|e| e.to_string())?;
+    return Ok(aside_path);
+}
@@ -0,0 +1,152 @@
+// A text histogram of scores, showing the shape of how they're spread
+use serde::Serialize;
+
+use crate::export::csv_line;
+
+// The percentiles marked on the histogram
+static PERCENTILES: [u8; 3] = [5, 50, 95];
+
+// The character bars are drawn with
+static BAR: &str = "#";
+
+// The scores from low up to (but not including) high
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bucket {
+    pub low: i32,
+    pub high: i32,
+    pub count: u32, // The number of scores in the bucket
+}
+
+// Scores counted into buckets of the same width, from the lowest score's bucket to the highest's
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Histogram {
+    pub bucket_width: i32,
+    pub buckets: Vec<Bucket>,
+    pub total: u32, // The number of scores counted
+    pub percentiles: Vec<(u8, i32)>, // Each marked percentile, and the score at it
+}
+
+// The score that the given percent of the (sorted) scores are at or below
+fn percentile(sorted: &[i32], percent: u8) -> i32 {
+    // The nearest rank, counting from 1
+    let rank = ((f64::from(percent) / 100.0) * (sorted.len() as f64)).ceil() as usize;
+    return sorted[rank.clamp(1, sorted.len()) - 1];
+}
+
+impl Histogram {
+    // Count the scores into buckets of the given width
+    pub fn new(scores: &[i32], bucket_width: i32) -> Histogram {
+        // Buckets need some width, or they'd never reach the highest score
+        let bucket_width = bucket_width.max(1);
+        let mut sorted = scores.to_vec();
+        sorted.sort();
+
+        let mut buckets = Vec::new();
+        if let (Some(lowest), Some(highest)) = (sorted.first(), sorted.last()) {
+            // Buckets start on a multiple of the width, ex: 130-139
+            let mut low = lowest.div_euclid(bucket_width) * bucket_width;
+            while low <= *highest {
+                let high = low + bucket_width;
+                let count = sorted
+                    .iter()
+                    .filter(|score| (**score >= low) & (**score < high))
+                    .count();
+                buckets.push(Bucket { low, high, count: count as u32 });
+                low = high;
+            }
+        }
+
+        let percentiles = if sorted.is_empty() {
+            Vec::new()
+        } else {
+            PERCENTILES.iter()
+                .map(|percent| (*percent, percentile(&sorted, *percent)))
+                .collect()
+        };
+
+        return Histogram {
+            bucket_width,
+            buckets,
+            total: sorted.len() as u32,
+            percentiles,
+        };
+    }
+
+    // The histogram as text, a row for each bucket with its bar, count, and percentage, and the
+    // percentiles marked on the buckets they fall in. The bars are scaled to fit the width
+    pub fn render(&self, width: usize) -> String {
+        let labels: Vec<String> = self.buckets
+            .iter()
+            .map(|bucket| format!("{}-{}", bucket.low, bucket.high - 1))
+            .collect();
+        let counts: Vec<String> = self.buckets
+            .iter()
+            .map(|bucket| {
+                let percent = (f64::from(bucket.count) * 100.0) / f64::from(self.total);
+                format!("{} ({:.1}%)", bucket.count, percent)
+            })
+            .collect();
+        let markers: Vec<String> = self.buckets
+            .iter()
+            .map(|bucket| {
+                let marked: Vec<String> = self.percentiles
+                    .iter()
+                    .filter(|(_, score)| (*score >= bucket.low) & (*score < bucket.high))
+                    .map(|(percent, _)| format!("p{}", percent))
+                    .collect();
+                if marked.is_empty() { String::new() } else { format!(" <- {}", marked.join(", ")) }
+            })
+            .collect();
+
+        // The longest bar fills whatever the rows' text leaves of the width (always at least 10)
+        let label_width = labels
+            .iter()
+            .map(|label| label.len())
+            .max()
+            .unwrap_or(0);
+        let text_width = (0..self.buckets.len())
+            .map(|i| label_width + 3 + 1 + counts[i].len() + markers[i].len())
+            .max()
+            .unwrap_or(0);
+        let bar_width = width.saturating_sub(text_width).max(10);
+        let most = self.buckets
+            .iter()
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut rendered = Vec::new();
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            let length = ((bucket.count as usize) * bar_width) / (most as usize);
+            let bar = BAR.repeat(length);
+            let row = format!("{: >width$} | {} {}{}", labels[i], bar, counts[i], markers[i], width = label_width);
+            rendered.push(row);
+        }
+
+        let marked: Vec<String> = self.percentiles
+            .iter()
+            .map(|(percent, score)| format!("p{}: {}", percent, score))
+            .collect();
+        rendered.push(format!("Percentiles: {}", marked.join(", ")));
+        return rendered.join("\n");
+    }
+
+    // The buckets as CSV, for plotting elsewhere
+    pub fn csv(&self) -> String {
+        let header = ["Low", "High", "Count", "Percent"].map(|field| field.to_string());
+        let mut csv = csv_line(&header);
+        for bucket in &self.buckets {
+            let percent = (f64::from(bucket.count) * 100.0) / f64::from(self.total);
+            csv += &csv_line(
+                &[
+                    bucket.low.to_string(),
+                    (bucket.high - 1).to_string(),
+                    bucket.count.to_string(),
+                    format!("{:.2}", percent),
+                ]
+            );
+        }
+        return csv;
+    }
+}
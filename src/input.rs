@@ -0,0 +1,56 @@
+// Reading lines of input, optionally on another thread so waiting for input can time out
+use std::io::BufRead;
+use std::sync::mpsc::{ channel, Receiver, RecvTimeoutError };
+use std::sync::{ Mutex, OnceLock };
+use std::time::Duration;
+
+// Lines read by the background thread, once it has been started
+static BACKGROUND: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+// Start reading stdin on another thread, all input then comes through it
+pub fn read_in_background() {
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            // Stop at a read error, or once nothing is listening
+            let sent = match line {
+                Ok(line) => sender.send(line).is_ok(),
+                Err(..) => false,
+            };
+            if !sent {
+                break;
+            }
+        }
+    });
+
+    // Only the first background reader is used
+    let _ = BACKGROUND.set(Mutex::new(receiver));
+}
+
+// Read a line of input, None at the end of input
+pub fn read_line() -> std::io::Result<Option<String>> {
+    if let Some(receiver) = BACKGROUND.get() {
+        return Ok(receiver.lock().unwrap().recv().ok());
+    }
+
+    let mut input = String::new();
+    let read = std::io::stdin().read_line(&mut input)?;
+    return Ok(if read == 0 { None } else { Some(input) });
+}
+
+// Wait up to the timeout for a line of input, None if there wasn't one in time.
+// Only works once input is being read in the background, it's an error before then
+pub fn read_line_timeout(timeout: Duration) -> std::io::Result<Option<String>> {
+    let receiver = BACKGROUND
+        .get()
+        .ok_or_else(|| std::io::Error::other("input is not being read in the background"))?;
+    return match receiver.lock().unwrap().recv_timeout(timeout) {
+        Ok(line) => Ok(Some(line)),
+        Err(RecvTimeoutError::Timeout) => Ok(None),
+        // Nothing more will be read, but still wait out the timeout
+        Err(RecvTimeoutError::Disconnected) => {
+            std::thread::sleep(timeout);
+            Ok(None)
+        }
+    };
+}
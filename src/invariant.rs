@@ -0,0 +1,34 @@
+// Checking what should always be true about a game. Breaking one of these is a bug: strict checking
+// panics so it's found, otherwise the caller recovers with a safe value and keeps the warning, so a
+// player doesn't lose their game to it. Each game picks how its own are handled (Game::strictness), and
+// keeps its warnings (Game::warnings) for the program playing it to report, nothing here prints them
+
+// How a broken invariant is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    Strict, // Panic, so the bug is found
+    Recover, // Warn and carry on with a safe value
+}
+
+// Debug builds are strict and release builds recover, unless a game picks otherwise
+impl Default for Strictness {
+    fn default() -> Strictness {
+        return if cfg!(debug_assertions) { Strictness::Strict } else { Strictness::Recover };
+    }
+}
+
+impl Strictness {
+    // Check the invariant holds. When it doesn't, strict checking panics with the message, otherwise
+    // it's returned as the warning and the caller has to recover
+    pub fn check(self, holds: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+        if holds {
+            return Ok(());
+        }
+
+        let message = message();
+        if self == Strictness::Strict {
+            panic!("{}", message);
+        }
+        return Err(message);
+    }
+}
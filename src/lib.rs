@@ -0,0 +1,44 @@
+/*
+    Author: Ashley DeMott
+    Project: Yahtzee
+    Description: The game of Yahtzee and its players, shared by the terminal game and the benchmarks
+*/
+// Explicit returns and &Vec parameters are the style used throughout
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+pub mod achievements;
+pub mod advisor;
+pub mod ai;
+pub mod args;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod bot;
+pub mod console;
+pub mod describe;
+pub mod die;
+pub mod engine;
+pub mod error;
+pub mod events;
+pub mod expected;
+pub mod export;
+pub mod game;
+pub mod highscores;
+pub mod histogram;
+pub mod input;
+pub mod invariant;
+pub mod lookahead;
+pub mod odds;
+pub mod protocol;
+pub mod replay;
+pub mod results;
+pub mod save;
+pub mod sections;
+pub mod selfcheck;
+pub mod share;
+pub mod simulate;
+pub mod solver;
+pub mod stats;
+pub mod tournament;
+pub mod transcript;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,149 @@
+// A computer player that looks ahead, simulating rerolls to find which dice are worth keeping
+use rand::rngs::StdRng;
+use rand::{ Rng, SeedableRng };
+
+use crate::ai::{ choose_freezes, choose_section };
+use crate::bot::{ GameView, HoldMask, Strategy };
+use crate::die::{ Die, Random };
+use crate::game::NUM_DICE;
+use crate::sections::{ create_scorecard, odd_even_sections, PointSection, Scorecard };
+
+// The number of simulated turns used for each estimate
+static SAMPLES: u32 = 200;
+
+// Estimate the average points the section would score, keeping the given dice and rolling the rest.
+// Each roll rerolls every die that isn't kept, stopping once the section scores anything
+pub fn expected_value(
+    section: &dyn PointSection,
+    dice_kept: &Vec<Die>,
+    rolls_left: u8,
+    samples: u32,
+    rng: &mut impl Rng
+) -> f64 {
+    // Only a full set of dice can be kept, any more are left out
+    let dice_kept: &[Die] = &dice_kept[..dice_kept.len().min(NUM_DICE)];
+
+    // Without rolls, only a full set of dice can be scored
+    if rolls_left == 0 {
+        return if dice_kept.len() == NUM_DICE { section.calc_score(dice_kept) as f64 } else { 0.0 };
+    }
+
+    let mut total = 0;
+    for _ in 0..samples {
+        // The kept dice are frozen, the others are filled in by rolling
+        let mut dice = dice_kept.to_vec();
+        for die in &mut dice {
+            die.frozen = true;
+        }
+        dice.resize(NUM_DICE, Die::default());
+
+        let mut score = 0;
+        for _ in 0..rolls_left {
+            for die in &mut dice {
+                die.roll(rng);
+            }
+
+            score = section.calc_score(&dice);
+            if score > 0 {
+                break;
+            }
+        }
+        total += score;
+    }
+
+    return (total as f64) / (samples as f64);
+}
+
+// Which dice to keep for a straight: one of each number in the longest run
+fn choose_run(dice: &[Die]) -> Vec<bool> {
+    let mut best_run = (0, 0); // The first number and length of the longest run
+
+    for die in dice {
+        // Count up from this number while the next one is present
+        let mut length = 0;
+        while dice.iter().any(|d| d.num == die.num + length) {
+            length += 1;
+        }
+        if length > best_run.1 {
+            best_run = (die.num, length);
+        }
+    }
+
+    // Keep the first die showing each number in the run
+    let mut kept = vec![false; dice.len()];
+    for num in best_run.0..best_run.0 + best_run.1 {
+        if let Some(i) = dice.iter().position(|d| d.num == num) {
+            kept[i] = true;
+        }
+    }
+    return kept;
+}
+
+// Weighs keeping the most common number, a run, or everything, against every open section
+pub struct LookaheadStrategy {
+    rules: Scorecard, // Every section that can be on a scorecard, in order, for scoring simulated dice
+    rng: StdRng, // Rolls the simulated dice
+}
+impl LookaheadStrategy {
+    pub fn new(seed: u64) -> LookaheadStrategy {
+        return LookaheadStrategy {
+            rules: create_scorecard().into_iter().chain(odd_even_sections()).collect(),
+            rng: StdRng::seed_from_u64(seed),
+        };
+    }
+}
+impl Strategy for LookaheadStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        // The dice that could be kept, keeping everything is scoring now
+        let candidates = [
+            vec![true; view.dice.len()],
+            choose_freezes(&view.dice),
+            choose_run(&view.dice),
+        ];
+
+        // Find the candidate with the best expected score in any open section
+        let mut best = (0, f64::MIN);
+        for (candidate_i, kept) in candidates.iter().enumerate() {
+            let dice_kept: Vec<Die> = view.dice
+                .iter()
+                .zip(kept)
+                .filter(|(_, keep)| **keep)
+                .map(|(die, _)| die.clone())
+                .collect();
+
+            // Keeping everything is already a full roll, it doesn't need rolling
+            let rolls_left = if dice_kept.len() == NUM_DICE { 0 } else { view.rolls_left };
+
+            for section in &view.scorecard {
+                if section.potential.is_none() {
+                    continue;
+                }
+
+                // The section's rules are found by name, since a scorecard can leave some out
+                let rule = match self.rules.iter().find(|rule| rule.get_name() == section.name) {
+                    Some(rule) => rule,
+                    None => {
+                        continue;
+                    }
+                };
+                let ev = expected_value(
+                    rule.as_ref(),
+                    &dice_kept,
+                    rolls_left,
+                    SAMPLES,
+                    &mut self.rng
+                );
+
+                // Earlier candidates win ties, so nothing is rerolled for no gain
+                if ev > best.1 {
+                    best = (candidate_i, ev);
+                }
+            }
+        }
+
+        return candidates[best.0].clone();
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        return choose_section(&view.scorecard);
+    }
+}
@@ -3,594 +3,2335 @@
     Project: Yahtzee
     Description: Creating a game of Yahtzee using Rust
 */
-use std::io::Write;
-use rand::Rng;
-use std::collections::HashMap;
-
-// The ability to roll a random value
-trait Random {
-    fn roll(&mut self);
-}
-
-// Allow cloning of Die, used with vec![]
-#[allow(unused)]
-#[derive(Debug, Clone)]
-struct Die {
-    num: u8, // The Die's number
-    frozen: bool, // If the Die cannot be rolled
-}
-// Implement the default values for a DIe
-impl Default for Die {
-    fn default() -> Die {
-        // Create a default Die
-        return Die {
-            num: 0,
-            frozen: false,
-        };
+// Explicit returns and &Vec parameters are the style used throughout
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+use std::path::{ Path, PathBuf };
+use std::time::Duration;
+
+use yahtzee::{
+    advisor,
+    ai,
+    args,
+    bot,
+    console,
+    describe,
+    die,
+    engine,
+    error,
+    events,
+    export,
+    game,
+    highscores,
+    histogram,
+    input,
+    lookahead,
+    odds,
+    protocol,
+    replay,
+    results,
+    save,
+    sections,
+    selfcheck,
+    share,
+    simulate,
+    solver,
+    stats,
+    tournament,
+    transcript,
+    say,
+    sayln,
+};
+#[cfg(feature = "bench")]
+use yahtzee::bench;
+use ai::GreedyStrategy;
+use args::{ parse_args, Options, RuleOptions };
+use bot::{ Bot, GameView, LocalTable, RemoteTable, StrategyBot, Table };
+use die::Die;
+use engine::EngineState;
+use error::GameError;
+use events::emit_state;
+use game::{ Game, Move, TurnSnapshot, MAX_ROLLS };
+use highscores::{ HighScore, HighScores, ReadError, HIGHSCORE_FILE };
+use histogram::Histogram;
+use lookahead::LookaheadStrategy;
+use replay::Replay;
+use results::{ append_result, GameResult, SectionResult };
+use save::{ LoadError, SavedGame };
+use solver::{ Solution, SOLUTION_FILE };
+use sections::{ audit, find_sections, numbered_section, short_name, Scorecard, ScoreSummary };
+use transcript::Transcript;
+
+// The terminal width assumed when it can't be found
+static DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+// The most columns the scorecard is split into
+static MAX_COLUMNS: usize = 5;
+
+// The width of a scorecard entry beyond its name: ": ", the points, and the space between columns
+static SCORE_WIDTH: usize = 8;
+
+// How long the demo waits for the player before making the next move
+static DEMO_DELAY: Duration = Duration::from_secs(2);
+
+// Exit codes for a save given with --load that can't be continued, so scripts can tell them apart
+static EXIT_SAVE_MISSING: i32 = 3;
+static EXIT_SAVE_NEWER: i32 = 4;
+static EXIT_SAVE_UNREADABLE: i32 = 5;
+
+// The exit code for a --script that can't be read, or stops at a move that can't be made
+static EXIT_SCRIPT_FAILED: i32 = 6;
+
+// Display the current state of the dice, with each die's number (for freezing) beneath it
+fn display_dice(dice: &[Die]) {
+    display_marked_dice(dice, &[]);
+}
+
+// Display the dice as above, underlining the dice at the marked indexes (ex: the ones a section counts)
+fn display_marked_dice(dice: &[Die], marked: &[usize]) {
+    let mut labels = String::new();
+    let mut marks = String::new();
+
+    for (i, die) in dice.iter().enumerate() {
+        // The box's exterior is different if frozen/unfrozen
+        let box_str = if die.frozen { ('<', '>') } else { ('[', ']') };
+
+        // Die numbers from 1 - sides are shown, 0 is not shown (nothing has been rolled)
+        let box_num = if die.num != 0 { die.num.to_string() } else { " ".to_string() };
+
+        // Pad the number so every box is as wide as the die's largest number
+        let width = die.sides.to_string().len();
+        let die_box = format!("{} {: >width$} {}", box_str.0, box_num, box_str.1);
+
+        // Display the number within the box, and the die's index centered beneath it
+        say!("{} ", die_box);
+        labels += &format!("{: ^width$} ", i + 1, width = die_box.len());
+        let mark = if marked.contains(&i) { "^" } else { " " };
+        marks += &format!("{} ", mark.repeat(die_box.len()));
+    }
+    sayln!();
+    if !marked.is_empty() {
+        sayln!("{}", marks.trim_end());
+    }
+    sayln!("{}", labels.trim_end());
+}
+
+// The width of the terminal in characters, from the terminal itself or the COLUMNS variable
+fn terminal_width() -> usize {
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return width as usize;
     }
+
+    // Not a terminal (ex: piped output), fall back to the shell's setting or a standard width
+    return std::env
+        ::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH);
 }
-// Implement the functions of Random (can roll a Die)
-impl Random for Die {
-    fn roll(&mut self) {
-        // Randomize the die value if it isn't frozen
-        if !self.frozen {
-            self.num = rand::thread_rng().gen_range(1..7); // 1 - 6 (inclusive)
+
+// Display the current state of the Scorecard, in as many aligned columns as fit the terminal
+fn display_scorecard(scorecard: &Scorecard) {
+    display_scorecard_in(scorecard, terminal_width());
+}
+
+// Display the Scorecard in as many aligned columns as fit the width
+fn display_scorecard_in(scorecard: &Scorecard, width: usize) {
+    // Every column is as wide as the longest name, plus ": " and the points
+    let name_width = scorecard
+        .iter()
+        .map(|score| score.get_name().len())
+        .max()
+        .unwrap_or(0);
+    let column_width = name_width + SCORE_WIDTH;
+    let columns = (width / column_width).clamp(1, MAX_COLUMNS);
+
+    let mut col = 0; // Count the columns printed
+
+    sayln!(); // newline
+    // For every score in the scorecard,
+    for score in scorecard {
+        score.print(name_width);
+        col += 1;
+
+        // Limit the number of columns to what fits
+        if col == columns {
+            sayln!(); // Start a new line
+            col = 0; // Reset the count
+        } else {
+            say!("  "); // Space between columns
         }
     }
+    if col != 0 {
+        sayln!(); // End line
+    }
+}
+
+// Check the player still wants to roll, when coaching or careful play asks first
+fn confirm_roll(game: &Game, options: &Options) -> Result<bool, GameError> {
+    if options.play.coach && !coach_roll(game)? {
+        return Ok(false);
+    }
+    if options.play.careful & (game.rolls == 1) {
+        sayln!("This is your final roll, answer no to freeze dice first.");
+        return get_yes_no("Roll now?");
+    }
+    return Ok(true);
 }
 
-// Getters, immutable and perform the same for ALL scorecard Sections
-trait Section {
-    fn is_filled(&self) -> bool;
-    fn get_points(&self) -> i32;
-    fn get_name(&self) -> &'static str;
-    fn print(&self);
+// Warn before rolling away a strong hand, returning if the player still wants to roll
+fn coach_roll(game: &Game) -> Result<bool, GameError> {
+    let strong = game.strong_sections();
+    if strong.is_empty() {
+        return Ok(true);
+    }
+
+    for section_i in strong {
+        let section = &game.scorecard[section_i];
+        sayln!(
+            "You already have a strong {} ({} points), rolling again risks losing it.",
+            short_name(section.get_name()),
+            game.potential(section_i)
+        );
+    }
+    return get_yes_no("Roll anyway?");
 }
 
-// The ability to calculate points from a vector of Die
-trait Points {
-    fn calc_score(&self, dice: &Vec<Die>) -> i32;
-    fn set_score(&mut self, score: i32);
+// Display which dice the advisor would freeze, and the sections it would aim for
+fn display_advice(game: &Game) {
+    let advice = advisor::advise(game);
+    match advice.score_now {
+        Some(section_i) => sayln!("Advice: score in {}", game.scorecard[section_i].get_name()),
+        None => {
+            let held: Vec<usize> = (0..game.dice.len()).filter(|die_i| advice.hold[*die_i]).collect();
+            if held.is_empty() {
+                sayln!("Advice: roll all the dice again");
+            } else {
+                let positions: Vec<String> = held
+                    .iter()
+                    .map(|die_i| (die_i + 1).to_string())
+                    .collect();
+                let nums: Vec<String> = held
+                    .iter()
+                    .map(|die_i| game.dice[*die_i].num.to_string())
+                    .collect();
+                sayln!("Advice: hold dice {} (the {}) and roll the rest", positions.join(", "), nums.join(", "));
+            }
+        }
+    }
+
+    let targets: Vec<String> = advice.targets
+        .iter()
+        .map(|(section_i, points)| format!("{} (EV {:.1})", game.scorecard[*section_i].get_name(), points))
+        .collect();
+    sayln!("Best targets: {}", targets.join(", "));
 }
 
-// All Scores have these attributes and implement Section
-struct Score {
-    filled: bool, // If the score section has been filled
-    points: i32, // The point value of the score section
-    name: &'static str, // Name of the score section
+// Score the best from fixed dice with no rolls left, then compare with the advisor's pick
+fn play_puzzle(nums: &[u8], options: &Options) -> Result<(), GameError> {
+    let mut game = Game::new(0);
+    if options.rules.odd_even {
+        game.add_odd_even();
+    }
+    if options.rules.sum_straights {
+        game.add_sum_straights();
+    }
+    let dice: Vec<Die> = nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+    game.set_dice(&dice, 0)?;
+
+    sayln!("Puzzle: score the best you can from these dice, with no rolls left");
+    display_scorecard(&game.scorecard);
+    display_dice(&game.dice);
+    let best_i = advisor::advise(&game).score_now.expect("with no rolls left the advisor always scores");
+
+    let section_i = get_section(&game.scorecard)?;
+    let points = game.potential(section_i);
+    game.score(section_i)?;
+    sayln!("You scored {} in {}.", points, game.scorecard[section_i].get_name());
+
+    let best = &game.scorecard[best_i];
+    if section_i == best_i {
+        sayln!("That's the advisor's pick too!");
+    } else {
+        sayln!("The advisor would score {} for {} points.", best.get_name(), best.calc_score(&dice));
+    }
+    return Ok(());
 }
-impl Section for Score {
-    fn is_filled(&self) -> bool {
-        return self.filled;
+
+// Display the sections that can be filled, the most points for the dice first
+fn display_best_plays(game: &Game) {
+    let mut plays = game.preview_scores();
+    plays.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+    sayln!("Best plays for these dice:");
+    for (section_i, points) in plays {
+        let contributing = game.scorecard[section_i].contributing_dice(&game.dice);
+        if (points == 0) | contributing.is_empty() {
+            sayln!("{}: {}", game.scorecard[section_i].get_name(), points);
+            continue;
+        }
+
+        // The dice by their numbers beneath them, ex: "(dice 1, 3, 5)"
+        let numbers: Vec<String> = contributing.iter().map(|die_i| (die_i + 1).to_string()).collect();
+        sayln!("{}: {} (dice {})", game.scorecard[section_i].get_name(), points, numbers.join(", "));
     }
-    fn get_points(&self) -> i32 {
-        return self.points;
+}
+
+// Display the chance of ending the turn with dice that score in each open section
+fn display_odds(scorecard: &Scorecard, dice: &[Die], rolls: u8) {
+    sayln!(
+        "Chance of scoring in each open section by the end of the turn ({} rolls left, keeping the frozen dice):",
+        rolls
+    );
+    for (section_i, chance) in odds::open_chances(scorecard, dice, rolls) {
+        sayln!("{}: {:.1}%", scorecard[section_i].get_name(), chance * 100.0);
     }
-    fn get_name(&self) -> &'static str {
-        return self.name;
+}
+
+// Display the Scorecard like a paper score sheet, the upper and lower sections each with their totals
+fn display_grouped_scorecard(scorecard: &Scorecard, summary: &ScoreSummary) {
+    let name_width = scorecard
+        .iter()
+        .map(|score| score.get_name().len())
+        .max()
+        .unwrap_or(0)
+        .max("Upper Subtotal".len());
+
+    // The upper sections count a single number, the lower sections are everything else
+    for (title, upper_group) in [("Upper Section", true), ("Lower Section", false)] {
+        sayln!("\n{}", title);
+        for score in scorecard {
+            if score.face_value().is_some() == upper_group {
+                score.print(name_width);
+                sayln!();
+            }
+        }
     }
-    fn print(&self) {
-        // Display points if filled, otherwise empty string
-        print!("{0}: {1: <3}", self.name, if self.filled {
-            self.points.to_string()
+    sayln!();
+    for (name, points) in summary.rows() {
+        sayln!("{: <width$}{}", format!("{}: ", name), points, width = name_width + 2);
+    }
+}
+
+// Pick a choice from the displayed menu, automatically sets to 'Pick score' if out of rolls
+fn menu_choice(rolls: u8, practice: bool) -> Result<u8, GameError> {
+    // Display the menu, prompt for a choice (practice mode adds the snapshot choices)
+    let practice_choices = if practice { "[11] Take a Snapshot\n[12] Back to the Snapshot\n" } else { "" };
+    sayln!(
+        "\nMenu:\n[1] Roll Dice\n[2] Freeze Dice\n[3] Pick Score\n[4] Save Game\n[5] Reroll All Dice\n[6] Show Odds\n[7] Get Advice\n[8] Best Plays\n[9] Nudge a Die\n[10] Describe Roll\n{}[0] Quit\n",
+        practice_choices
+    ); // Display the menu
+
+    // Until the user has picked a valid choice,
+    loop {
+        let choice = get_int("Pick a menu choice", &0, if practice { &12 } else { &10 })?;
+
+        // always allow the user to quit, save, or see the odds
+        if (choice == 0) | (choice == 4) | (choice == 6) {
+            return Ok(choice);
+        } else if
+            // If the user is out of rolls, but hasn't chosen to end their turn,
+            (rolls == 0) & (choice != 3) & (choice != 7) & (choice != 8) & (choice != 9) & (choice < 10)
+        {
+            // Cannot roll if out of rolls
+            sayln!("Please pick a score section.");
+        } else if
+            // If the user hasn't rolled yet, but is choosing something else,
+            (rolls == MAX_ROLLS) & (choice != 1) & (choice != 5)
+        {
+            // Cannot roll if out of rolls
+            sayln!("Please roll first.");
         } else {
-            " ".to_string()
-        });
+            // Valid choice
+            return Ok(choice);
+        }
+    }
+}
+
+// Min and max acceptable values (within u8, positive integers), running out of input is an error
+fn get_int(prompt: &str, min: &u8, max: &u8) -> Result<u8, GameError> {
+    loop {
+        say!("{}: ", prompt);
+
+        let input = console::read_line()?.ok_or(GameError::EndOfInput)?;
+
+        match input.trim().parse::<u8>() {
+            Ok(i) => {
+                sayln!();
+                if (i <= *max) & (i >= *min) {
+                    return Ok(i);
+                } else {
+                    sayln!("Please enter positive integer between {} and {}", *min, *max);
+                }
+            }
+            Err(..) => {
+                sayln!("Please enter a valid integer");
+            }
+        }
+    }
+}
+
+// Ask for a section by its number or (part of) its name, asking which one was meant if several match
+fn get_section(scorecard: &Scorecard) -> Result<usize, GameError> {
+    loop {
+        say!("Pick a section (number or name): ");
+
+        let input = console::read_line()?.ok_or(GameError::EndOfInput)?;
+        let input = input.trim();
+        sayln!();
+
+        // A number picks the section with that number
+        if let Ok(number) = input.parse::<usize>() {
+            if let Some(section_i) = numbered_section(scorecard, number) {
+                return Ok(section_i);
+            }
+            sayln!("There is no section {} on the scorecard", number);
+            continue;
+        }
+
+        let matches = find_sections(scorecard, input);
+        match matches.len() {
+            0 => sayln!("There is no section named '{}'", input),
+            1 => {
+                return Ok(matches[0]);
+            }
+            _ => {
+                sayln!("'{}' could be:", input);
+                for (i, section_i) in matches.iter().enumerate() {
+                    sayln!("[{}] {}", i + 1, scorecard[*section_i].get_name());
+                }
+                let choice = get_int("Which section (0 to pick again)", &0, &(matches.len() as u8))?;
+                if choice != 0 {
+                    return Ok(matches[usize::from(choice - 1)]);
+                }
+            }
+        }
     }
 }
 
-// Get points for having specific number/value
-struct Section1 {
-    score: Score, // Has a Score section
-    value: u8, // The Die value that counts for points
+// Read a line of text, running out of input gives an empty line
+fn get_line(prompt: &str) -> Result<String, GameError> {
+    say!("{}: ", prompt);
+
+    let input = console::read_line()?.unwrap_or_default();
+    return Ok(input.trim().to_string());
 }
-impl Points for Section1 {
-    fn calc_score(&self, dice: &Vec<Die>) -> i32 {
-        let mut score = 0;
 
-        // For every die,
-        for die in dice {
-            // Only add points for those of the specified value
-            if die.num == self.value {
-                score += die.num as i32;
+// Ask a yes or no question, running out of input counts as no
+fn get_yes_no(prompt: &str) -> Result<bool, GameError> {
+    loop {
+        say!("{} (y/n): ", prompt);
+
+        let input = match console::read_line()? {
+            Some(input) => input,
+            None => {
+                sayln!();
+                return Ok(false);
+            }
+        };
+
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => {
+                return Ok(true);
+            }
+            "n" | "no" => {
+                return Ok(false);
+            }
+            _ => {
+                sayln!("Please enter y or n");
             }
         }
-        return score;
     }
-    fn set_score(&mut self, score: i32) {
-        // Assert that the score hasn't already been set
-        assert!(self.score.points == 0);
-        assert!(!self.score.filled);
+}
+
+// Save a finished game to the stats file
+fn record_result(path: &Path, player: &str, game: &Game) {
+    let result = game_result(player, game);
 
-        // Fill with the given score
-        self.score.filled = true;
-        self.score.points = score;
+    // Failing to save shouldn't take away the end of the game
+    if let Err(e) = append_result(path, &result) {
+        sayln!("Could not write to stats file {}: {}", path.display(), e);
     }
 }
-// To access score's values at the top level..
-impl Section for Section1 {
-    fn get_points(&self) -> i32 {
-        return self.score.get_points();
+
+// The result of the game: every section's score, the bonuses, and the total
+fn game_result(player: &str, game: &Game) -> GameResult {
+    let sections = game.scorecard
+        .iter()
+        .map(|section| SectionResult {
+            name: section.get_name().to_string(),
+            points: section.get_points(),
+        })
+        .collect();
+    let totals = game.totals();
+    let bonuses = totals.upper_bonus + totals.yahtzee_bonus;
+    return GameResult::new(player, game.seed, sections, bonuses, game.total(), game.rolls_used);
+}
+
+// For scripts, print just the final total, or the whole result as a line of JSON
+fn print_quiet(player: &str, game: &Game, json: bool) {
+    if json {
+        sayln!("{}", serde_json::to_string(&game_result(player, game)).expect("results serialize"));
+    } else {
+        sayln!("{}", game.total());
     }
-    fn is_filled(&self) -> bool {
-        return self.score.is_filled();
+}
+
+// Ask for a name and save the game under it in the data directory
+fn save_game(data_dir: &Path, game: &Game, player: &str) -> Result<(), GameError> {
+    let name = get_line("Save name")?;
+    if !save::valid_name(&name) {
+        sayln!("Save names can only use letters, numbers, '-', and '_'.");
+        return Ok(());
     }
-    fn get_name(&self) -> &'static str {
-        return self.score.get_name();
+
+    // Don't replace another save without asking
+    let path = save::save_path(data_dir, &name);
+    let prompt = format!("A save named {} already exists, overwrite it?", name);
+    if path.exists() && !get_yes_no(&prompt)? {
+        sayln!("The game wasn't saved.");
+        return Ok(());
     }
-    fn print(&self) {
-        self.score.print();
+    match save::write_save(&path, &SavedGame::new(game, player)) {
+        Ok(()) => sayln!("Saved to {}", path.display()),
+        Err(message) => sayln!("Could not save to {}: {}", path.display(), message),
     }
+    return Ok(());
 }
 
-// Get points for having # of a kind, YAHTZEE = 5 of a kind
-struct Section2 {
-    score: Score,
-    value: u8,
+// Save the game where it can be continued the next time the game starts
+fn autosave(data_dir: &Path, game: &Game, player: &str) {
+    let path = save::autosave_path(data_dir);
+    if let Err(message) = save::write_save(&path, &SavedGame::new(game, player)) {
+        sayln!("Could not autosave to {}: {}", path.display(), message);
+    }
 }
-impl Points for Section2 {
-    fn calc_score(&self, dice: &Vec<Die>) -> i32 {
-        let mut score = 0;
 
-        // Create a hashmap (key: die num, value: # in game_dice)
-        let mut counts: HashMap<u8, u8> = HashMap::new();
-        let mut dice_total: i32 = 0; // The total value of game_dice
+// If there is an autosave, ask to continue it (otherwise it is archived)
+fn resume_autosave(data_dir: &Path) -> Result<Option<SavedGame>, GameError> {
+    let path = save::autosave_path(data_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    match save::read_save(&path) {
+        Ok(saved) => {
+            let prompt = format!(
+                "Resume the game in progress from {} ({}'s turn, total {})?",
+                results::format_date(saved.saved_at),
+                saved.player,
+                saved.total
+            );
+            if get_yes_no(&prompt)? {
+                return Ok(Some(saved));
+            }
+        }
+        Err(message) => {
+            sayln!("The autosave at {} can't be read: {}", path.display(), message);
+        }
+    }
+
+    // Keep the old autosave, but out of the way of the new game's
+    match save::archive_autosave(data_dir) {
+        Ok(archive_path) => sayln!("Moved the autosave to {}", archive_path.display()),
+        Err(message) => sayln!("Could not move the autosave: {}", message),
+    }
+    return Ok(None);
+}
 
-        for die in dice {
-            // Find if the number is there, otherwise create a new key/value pair
-            let c = counts.entry(die.num).or_insert(0);
-            *c += 1; // Add one to the count
+// List every save, with when it was saved, whose game it is, and its total
+fn show_saves(data_dir: &Path) {
+    let saves = match save::list_saves(data_dir) {
+        Ok(saves) => saves,
+        Err(message) => {
+            sayln!("Could not list the saves: {}", message);
+            return;
+        }
+    };
+    if saves.is_empty() {
+        sayln!("There are no saved games.");
+    }
 
-            // Add to the total value of the dice
-            dice_total += die.num as i32;
+    for (name, summary) in saves {
+        match summary {
+            Some(summary) =>
+                sayln!(
+                    "{: <20} {}  {: <12} total {}",
+                    name,
+                    results::format_date(summary.saved_at),
+                    summary.player,
+                    summary.total
+                ),
+            None => sayln!("{: <20} (can't be read)", name),
         }
+    }
+}
+
+// Delete a save, once the player confirms it
+fn delete_save(data_dir: &Path, name: &str) -> Result<(), GameError> {
+    if !save::valid_name(name) | !save::save_path(data_dir, name).exists() {
+        sayln!("There is no save named {}.", name);
+        return Ok(());
+    }
+    if !get_yes_no(&format!("Delete the save {}?", name))? {
+        return Ok(());
+    }
 
-        // Find the mode from the hashmap (or 0, if not found)
-        let mode = counts.values().cloned().max().unwrap_or(0);
+    match save::delete_save(data_dir, name) {
+        Ok(()) => sayln!("Deleted the save {}.", name),
+        Err(message) => sayln!("Could not delete the save {}: {}", name, message),
+    }
+    return Ok(());
+}
 
-        // If enough of a single type, points = dice total [Hasbro Yahtzee rules]
-        if mode >= self.value {
-            score = dice_total;
+// Read the high score table, moving an unreadable one aside (None if it can't be)
+fn load_high_scores(data_dir: &Path) -> Option<HighScores> {
+    let path = data_dir.join(HIGHSCORE_FILE);
+    match highscores::read_high_scores(&path) {
+        Ok(scores) => {
+            return Some(scores);
+        }
+        // A newer version's table is kept as it is, without adding to it
+        Err(error @ ReadError::NewerVersion(..)) => {
+            sayln!("The high scores in {} can't be read: {}", path.display(), error);
+            return None;
+        }
+        Err(error) => {
+            sayln!("The high scores in {} can't be read: {}", path.display(), error);
         }
+    }
+
+    // Start a new table, keeping the old file
+    match highscores::move_aside(&path) {
+        Ok(aside_path) => {
+            sayln!("Moved the old high scores to {}", aside_path.display());
+            return Some(HighScores::default());
+        }
+        Err(message) => {
+            sayln!("Could not move the old high scores: {}", message);
+            return None;
+        }
+    }
+}
+
+// Display the high score table, marking the entry at the given place
+fn display_high_scores(scores: &HighScores, new_place: Option<usize>, new_efficient: Option<usize>) {
+    sayln!("High Scores:");
+    if scores.entries.is_empty() {
+        sayln!("No games have been finished yet.");
+    }
 
-        // Return the calculated score
-        return score;
+    for (place, entry) in scores.entries.iter().enumerate() {
+        let marker = if Some(place) == new_place { "  <- new!" } else { "" };
+        sayln!(
+            "{: >2}. {: >4}  {: <12} {: <20} {}  seed {}{}",
+            place + 1,
+            entry.score,
+            entry.player,
+            entry.variant,
+            results::format_date(entry.timestamp),
+            entry.seed,
+            marker
+        );
     }
-    fn set_score(&mut self, score: i32) {
-        // Assert that the score hasn't already been set
-        assert!(self.score.points == 0);
-        assert!(!self.score.filled);
 
-        // Fill with the given score
-        self.score.filled = true;
-        self.score.points = score;
+    // Then the games finished in the fewest rolls, which needn't be the highest scores
+    if !scores.efficient.is_empty() {
+        sayln!("\nMost Efficient Games (fewest rolls):");
+    }
+    for (place, entry) in scores.efficient.iter().enumerate() {
+        let marker = if Some(place) == new_efficient { "  <- new!" } else { "" };
+        sayln!(
+            "{: >2}. {: >3} rolls {: >4}  {: <12} {: <20} {}  seed {}{}",
+            place + 1,
+            entry.rolls_used,
+            entry.score,
+            entry.player,
+            entry.variant,
+            results::format_date(entry.timestamp),
+            entry.seed,
+            marker
+        );
     }
 }
-impl Section for Section2 {
-    fn get_points(&self) -> i32 {
-        return self.score.get_points();
+
+// Add a finished game to the high scores if it makes the table, then show them
+fn record_high_score(data_dir: &Path, game: &Game, player: &str) {
+    let mut scores = match load_high_scores(data_dir) {
+        Some(scores) => scores,
+        None => {
+            return;
+        }
+    };
+
+    let new_place = scores.insert(HighScore::new(game, player));
+    let new_efficient = scores.insert_efficient(HighScore::new(game, player));
+    if new_place.is_some() | new_efficient.is_some() {
+        if let Err(message) = highscores::write_high_scores(&data_dir.join(HIGHSCORE_FILE), &scores) {
+            sayln!("Could not save the high scores: {}", message);
+        }
     }
-    fn is_filled(&self) -> bool {
-        return self.score.is_filled();
+    display_high_scores(&scores, new_place, new_efficient);
+}
+
+// Let the bot make one move in the demo, returning true if it scored a section
+fn demo_move(game: &mut Game, bot: &mut dyn Bot) -> Result<bool, GameError> {
+    let view = GameView::new(EngineState::new(game), Vec::new());
+    let mut player_move = bot.choose(&view);
+
+    // The bots don't know about the limited holds rule, so only the first dice are held
+    if let (Move::Hold(dice), Some(max)) = (&mut player_move, game.max_holds) {
+        dice.truncate(max);
     }
-    fn get_name(&self) -> &'static str {
-        return self.score.get_name();
+
+    // Show what the computer did
+    match &player_move {
+        Move::Roll => sayln!("Demo: rolling the dice"),
+        Move::Hold(dice) => {
+            let numbers: Vec<String> = dice.iter().map(|i| (i + 1).to_string()).collect();
+            sayln!("Demo: holding dice {}", numbers.join(" "));
+        }
+        Move::Score(i) => sayln!("Demo: scoring {}", game.scorecard[*i].get_name()),
+        Move::Nudge(die_i, up) => sayln!("Demo: nudging die {} {}", die_i + 1, if *up { "up" } else { "down" }),
     }
-    fn print(&self) {
-        self.score.print();
+
+    let scored = matches!(player_move, Move::Score(_));
+    game.play(player_move)?;
+    return Ok(scored);
+}
+
+// Add a finished game to the lifetime statistics
+fn record_lifetime(path: &Path, state: &EngineState) {
+    // Failing to save shouldn't take away the end of the game
+    if let Err(message) = stats::record_game(path, state) {
+        sayln!("Could not update the statistics in {}: {}", path.display(), message);
     }
 }
 
-// Small (3), Large(4), and full/one-of-a-kind(5) straights (num = num in a row needed)
-struct Section3 {
-    score: Score,
-    value: u8,
+// Where the solution is kept between runs: the --solution-file, the --data-dir, or the user's data
+// directory if there is one
+fn solution_path(options: &Options) -> Option<PathBuf> {
+    if let Some(path) = &options.files.solution_file {
+        return Some(path.clone());
+    }
+    if !options.files.data_dir.as_os_str().is_empty() {
+        return Some(options.files.data_dir.join(SOLUTION_FILE));
+    }
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    return Some(data_dir.join("yahtzee").join(SOLUTION_FILE));
 }
-impl Points for Section3 {
-    fn calc_score(&self, dice: &Vec<Die>) -> i32 {
-        // For every die,
-        let mut present: Vec<u8> = Vec::new();
 
-        let mut straight = false;
+// Read the solution from where it's kept, or work it out and keep it there for next time
+fn prepare_solution(path: Option<&Path>) {
+    if let Some(solution) = path.and_then(Solution::read) {
+        solver::use_solution(solution);
+        return;
+    }
 
-        // Get a true/false for every value
-        for die in dice {
-            // If not already in the list,
-            if !present.contains(&die.num) {
-                present.push(die.num);
-            }
+    let solution = Solution::solve();
+    if let Some(path) = path {
+        // Failing to save only means working it out again next time
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(|e| e.to_string())
+            .and_then(|_| solution.write(path));
+        if let Err(message) = written {
+            sayln!("Could not save the solution to {}: {}", path.display(), message);
         }
+    }
+    solver::use_solution(solution);
+}
 
-        // TODO: Remake this without hardcoded straights
-        // For a straight of five (12345 or 23456)
-        if self.value == 5 {
-            // If there isn't a 1,
-            if !present.contains(&1) {
-                // If there isn't a 6,
-                if !present.contains(&6) {
-                    return 0; // Doesn't have a 1 or 6, cannot be a straight of 5
-                }
-                // There is a 6, but not a 1
-            } else {
-                if present.contains(&6) {
-                    return 0; // Can't have both a 1 or 6 in a straight of 5
-                }
-                // There is a 1, but no 6
-            }
-
-            // Both straights contain 2345
-            straight =
-                present.contains(&2) &
-                present.contains(&3) &
-                present.contains(&4) &
-                present.contains(&5);
-        }
-        if self.value == 4 {
-            // Hardcoded straights
-            straight =
-                (present.contains(&1) &
-                    present.contains(&2) &
-                    present.contains(&3) &
-                    present.contains(&4)) |
-                (present.contains(&2) &
-                    present.contains(&3) &
-                    present.contains(&4) &
-                    present.contains(&5)) |
-                (present.contains(&3) &
-                    present.contains(&4) &
-                    present.contains(&5) &
-                    present.contains(&6));
-        }
-        if self.value == 3 {
-            // Hardcoded straights
-            straight =
-                (present.contains(&1) & present.contains(&2) & present.contains(&3)) |
-                (present.contains(&2) & present.contains(&3) & present.contains(&4)) |
-                (present.contains(&3) & present.contains(&4) & present.contains(&5)) |
-                (present.contains(&4) & present.contains(&5) & present.contains(&6));
-        }
-
-        // Return 30, 40, or 50 (depending on straight size) if there is a straight
-        return if straight {
-            (self.value * 10) as i32
-        } else {
-            0
-        };
+// Write the finished game's scorecard to a CSV file
+fn export_csv(path: &Path, game: &Game, player: &str) {
+    match std::fs::write(path, export::scorecard_csv(&[(player, game)])) {
+        Ok(()) => sayln!("Exported the scorecard to {}", path.display()),
+        Err(e) => sayln!("Could not export to {}: {}", path.display(), e),
     }
-    fn set_score(&mut self, score: i32) {
-        // Assert that the score hasn't already been set
-        assert!(self.score.points == 0);
-        assert!(!self.score.filled);
+}
 
-        // Fill with the given score
-        self.score.filled = true;
-        self.score.points = score;
+// Show the lifetime statistics
+fn show_stats(path: &Path) {
+    match stats::read_stats(path) {
+        Ok(stats) => sayln!("{}", stats.report()),
+        Err(message) => sayln!("Could not read the statistics in {}: {}", path.display(), message),
     }
 }
-impl Section for Section3 {
-    fn get_points(&self) -> i32 {
-        return self.score.get_points();
+
+// Let the computer play a whole game from the seed, then show how it scored
+fn analyze_seed(seed: u64, lifetime_file: Option<&Path>, options: &Options) {
+    // The greedy bot plays, unless a level was chosen
+    let (name, mut bot): (&str, Box<dyn Bot>) = match options.bots.ai_level {
+        Some(level) => (level.name(), level.bot(seed)),
+        None => ("greedy", Box::new(StrategyBot::new(GreedyStrategy))),
+    };
+    let mut table = LocalTable { game: Game::new(seed) };
+    bot::play_solitaire(bot.as_mut(), &mut table).expect("the bots only make legal moves");
+    let mut game = table.game;
+    report_warnings(&mut game);
+
+    if options.bots.quiet {
+        print_quiet(name, &game, options.bots.json);
+    } else {
+        sayln!("Seed: {}", seed);
+        for section in &game.scorecard {
+            sayln!("{}: {}", section.get_name(), section.get_points());
+        }
+        sayln!("Total Score: {}", game.total());
     }
-    fn is_filled(&self) -> bool {
-        return self.score.is_filled();
+
+    if let Some(path) = lifetime_file {
+        record_lifetime(path, &EngineState::new(&game));
     }
-    fn get_name(&self) -> &'static str {
-        return self.score.get_name();
+}
+
+// Create a table for a bot's game, in this process or on an engine process
+fn create_table(seed: u64, remote: bool) -> Result<Box<dyn Table>, String> {
+    if !remote {
+        return Ok(Box::new(LocalTable { game: Game::new(seed) }));
     }
-    fn print(&self) {
-        self.score.print();
+
+    // Run this same program as the engine
+    let program = std::env::current_exe().map_err(|e| e.to_string())?;
+    return Ok(Box::new(RemoteTable::spawn(&program, &["--engine"], seed)?));
+}
+
+// Let two bots play games with the same seed, then show who won
+fn botmatch(
+    names: (String, String),
+    seed: u64,
+    remote: bool,
+    lifetime_file: Option<&Path>
+) -> Result<(), String> {
+    let mut bot_a = bot::bot_by_name(&names.0, seed).ok_or(format!("Unknown bot '{}'", names.0))?;
+    let mut bot_b = bot::bot_by_name(&names.1, seed).ok_or(format!("Unknown bot '{}'", names.1))?;
+    let mut table_a = create_table(seed, remote)?;
+    let mut table_b = create_table(seed, remote)?;
+
+    let totals = bot::play_match(
+        [bot_a.as_mut(), bot_b.as_mut()],
+        [table_a.as_mut(), table_b.as_mut()]
+    )?;
+
+    if let Some(path) = lifetime_file {
+        record_lifetime(path, &table_a.state()?);
+        record_lifetime(path, &table_b.state()?);
+    }
+
+    sayln!("Seed: {}", seed);
+    sayln!("{} (A): {}", names.0, totals[0]);
+    sayln!("{} (B): {}", names.1, totals[1]);
+    if totals[0] == totals[1] {
+        sayln!("It's a tie!");
+    } else {
+        let winner = if totals[0] > totals[1] { &names.0 } else { &names.1 };
+        sayln!("{} wins!", winner);
     }
+    return Ok(());
 }
 
-// Combination of the traits Points ans Section, all structs
-//  implementing both can be in a collection of &dyn PointSections
-trait PointSection: Points + Section {}
+// Save the replay of a game played from its start
+fn record_replay(data_dir: &Path, game: &Game) {
+    let path = replay::replay_path(data_dir, game.seed);
+    if let Err(message) = replay::write_replay(&path, &Replay::new(game)) {
+        sayln!("Could not save the replay to {}: {}", path.display(), message);
+    }
+}
 
-// All score sections are under a shared trait
-impl PointSection for Section1 {}
-impl PointSection for Section2 {}
-impl PointSection for Section3 {}
+// Play the moves in a script file without prompts (a move list, as replay --moves reads), then show the
+// scorecard, or only the total when quiet. An unfinished game is autosaved, so the next script can carry
+// on from it with --load autosave
+fn run_script(game: &mut Game, player: &str, path: &Path, options: &Options) -> Result<(), String> {
+    let moves = std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
 
-// Display the current state of the dice
-fn display_dice(dice: &Vec<Die>) {
-    for die in dice {
-        // The box's exterior is different if frozen/unfrozen
-        let box_str = if die.frozen { ('<', '>') } else { ('[', ']') };
+    // Keep another game's autosave out of the way, as starting a new game does
+    let autosave_path = save::autosave_path(&options.files.data_dir);
+    if autosave_path.exists() & (options.files.load.as_ref() != Some(&autosave_path)) {
+        save::archive_autosave(&options.files.data_dir)?;
+    }
 
-        // Die numbers from 1 - 6 are shown, 0 is not shown (nothing has been rolled)
-        let box_num = if die.num != 0 { die.num.to_string() } else { " ".to_string() };
+    // The moves made before one that can't be made are kept
+    let played = replay::play_moves(game, &moves);
+    report_warnings(game);
+    if game.in_progress() {
+        autosave(&options.files.data_dir, game, player);
+    } else {
+        let _ = std::fs::remove_file(&autosave_path);
+    }
+    played?;
 
-        // Display the number within the box
-        print!("{} {} {} ", box_str.0, box_num, box_str.1);
+    if options.bots.quiet {
+        print_quiet(player, game, options.bots.json);
+        return Ok(());
     }
-    println!();
+    display_dice(&game.dice);
+    display_scorecard(&game.scorecard);
+    sayln!("Total Score: {}", game.total());
+    return Ok(());
 }
 
-// Display the current state of the Scorecard
-fn display_scorecard(scorecard: &Vec<&mut &mut dyn PointSection>) {
-    let mut col: u8 = 0; // Count the columns printed
+// Report the broken invariants the game recovered from, on stderr to keep them apart from the game
+fn report_warnings(game: &mut Game) {
+    for warning in game.take_warnings() {
+        eprintln!("Warning: {}, recovering", warning);
+    }
+}
 
-    println!(); // newline
-    // For every score in the scorecard,
-    for score in scorecard {
-        score.print();
-        col += 1;
+// Set up a new game with the rules from the options
+fn with_rules(mut game: Game, rules: &RuleOptions) -> Result<Game, GameError> {
+    if let Some(sections) = &rules.sections {
+        game.enable_sections(sections)?;
+    }
+    if rules.odd_even {
+        game.add_odd_even();
+    }
+    if rules.sum_straights {
+        game.add_sum_straights();
+    }
 
-        // Limit the number of columns to 5
-        if col > 4 {
-            println!(); // Start a new line
-            col = 0; // Reset the count
-        }
+    // The bonuses come first, so the handicap's sections are totalled with them
+    game.yahtzee_bonus = rules.yahtzee_bonus;
+    game.upper_bonus = rules.upper_bonus;
+
+    // Pre-fill sections for the handicap, leaving at least one to play
+    game.handicap(rules.handicap)?;
+    game.joker = rules.joker;
+    game.max_holds = rules.max_holds;
+    game.max_zeros = rules.max_zeros;
+    if rules.nudge {
+        game.set_nudges(1);
     }
-    println!(); // End line
+    return Ok(game);
 }
 
-// Pick a choice from the displayed menu, automatically sets to 'Pick score' if out of rolls
-fn menu_choice(rolls: u8) -> u8 {
-    assert!(rolls >= (0 as u8)); // Assert game in valid state
-    assert!(rolls <= MAX_ROLLS);
+// Replay a move list from its seed without showing it, then show the scorecard it ends with
+fn replay_moves(seed: u64, path: &Path, options: &Options) -> Result<(), String> {
+    let moves = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut game = with_rules(Game::new(seed), &options.rules).map_err(|e| e.to_string())?;
+    let played = replay::play_moves(&mut game, &moves);
+    report_warnings(&mut game);
+    played?;
+    if options.bots.quiet {
+        print_quiet(&options.player, &game, options.bots.json);
+        return Ok(());
+    }
 
-    // Display the menu, prompt for a choice
-    println!("\nMenu:\n[1] Roll Dice\n[2] Freeze Dice\n[3] Pick Score\n[0] Quit\n"); // Display the menu
+    display_dice(&game.dice);
+    display_scorecard(&game.scorecard);
+    sayln!("Total Score: {}", game.total());
+    if game.in_progress() {
+        sayln!("Turn {} | Rolls left: {} | Sections left: {}", game.turn, game.rolls, game.sections_left());
+    } else {
+        sayln!("Game over!");
+    }
+    return Ok(());
+}
 
-    // Until the user has picked a valid choice,
-    loop {
-        let choice = get_int("Pick a menu choice", &0, &MAX_ROLLS);
+// Display the final score of a finished game, and its card to share
+fn display_game_over(game: &Game) {
+    let totals = game.totals();
+    sayln!("Game over! Total score: {} in {} rolls", game.total(), game.rolls_used);
+    sayln!(
+        "Upper: {} (bonus {}) | Lower: {} (Yahtzee bonus {})",
+        totals.upper,
+        totals.upper_bonus,
+        totals.lower,
+        totals.yahtzee_bonus
+    );
+    if !game.achievements.is_empty() {
+        let achieved: Vec<String> = game.achievements
+            .iter()
+            .map(|(turn, achievement)| format!("{} (turn {})", achievement.name(), turn))
+            .collect();
+        sayln!("Achievements: {}", achieved.join(", "));
+    }
+    sayln!("\n{}\n", share::share_card(game));
+}
 
-        // always allow the user to quit
-        if choice == 0 {
-            return choice;
-        } else if
-            // If the user is out of rolls, but hasn't chosen to end their turn,
-            (rolls == 0) & (choice != MAX_ROLLS)
-        {
-            // Cannot roll if out of rolls
-            println!("Please pick a score section.");
-        } else if
-            // If the user hasn't rolled yet, but is choosing something else,
-            (rolls == MAX_ROLLS) & (choice != 1)
-        {
-            // Cannot roll if out of rolls
-            println!("Please roll first.");
+// Play a replay back, showing every move (and waiting for Enter between turns if stepping)
+fn play_replay(replay: &Replay, step: bool) -> Result<(), GameError> {
+    let mut game = replay.start()?;
+
+    for player_move in &replay.moves {
+        match player_move {
+            Move::Roll => sayln!("Turn {}: rolling", game.turn),
+            Move::Hold(dice) => {
+                let numbers: Vec<String> = dice.iter().map(|i| (i + 1).to_string()).collect();
+                sayln!("Turn {}: holding dice {}", game.turn, numbers.join(" "));
+            }
+            Move::Score(i) => sayln!("Turn {}: scoring {}", game.turn, game.scorecard[*i].get_name()),
+            Move::Nudge(die_i, up) => {
+                sayln!("Turn {}: nudging die {} {}", game.turn, die_i + 1, if *up { "up" } else { "down" })
+            }
+        }
+        let scored = matches!(player_move, Move::Score(_));
+        game.play(player_move.clone())?;
+
+        if scored {
+            display_scorecard(&game.scorecard);
+            sayln!("Total Score: {}", game.total());
+            if step & game.in_progress() {
+                get_line("Press Enter for the next turn")?;
+            }
         } else {
-            // Valid choice
-            return choice;
+            display_dice(&game.dice);
         }
     }
+
+    sayln!("Final score: {} (recorded as {})", game.total(), replay.total);
+    return Ok(());
 }
 
-// Checks if there is an empty section in the Scorecard
-fn empty_section(scorecard: &Vec<&mut &mut dyn PointSection>) -> bool {
-    // For every score section in the scorecard,
-    for score in scorecard {
-        // Check if there is an empty section
-        if !score.is_filled() {
-            return true; // Not done with game
-        }
+// Play the replay without showing it, checking it ends with the recorded score
+fn verify_replay(replay: &Replay) -> Result<(), String> {
+    let game = replay.play().map_err(|e| e.to_string())?;
+    if game.in_progress() {
+        return Err("The replay ends before the game is over".to_string());
     }
-
-    // If none are empty, return false, the game is over
-    return false;
+    if game.total() != replay.total {
+        return Err(format!("Scored {}, but the replay recorded {}", game.total(), replay.total));
+    }
+    sayln!("Verified: seed {}, {} moves, final score {}", replay.seed, replay.moves.len(), game.total());
+    return Ok(());
 }
 
-// Calculate the total game score
-fn get_score(scorecard: &Vec<&mut &mut dyn PointSection>) -> i32 {
-    let mut total = 0;
-    for section in scorecard {
-        total += section.get_points();
+// Write to the transcript, giving up on it if it can't be written
+fn write_transcript(
+    transcript: &mut Option<Transcript>,
+    write: impl FnOnce(&mut Transcript) -> std::io::Result<()>
+) {
+    if let Some(file) = transcript {
+        if let Err(e) = write(file) {
+            sayln!("Could not write the transcript, it won't be continued: {}", e);
+            *transcript = None;
+        }
     }
-    return total;
 }
 
-// Min and max acceptable values (within u8, positive integers)
-fn get_int(prompt: &str, min: &u8, max: &u8) -> u8 {
+// Play games through the menu until the player quits, the first game may have come from a save
+fn play_games(
+    game: &mut Game,
+    player: &str,
+    options: &Options,
+    from_save: bool
+) -> Result<(), GameError> {
+    // Only games played from their start can be replayed
+    let mut from_start = !from_save;
+
+    // The demo keeps playing until the player takes over, which needs input that can time out
+    let mut demo = options.play.demo;
+    let mut demo_bot: Box<dyn Bot> = match options.bots.ai_level {
+        Some(level) => level.bot(game.seed),
+        None => Box::new(StrategyBot::new(LookaheadStrategy::new(game.seed))),
+    };
+    if demo {
+        input::read_in_background();
+    }
+
+    let mut transcript = match &options.files.transcript {
+        Some(path) =>
+            match Transcript::open(path) {
+                Ok(transcript) => Some(transcript),
+                Err(e) => {
+                    sayln!("Could not open the transcript {}: {}", path.display(), e);
+                    None
+                }
+            }
+        None => None,
+    };
+
+    // In free play, the points from every finished scorecard
+    let mut freeplay_total = 0;
+    let mut freeplay_cards = 0;
+
+    // In practice mode, the dice and rolls to go back to (only in the turn it was taken)
+    let mut snapshot: Option<TurnSnapshot> = None;
+
+    // Keep playing new games until the player is done
     loop {
-        print!("{}: ", prompt);
-        std::io::stdout().flush().unwrap(); // Flush the buffer so the print shows
+        let mut total_score = game.total(); // Total points from all scorecard sections and bonuses
+        write_transcript(&mut transcript, |file| file.start_game(game, player));
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).expect("failed to read input");
+        // The seed, with the moves made, is enough to play the game again exactly
+        if from_start {
+            sayln!("Seed: {}", game.seed);
+        }
 
-        match input.trim().parse::<u8>() {
-            Ok(i) => {
-                println!();
-                if (i <= *max) & (i >= *min) {
-                    return i;
-                } else {
-                    println!("Please enter positive integer between {} and {}", *min, *max);
+        if options.play.json_events {
+            emit_state("start", game);
+        }
+
+        // While the scorecard is not full,
+        while game.in_progress() {
+            report_warnings(game);
+            write_transcript(&mut transcript, |file| file.update(game));
+
+            // Display the dice, scoreboard, and total score
+            display_dice(&game.dice);
+            if options.play.grouped {
+                display_grouped_scorecard(&game.scorecard, &game.totals());
+            } else {
+                display_scorecard(&game.scorecard);
+            }
+            sayln!("Total Score: {total_score}");
+            if options.play.freeplay {
+                sayln!(
+                    "Free play total: {} ({} scorecards finished)",
+                    freeplay_total + total_score,
+                    freeplay_cards
+                );
+            }
+            sayln!(
+                "Turn {} | Rolls left: {} | Sections left: {}",
+                game.turn,
+                game.rolls,
+                game.sections_left()
+            );
+
+            // In the demo, the computer moves unless the player presses Enter in time
+            if demo {
+                sayln!("\nDemo mode, press Enter to take over.");
+                if input::read_line_timeout(DEMO_DELAY)?.is_none() {
+                    if demo_move(game, demo_bot.as_mut())? {
+                        total_score = game.total();
+                        debug_assert!(
+                            audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus),
+                            "The total score {} doesn't match the scorecard",
+                            total_score
+                        );
+                    }
+
+                    if options.play.json_events {
+                        emit_state("demo", game);
+                    }
+                    continue;
                 }
+
+                demo = false;
+                sayln!("You have control of the game.");
+                display_dice(&game.dice);
             }
-            Err(..) => {
-                println!("Please enter a valid integer");
+
+            // The tutorial shows what rolling would give, to help decide which dice to freeze
+            if options.play.tutorial & (game.rolls > 0) {
+                sayln!("\nTutorial: rolling now would give");
+                display_dice(&game.peek_roll());
+            }
+
+            // Depending on the user's choice,
+            match menu_choice(game.rolls, options.play.practice)? {
+                // 1. Roll the Dice
+                1 => {
+                    if !confirm_roll(game, options)? {
+                        continue;
+                    }
+                    match game.roll() {
+                        Ok(()) => {
+                            if options.play.json_events {
+                                emit_state("roll", game);
+                            }
+                        }
+                        // Tell the user the roll would be wasted
+                        Err(GameError::InvalidMove(message)) => sayln!("{}.", message),
+                        Err(e) => {
+                            return Err(e);
+                        }
+                    }
+                }
+
+                // 2. Freeze/unfreeze a certain Die
+                2 => {
+                    display_dice(&game.dice); // Display dice
+
+                    // Pick a die to freeze, 0 to cancel
+                    let choice = get_int(
+                        "Which die should be frozen/unfrozen?",
+                        &0,
+                        &(game.dice.len() as u8)
+                    )?;
+
+                    // If a Die has been chosen,
+                    if choice != 0 {
+                        // Get the Die's index from the user's choice, invert its frozen state
+                        match game.toggle_freeze(usize::from(choice - 1)) {
+                            Ok(()) => {
+                                if options.play.json_events {
+                                    emit_state("freeze", game);
+                                }
+                            }
+                            // Tell the user they're at the limit of frozen dice
+                            Err(GameError::InvalidMove(message)) => sayln!("{}.", message),
+                            Err(e) => {
+                                return Err(e);
+                            }
+                        }
+                    }
+                }
+
+                // 3. Pick point section
+                3 => {
+                    let mut pick = false;
+                    while !pick {
+                        // Display scorecard sections
+                        if options.play.grouped {
+                            display_grouped_scorecard(&game.scorecard, &game.totals());
+                        } else {
+                            display_scorecard(&game.scorecard);
+                        }
+                        let section_i = get_section(&game.scorecard)?;
+
+                        // If the section can be filled,
+                        if game.can_score(section_i) {
+                            pick = true;
+
+                            // Set the score and reset for the next turn, showing the dice that counted and
+                            // celebrating anything it achieved
+                            let dice = game.dice.clone();
+                            let contributing = game.scorecard[section_i].contributing_dice(&dice);
+                            let achieved_before = game.achievements.len();
+                            game.score(section_i)?;
+                            if !contributing.is_empty() {
+                                sayln!("{} counts these dice:", game.scorecard[section_i].get_name());
+                                display_marked_dice(&dice, &contributing);
+                            }
+                            for (_, achievement) in &game.achievements[achieved_before..] {
+                                sayln!("\n{}", achievement.celebration());
+                            }
+
+                            // Recalculate the total score
+                            total_score = game.total();
+                            debug_assert!(
+                                audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus),
+                                "The total score {} doesn't match the scorecard",
+                                total_score
+                            );
+                            autosave(&options.files.data_dir, game, player);
+
+                            if options.play.json_events {
+                                emit_state("score", game);
+                            }
+                        } else if game.scorecard[section_i].is_filled() {
+                            // Tell the user it is already filled
+                            sayln!("That section is already filled.");
+                        } else if game.zero_blocked(section_i) {
+                            // Tell the user they've used up their zeros
+                            sayln!(
+                                "Only {} sections can be scored at 0, pick a section worth points.",
+                                game.max_zeros.unwrap_or_default()
+                            );
+                        } else if let Some(forced_i) = game.forced_section() {
+                            // Tell the user where the Yahtzee has to go
+                            sayln!(
+                                "Under joker rules, this Yahtzee must be scored in {}.",
+                                game.scorecard[forced_i].get_name()
+                            );
+                        }
+                    }
+                }
+                // 4. Save the game to continue later
+                4 => {
+                    save_game(&options.files.data_dir, game, player)?;
+                }
+
+                // 5. Unfreeze every die and roll them all
+                5 => {
+                    if !confirm_roll(game, options)? {
+                        continue;
+                    }
+                    game.reroll_all()?;
+
+                    if options.play.json_events {
+                        emit_state("roll", game);
+                    }
+                }
+
+                // 6. Show the chance of scoring in each open section
+                6 => {
+                    display_odds(&game.scorecard, &game.dice, game.rolls);
+                }
+
+                // 7. Ask the advisor which dice to freeze and what to aim for
+                7 => {
+                    if options.play.advisor {
+                        display_advice(game);
+                    } else {
+                        sayln!("The advisor is turned off for this game.");
+                    }
+                }
+
+                // 8. List the open sections by what the dice would score in them
+                8 => {
+                    display_best_plays(game);
+                }
+
+                // 9. Nudge a die up or down by one, under the nudge rule
+                9 => {
+                    if game.nudges_remaining == 0 {
+                        sayln!("There are no nudges left this game.");
+                        continue;
+                    }
+                    display_dice(&game.dice);
+                    let choice = get_int("Which die should be nudged (0 to cancel)?", &0, &(game.dice.len() as u8))?;
+                    if choice != 0 {
+                        let up = get_int("Nudge it [1] up or [2] down?", &1, &2)? == 1;
+                        match game.nudge(usize::from(choice - 1), up) {
+                            Ok(()) => {
+                                if options.play.json_events {
+                                    emit_state("nudge", game);
+                                }
+                            }
+                            // Tell the user the die can't go that way
+                            Err(GameError::InvalidMove(message)) => sayln!("{}.", message),
+                            Err(e) => {
+                                return Err(e);
+                            }
+                        }
+                    }
+                }
+
+                // 10. Describe the patterns the dice make, by name
+                10 => {
+                    sayln!("{}", describe::describe(&game.dice));
+                }
+
+                // 11. Remember the dice and rolls, to try some rolls and come back (practice mode)
+                11 => {
+                    snapshot = Some(game.snapshot_turn());
+                    sayln!("Took a snapshot with {} rolls left.", game.rolls);
+                }
+
+                // 12. Go back to the snapshot
+                12 => {
+                    match &snapshot {
+                        Some(taken) =>
+                            match game.restore_turn(taken) {
+                                Ok(()) => sayln!("Back to the snapshot with {} rolls left.", game.rolls),
+                                Err(GameError::InvalidMove(message)) => sayln!("{}, take a new one.", message),
+                                Err(e) => {
+                                    return Err(e);
+                                }
+                            }
+                        None => sayln!("Take a snapshot first."),
+                    }
+                }
+
+                // Exit the game, saving it to continue next time
+                0 => {
+                    autosave(&options.files.data_dir, game, player);
+                    return Ok(());
+                }
+
+                // Invalid menu option
+                _ => {
+                    sayln!("Invalid choice.");
+                }
+            }
+        }
+
+        report_warnings(game);
+        write_transcript(&mut transcript, |file| file.finish_game(game));
+
+        // The game was finished, there's nothing left to continue (the demo never autosaves)
+        if !demo {
+            let _ = std::fs::remove_file(save::autosave_path(&options.files.data_dir));
+        }
+
+        display_game_over(game);
+        if options.play.freeplay {
+            freeplay_total += total_score;
+            freeplay_cards += 1;
+            sayln!("Free play total: {} over {} scorecards", freeplay_total, freeplay_cards);
+        }
+
+        // The demo starts another game by itself, and its games aren't the player's stats
+        if demo {
+            std::thread::sleep(DEMO_DELAY);
+        } else {
+            // Practice games can go back on their rolls, so they aren't recorded anywhere
+            if !options.play.practice {
+                if let Some(path) = &options.files.stats_file {
+                    record_result(path, &options.player, game);
+                }
+                record_lifetime(&options.files.lifetime_file, &EngineState::new(game));
+                record_high_score(&options.files.data_dir, game, player);
+                if from_start {
+                    record_replay(&options.files.data_dir, game);
+                }
+            }
+
+            // Export the scorecard to the given file, or offer to
+            match &options.files.export_csv {
+                Some(path) => export_csv(path, game, player),
+                None => {
+                    if get_yes_no("Export the scorecard to CSV?")? {
+                        let path = get_line("CSV file path")?;
+                        export_csv(Path::new(&path), game, player);
+                    }
+                }
+            }
+
+            // Free play carries on to a new scorecard, adding to the running total
+            let prompt = if options.play.freeplay { "Continue on a new scorecard?" } else { "Play again?" };
+            if !get_yes_no(prompt)? {
+                return Ok(());
             }
         }
+
+        // Start a fresh game with the next seed
+        game.restart();
+        from_start = true;
+        game.handicap(options.rules.handicap)?;
     }
 }
 
-// Reset for the next turn
-fn reset_turn(rolls: &mut u8, dice: &mut Vec<Die>) {
-    assert!(dice.len() == 5); // Assert number of dice is the same
-    *rolls = MAX_ROLLS;
+fn main() {
+    // Read the command line options (skipping the program name)
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            sayln!("{}", message);
+            sayln!(
+                "Usage: yahtzee [--name <player>] [--stats-file <path>] [--seed <u64>] [--analyze-seed <u64>] [--ai-level <level>] [--quiet [--json]] [--json-events] [--handicap <sections>] [--joker] [--odd-even] [--sum-straights] [--max-holds <dice>] [--max-zeros <sections>] [--nudge] [--yahtzee-bonus <points>] [--bonus-threshold <points>] [--bonus-amount <points>] [--sections <numbers>] [--tutorial] [--practice] [--grouped] [--freeplay] [--no-advisor] [--coach] [--careful] [--engine] [--protocol] [--script <file>] [--load <path-or-name>] [--demo] [--data-dir <path>] [--lifetime-file <path>] [--export-csv <path>] [--transcript <path>]"
+            );
+            sayln!(
+                "       yahtzee botmatch <bot> <bot> [--seed <u64>] [--remote] [--record-bots]"
+            );
+            sayln!(
+                "       yahtzee simulate [--games <count>] [--strategy <bot>] [--seed <u64>] [--json] [--bucket-width <points>] [--histogram-csv <path>]"
+            );
+            sayln!(
+                "       yahtzee tournament [--strategies <bot>,<bot>,...] [--games <count>] [--seed <u64>] [--export-csv <path>]"
+            );
+            sayln!("       yahtzee solve [--solution-file <path>]");
+            sayln!("       yahtzee selfcheck");
+            sayln!("       yahtzee prob <dice> [--held <dice>] [--rolls <count>] [--odd-even] [--sum-straights]");
+            sayln!("       yahtzee puzzle <dice> [--odd-even] [--sum-straights]");
+            sayln!("       yahtzee stats [--lifetime-file <path>]");
+            sayln!("       yahtzee replay <file> [--step]");
+            sayln!("       yahtzee replay --seed <u64> --moves <file> [--quiet [--json]] [--joker] [--odd-even] [--sum-straights] [--max-zeros <sections>] [--nudge] [--yahtzee-bonus <points>] [--bonus-threshold <points>] [--bonus-amount <points>] [--sections <numbers>] [--handicap <sections>]");
+            sayln!("       yahtzee verify <file>");
+            sayln!("       yahtzee highscores");
+            sayln!("       yahtzee saves | --list-saves");
+            sayln!("       yahtzee delete-save <name>");
+            std::process::exit(2);
+        }
+    };
 
-    // Reset all the Die (unfreeze and set to 0)
-    for die in dice {
-        die.frozen = false;
-        die.num = 0;
+    // Timing the scoring doesn't play anything
+    #[cfg(feature = "bench")]
+    if options.commands.bench {
+        bench::run();
+        return;
     }
-}
 
-// The number of rolls the player starts each round with
-static MAX_ROLLS: u8 = 3;
+    // Bot games only count in the lifetime statistics when asked, so they don't mix with the player's
+    let bot_lifetime_file = if options.bots.record_bots { Some(options.files.lifetime_file.as_path()) } else { None };
+
+    // The optimal bot plays from the solution, so it's read (or worked out and kept) before any game
+    let optimal = |name: &String| name == "optimal";
+    let plays_optimal =
+        (options.commands.simulate & optimal(&options.bots.strategy)) |
+        (options.commands.tournament & options.bots.strategies.iter().any(optimal)) |
+        options.bots.botmatch.as_ref().is_some_and(|(bot_a, bot_b)| optimal(bot_a) | optimal(bot_b));
+    if plays_optimal {
+        prepare_solution(solution_path(&options).as_deref());
+    }
 
-fn main() {
-    // Create all the PointSections for the scorecard
-    let mut s1_1: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "1. Aces", points: 0, filled: false },
-        value: 1,
-    });
-    let mut s1_2: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "2. Twos", points: 0, filled: false },
-        value: 2,
-    });
-    let mut s1_3: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "3. Threes", points: 0, filled: false },
-        value: 3,
-    });
-    let mut s1_4: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "4. Fours", points: 0, filled: false },
-        value: 4,
-    });
-    let mut s1_5: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "5. Fives", points: 0, filled: false },
-        value: 5,
-    });
-    let mut s1_6: &mut dyn PointSection = &mut (Section1 {
-        score: Score { name: "6. Sixes", points: 0, filled: false },
-        value: 6,
-    });
-
-    // 3, 4, or 5 of a kind
-    let mut s2_3: &mut dyn PointSection = &mut (Section2 {
-        score: Score { name: "7. 3 of a Kind", points: 0, filled: false },
-        value: 3,
-    });
-    let mut s2_4: &mut dyn PointSection = &mut (Section2 {
-        score: Score { name: "8. 4 of a Kind", points: 0, filled: false },
-        value: 4,
-    });
-    let mut s2_5: &mut dyn PointSection = &mut (Section2 {
-        score: Score { name: "9. YAHTZEE", points: 0, filled: false },
-        value: 5,
-    });
-
-    // Straights of 3, 4, or 5 (all different)
-    let mut s3_3: &mut dyn PointSection = &mut (Section3 {
-        score: Score { name: "10. Small Straight", points: 0, filled: false },
-        value: 3,
-    });
-    let mut s3_4: &mut dyn PointSection = &mut (Section3 {
-        score: Score { name: "11. Large Straight", points: 0, filled: false },
-        value: 4,
-    });
-    let mut s3_5: &mut dyn PointSection = &mut (Section3 {
-        score: Score { name: "12. Full House", points: 0, filled: false },
-        value: 5,
-    });
-
-    // Chance (counts up all, as a points for '0 of a kind' Section)
-    let mut chance: &mut dyn PointSection = &mut (Section2 {
-        score: Score { name: "13. Chance", points: 0, filled: false },
-        value: 0,
-    });
-
-    // Keep all PointSections in a vector
-    let mut scorecard: Vec<&mut &mut dyn PointSection> = vec![
-        &mut s1_1,
-        &mut s1_2,
-        &mut s1_3,
-        &mut s1_4,
-        &mut s1_5,
-        &mut s1_6,
-        &mut s2_3,
-        &mut s2_4,
-        &mut s2_5,
-        &mut s3_3,
-        &mut s3_4,
-        &mut s3_5,
-        &mut chance
-    ];
-
-    // Create a Vector of 5 dice
-    let mut game_dice: Vec<Die> = vec![Die::default(); 5];
-    let mut total_score = 0; // Total points from all scorecard sections
-    let mut rolls = MAX_ROLLS; // The number of rolls the player has left
-
-    // While the scorecard is not full,
-    while empty_section(&scorecard) {
-        // Display the dice, scoreboard, and total score
-        display_dice(&game_dice);
-        display_scorecard(&scorecard);
-        println!("Total Score: {total_score}");
-
-        // Assert game is in a valid state
-        assert!(rolls >= (0 as u8));
-        assert!(rolls <= MAX_ROLLS);
-        assert!(empty_section(&scorecard));
-
-        // Depending on the user's choice,
-        match menu_choice(rolls) {
-            // 1. Roll the Dice
-            1 => {
-                rolls -= 1;
+    // Analyzing a seed doesn't need a player
+    if let Some(seed) = options.bots.analyze_seed {
+        analyze_seed(seed, bot_lifetime_file, &options);
+        return;
+    }
 
-                // For every die in the vector,
-                for die in &mut game_dice {
-                    die.roll();
+    // Neither does a match between bots
+    if let Some(names) = options.bots.botmatch.clone() {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        if let Err(message) = botmatch(names, seed, options.bots.remote, bot_lifetime_file) {
+            sayln!("{}", message);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Or simulating many games
+    if options.commands.simulate {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let simulation = match simulate::simulate(&options.bots.strategy, options.bots.games, seed) {
+            Ok(simulation) => simulation,
+            Err(message) => {
+                sayln!("{}", message);
+                std::process::exit(1);
+            }
+        };
+
+        let histogram = Histogram::new(&simulation.scores, options.bots.bucket_width);
+        if options.bots.json {
+            sayln!("{}", serde_json::to_string(&simulation).expect("simulations serialize"));
+        } else {
+            sayln!("{}\n", simulation.report());
+            sayln!("{}", histogram.render(terminal_width()));
+        }
+        if let Some(path) = &options.bots.histogram_csv {
+            if let Err(e) = std::fs::write(path, histogram.csv()) {
+                sayln!("Could not write the histogram to {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Or a tournament between strategies
+    if options.commands.tournament {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let tournament = match tournament::run(&options.bots.strategies, options.bots.games, seed) {
+            Ok(tournament) => tournament,
+            Err(message) => {
+                sayln!("{}", message);
+                std::process::exit(1);
+            }
+        };
+        sayln!("{}", tournament.report());
+        if let Some(path) = &options.files.export_csv {
+            match std::fs::write(path, tournament.csv()) {
+                Ok(()) => sayln!("Exported the results to {}", path.display()),
+                Err(e) => {
+                    sayln!("Could not export the results to {}: {}", path.display(), e);
+                    std::process::exit(1);
                 }
             }
+        }
+        return;
+    }
 
-            // 2. Freeze/unfreeze a certain Die
-            2 => {
-                display_dice(&game_dice); // Display dice
+    // Or showing the odds for some dice
+    if let Some(nums) = &options.dice.prob {
+        let mut game = Game::new(0);
+        if options.rules.odd_even {
+            game.add_odd_even();
+        }
+        if options.rules.sum_straights {
+            game.add_sum_straights();
+        }
+        let dice: Vec<Die> = nums
+            .iter()
+            .enumerate()
+            .map(|(die_i, num)| Die { num: *num, frozen: options.dice.held.contains(&(die_i + 1)), ..Die::default() })
+            .collect();
+        game.set_dice(&dice, options.dice.rolls_left).expect("prob reads exactly the dice a game uses");
+        display_dice(&game.dice);
+        display_odds(&game.scorecard, &game.dice, options.dice.rolls_left);
+        return;
+    }
 
-                // Pick a die to freeze, 0 to cancel
-                let choice = get_int(
-                    "Which die should be frozen/unfrozen?",
-                    &0,
-                    &(game_dice.len() as u8)
-                );
+    // Or scoring a puzzle's dice
+    if let Some(nums) = &options.dice.puzzle {
+        if let Err(e) = play_puzzle(nums, &options) {
+            sayln!("Could not play the puzzle: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-                // If a Die has been chosen,
-                if choice != 0 {
-                    // Get the Die's index from the user's choice
-                    let freeze_i: usize = usize::from(choice - 1);
+    // Or working out perfect play
+    if options.commands.solve {
+        let path = solution_path(&options);
+        match &path {
+            Some(path) if path.exists() => {}
+            Some(path) => sayln!("Working out perfect play, this is saved to {} for next time...", path.display()),
+            None => sayln!("Working out perfect play..."),
+        }
+        prepare_solution(path.as_deref());
+        sayln!("Expected final score with perfect play: {:.2}", solver::solution().expected_score());
+        return;
+    }
+
+    // Or checking this build scores correctly, failing if it doesn't
+    if options.commands.selfcheck {
+        let results = selfcheck::run_checks();
+        for result in &results {
+            sayln!("{} {}", if result.passed() { "ok  " } else { "FAIL" }, result.name);
+            for failure in &result.failures {
+                sayln!("     {}", failure);
+            }
+        }
+
+        let passed = results.iter().filter(|result| result.passed()).count();
+        sayln!("Self-check: {} of {} checks passed", passed, results.len());
+        if passed < results.len() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Neither does playing back a replay, or checking one
+    if let Some(path) = &options.replay.file {
+        let played = replay::read_replay(path).and_then(|replay| {
+            play_replay(&replay, options.replay.step).map_err(|e| e.to_string())
+        });
+        if let Err(message) = played {
+            sayln!("Could not play the replay {}: {}", path.display(), message);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let (Some(seed), Some(path)) = (options.seed, &options.replay.moves) {
+        if let Err(message) = replay_moves(seed, path, &options) {
+            sayln!("Could not replay the moves in {}: {}", path.display(), message);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(path) = &options.replay.verify {
+        if let Err(message) = replay::read_replay(path).and_then(|replay| verify_replay(&replay)) {
+            sayln!("Could not verify the replay {}: {}", path.display(), message);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-                    // Invert the Die's frozen state
-                    game_dice[freeze_i].frozen = !game_dice[freeze_i].frozen;
+    // Or looking at the statistics and high scores, or managing saves
+    if options.commands.show_stats {
+        show_stats(&options.files.lifetime_file);
+        return;
+    }
+    if options.commands.high_scores {
+        if let Some(scores) = load_high_scores(&options.files.data_dir) {
+            display_high_scores(&scores, None, None);
+        }
+        return;
+    }
+    if options.commands.list_saves {
+        show_saves(&options.files.data_dir);
+        return;
+    }
+    if let Some(name) = &options.commands.delete_save {
+        if let Err(e) = delete_save(&options.files.data_dir, name) {
+            sayln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut player = options.player.clone(); // The player's name, may come from a save
+
+    // Load the given save, or offer to continue the last game played (not for the engine)
+    let saved = match &options.files.load {
+        Some(path) =>
+            match save::read_save(path) {
+                Ok(saved) => Some(saved),
+                Err(error) => {
+                    sayln!("Could not load {}: {}", path.display(), error);
+                    std::process::exit(match error {
+                        LoadError::Missing(..) => EXIT_SAVE_MISSING,
+                        LoadError::NewerVersion(..) => EXIT_SAVE_NEWER,
+                        LoadError::Unreadable(..) => EXIT_SAVE_UNREADABLE,
+                    });
                 }
             }
+        None if !options.play.engine & !options.play.protocol & !options.play.demo & options.play.script.is_none() =>
+            match resume_autosave(&options.files.data_dir) {
+                Ok(saved) => saved,
+                Err(e) => {
+                    sayln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        None => None,
+    };
+
+    // Continue the saved game, or use the given seed, otherwise a random one
+    let from_save = saved.is_some();
+    let mut game = if let Some(saved) = saved {
+        player = saved.player.clone();
+        match saved.restore() {
+            Ok(game) => game,
+            Err(message) => {
+                sayln!("Could not continue the saved game: {}", message);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        let game = match options.seed {
+            Some(seed) => Game::new(seed),
+            None => Game::random(),
+        };
+        match with_rules(game, &options.rules) {
+            Ok(game) => game,
+            Err(e) => {
+                sayln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+    };
+
+    // The engine reads and writes its own JSON instead of using the menu
+    if options.play.engine {
+        let stdin = std::io::stdin();
+        if let Err(e) = engine::run_engine(game, stdin.lock(), std::io::stdout()) {
+            eprintln!("Engine stopped: {}", e);
+        }
+        return;
+    }
+
+    // As does the line protocol, with its plain text
+    if options.play.protocol {
+        let stdin = std::io::stdin();
+        if let Err(e) = protocol::run_protocol(game, stdin.lock(), std::io::stdout()) {
+            eprintln!("Protocol stopped: {}", e);
+        }
+        return;
+    }
+
+    // And a script, with its moves from a file
+    if let Some(path) = &options.play.script {
+        if let Err(message) = run_script(&mut game, &player, path, &options) {
+            sayln!("{}", message);
+            std::process::exit(EXIT_SCRIPT_FAILED);
+        }
+        return;
+    }
 
-            // 3. Pick point section
-            3 => {
-                let mut pick = false;
-                while !pick {
-                    display_scorecard(&scorecard); // Display scorecard sections
-                    let section_i: usize = usize::from(
-                        get_int("Pick a section", &1, &(scorecard.len() as u8)) - 1
-                    );
+    // Any error reading input or playing ends the game, but it can still be continued later
+    if let Err(e) = play_games(&mut game, &player, &options, from_save) {
+        sayln!("\n{}", e);
+        if !options.play.demo & game.in_progress() {
+            autosave(&options.files.data_dir, &game, &player);
+        }
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::{ CapturedOutput, ScriptedInput };
+    use yahtzee::achievements::Achievement;
+    use yahtzee::die::ScriptedDice;
+    use yahtzee::args::{ FileOptions, PlayOptions };
+    use yahtzee::sections::UpperBonus;
+    use yahtzee::stats::LIFETIME_FILE;
+    use proptest::prelude::*;
+
+    // Play the script with an empty data directory (rolling the scripted dice, if given), returning the
+    // finished game, everything printed, and the high score table written
+    fn play_scripted(name: &str, script: &[&str], options: Options, dice: Option<&[u8]>) -> (Game, String, HighScores) {
+        return run_scripted(name, script, options, dice).unwrap();
+    }
 
-                    // If the section has not been filled,
-                    if !scorecard[section_i].is_filled() {
-                        pick = true;
+    // Play the script as above, returning why the game couldn't be played to the end of it instead of panicking
+    fn run_scripted(name: &str, script: &[&str], options: Options, dice: Option<&[u8]>) -> Result<(Game, String, HighScores), String> {
+        // A finished game writes its autosave, high scores, and replay to the data directory
+        let dir = std::env::temp_dir().join(format!("yahtzee-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let output = CapturedOutput::default();
+        console::use_console(ScriptedInput::new(script), output.clone());
+        let options = Options { files: FileOptions { lifetime_file: dir.join(LIFETIME_FILE), data_dir: dir.clone(), ..options.files }, ..options };
+        let mut game = with_rules(Game::new(options.seed.unwrap()), &options.rules).unwrap();
+        if let Some(dice) = dice {
+            game.script_dice(ScriptedDice::new(dice));
+        }
+        let played = play_games(&mut game, "Tester", &options, false).map_err(|e| e.to_string());
 
-                        // Calculate and set the score
-                        let score = scorecard[section_i].calc_score(&game_dice);
-                        scorecard[section_i].set_score(score);
+        let scores = highscores::read_high_scores(&dir.join(HIGHSCORE_FILE));
+        std::fs::remove_dir_all(&dir).unwrap();
+        played?;
+        let scores = scores.map_err(|e| e.to_string())?;
+        return Ok((game, output.text(), scores));
+    }
 
-                        // Recalculate the total score and reset for next turn
-                        total_score = get_score(&scorecard);
-                        reset_turn(&mut rolls, &mut game_dice);
-                    } else {
-                        // Tell the user it is already filled
-                        println!("That section is already filled.");
+    #[test]
+    fn a_natural_yahtzee_is_celebrated() {
+        // Every roll is all 6s: score YAHTZEE after one roll, then Chance (a Yahtzee bonus, but not natural)
+        let options = Options { seed: Some(1), rules: RuleOptions { sections: Some(vec![9, 13]), ..RuleOptions::default() }, ..Options::default() };
+        let script = ["1", "3", "9", "1", "3", "13", "n", "n"];
+        let (game, transcript, _) = play_scripted("natural", &script, options, Some(&[6]));
+
+        assert_eq!(game.achievements, [(1, Achievement::NaturalYahtzee)]);
+        assert_eq!(transcript.matches(Achievement::NaturalYahtzee.celebration()).count(), 1, "{}", transcript);
+        assert!(transcript.contains("Achievements: Natural Yahtzee (turn 1)"), "{}", transcript);
+    }
+
+    #[test]
+    fn the_upper_bonus_can_be_changed() {
+        let args = ["--bonus-threshold", "70", "--bonus-amount", "50"].map(str::to_string);
+        let options = parse_args(args.into_iter()).unwrap();
+        let game = with_rules(Game::new(1), &options.rules).unwrap();
+        assert_eq!(game.upper_bonus, UpperBonus { threshold: 70, points: 50 });
+
+        assert!(parse_args(["--bonus-amount", "-5"].map(str::to_string).into_iter()).is_err());
+    }
+
+    #[test]
+    fn a_handicap_is_totalled_with_the_changed_upper_bonus() {
+        let args = ["--handicap", "6", "--bonus-threshold", "10", "--bonus-amount", "50"].map(str::to_string);
+        let options = parse_args(args.into_iter()).unwrap();
+        let game = with_rules(Game::new(1), &options.rules).unwrap();
+
+        // The pre-filled upper sections reach the lowered threshold, earning the changed bonus
+        let totals = game.totals();
+        assert!(totals.upper >= 10, "{:?}", totals);
+        assert_eq!(totals.upper_bonus, 50);
+        assert_eq!(totals.total, totals.upper + totals.lower + 50);
+        assert!(audit(&game.scorecard, &totals, game.yahtzee_bonus, game.upper_bonus));
+    }
+
+    #[test]
+    fn saves_and_statistics_are_found_in_the_data_directory() {
+        let args = ["--load", "morning", "--data-dir", "games"].map(str::to_string);
+        let options = parse_args(args.into_iter()).unwrap();
+        assert_eq!(options.files.load, Some(Path::new("games").join("saves").join("morning.json")));
+        assert_eq!(options.files.lifetime_file, Path::new("games").join(LIFETIME_FILE));
+
+        // A file given by its path is used as it is
+        let args = ["--data-dir", "games", "--lifetime-file", "mine.json"].map(str::to_string);
+        assert_eq!(parse_args(args.into_iter()).unwrap().files.lifetime_file, Path::new("mine.json"));
+    }
+
+    #[test]
+    fn the_yahtzee_bonus_can_be_changed() {
+        let options = parse_args(["--yahtzee-bonus", "25"].map(str::to_string).into_iter()).unwrap();
+        let game = with_rules(Game::new(1), &options.rules).unwrap();
+        assert_eq!(game.yahtzee_bonus, 25);
+
+        assert!(parse_args(["--yahtzee-bonus", "-5"].map(str::to_string).into_iter()).is_err());
+        assert!(parse_args(["--yahtzee-bonus"].map(str::to_string).into_iter()).is_err());
+    }
+
+    #[test]
+    fn coaching_asks_before_rolling_away_a_strong_hand() {
+        let coached = Options { play: PlayOptions { coach: true, ..PlayOptions::default() }, ..Options::default() };
+        let mut game = Game::new(1);
+        game.roll().unwrap();
+
+        // Four 6s score most of what Four of a Kind can, so rolling them away needs a yes
+        game.set_dice(&dice(&[(6, false), (6, false), (6, false), (6, false), (1, false)]), 2).unwrap();
+        assert!(game.strong_sections().contains(&7));
+        assert!(!answer(&["n"], || confirm_roll(&game, &coached)).unwrap());
+        assert!(answer(&["y"], || confirm_roll(&game, &coached)).unwrap());
+        assert!(answer(&[], || confirm_roll(&game, &Options::default())).unwrap());
+
+        // A weak hand rolls without asking
+        game.set_dice(&dice(&[(1, false), (2, false), (4, false), (5, false), (5, false)]), 2).unwrap();
+        assert!(game.strong_sections().is_empty());
+        assert!(answer(&[], || confirm_roll(&game, &coached)).unwrap());
+    }
+
+    #[test]
+    fn careful_play_asks_before_the_final_roll() {
+        // On a scorecard of only Chance: roll twice, decline the final roll, then take it and score
+        let options = Options {
+            seed: Some(7),
+            rules: RuleOptions { sections: Some(vec![13]), ..RuleOptions::default() },
+            play: PlayOptions { careful: true, ..PlayOptions::default() },
+            ..Options::default()
+        };
+        let script = ["1", "1", "1", "n", "1", "y", "3", "13", "n", "n"];
+        let (game, transcript, _) = play_scripted("careful", &script, options, None);
+
+        // Only the final roll asks, and declining it goes back to the menu with the roll still there
+        assert_eq!(transcript.matches("This is your final roll").count(), 2, "{}", transcript);
+        assert_eq!(transcript.matches("Turn 1 | Rolls left: 1 |").count(), 2, "{}", transcript);
+        assert_eq!(game.rolls_used, 3);
+    }
+
+    #[test]
+    fn a_puzzle_scores_its_fixed_dice() {
+        let output = CapturedOutput::default();
+        console::use_console(ScriptedInput::new(&["8"]), output.clone());
+        play_puzzle(&[6, 6, 6, 6, 1], &Options::default()).unwrap();
+
+        let transcript = output.text();
+        assert!(transcript.contains("You scored 25 in 8. 4 of a Kind."), "{}", transcript);
+    }
+
+    // Everything the display prints
+    fn render(display: impl FnOnce()) -> String {
+        let output = CapturedOutput::default();
+        console::use_console(ScriptedInput::new(&[]), output.clone());
+        display();
+        return output.text();
+    }
+
+    // Check what was rendered matches its snapshot in tests/snapshots, so layout changes show up as diffs.
+    // Running with UPDATE_SNAPSHOTS=1 writes the snapshots instead
+    fn assert_snapshot(name: &str, rendered: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{}.txt", name));
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, rendered).unwrap();
+            return;
+        }
+
+        let snapshot = std::fs
+            ::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("No snapshot {}, run with UPDATE_SNAPSHOTS=1 to write it", path.display()));
+        assert!(rendered == snapshot, "{} changed, it's now:\n{}", name, rendered);
+    }
+
+    // Six-sided dice showing these numbers (0 for unrolled), and if they're frozen
+    fn dice(faces: &[(u8, bool)]) -> Vec<Die> {
+        return faces
+            .iter()
+            .map(|(num, frozen)| Die { num: *num, frozen: *frozen, ..Die::default() })
+            .collect();
+    }
+
+    // A game on the seed with the first sections scored, each after one roll
+    fn scored_game(seed: u64, sections: usize) -> Game {
+        let mut game = Game::new(seed);
+        for section_i in 0..sections {
+            game.roll().unwrap();
+            game.score(section_i).unwrap();
+        }
+        return game;
+    }
+
+    // Answer the prompt with the lines, returning the answer read
+    fn answer<T>(lines: &[&str], prompt: impl FnOnce() -> Result<T, GameError>) -> Result<T, GameError> {
+        console::use_console(ScriptedInput::new(lines), CapturedOutput::default());
+        return prompt();
+    }
+
+    // An option or subcommand, or anything else, as a command line argument
+    fn arg() -> impl Strategy<Value = String> {
+        let known = [
+            "--seed", "--sections", "--handicap", "--max-holds", "--max-zeros", "--yahtzee-bonus", "--bonus-threshold", "--games", "--strategy",
+            "--strategies", "--bucket-width", "--rolls", "--held", "--load", "--analyze-seed", "--ai-level", "--quiet", "--json",
+            "simulate", "replay", "botmatch", "puzzle", "prob", "tournament", "bench", "--",
+        ];
+        return prop_oneof![
+            prop::sample::select(known.to_vec()).prop_map(str::to_string),
+            (0u64..20).prop_map(|n| n.to_string()),
+            any::<i128>().prop_map(|n| n.to_string()),
+            prop::collection::vec(0u8..20, 0..8).prop_map(|numbers| format!("{:?}", numbers).replace(['[', ']', ' '], "")),
+            any::<String>()
+        ];
+    }
+
+    proptest! {
+        #[test]
+        fn any_line_is_answered_or_asked_again(line in any::<String>()) {
+            let choice = answer(&[&line, "3"], || get_int("Pick", &1, &6)).unwrap();
+            prop_assert!((1..=6).contains(&choice));
+
+            let scorecard = Game::new(1).scorecard;
+            let section_i = answer(&[&line, "1", "1"], || get_section(&scorecard)).unwrap();
+            prop_assert!(section_i < scorecard.len());
+
+            let yes = answer(&[&line, "y"], || get_yes_no("Sure?")).unwrap();
+            prop_assert!(yes | matches!(line.trim().to_lowercase().as_str(), "n" | "no"));
+        }
+
+        #[test]
+        fn any_arguments_are_parsed_or_rejected(args in prop::collection::vec(arg(), 0..8)) {
+            let _ = parse_args(args.into_iter());
+        }
+    }
+
+    #[test]
+    fn running_out_of_input_is_an_error() {
+        assert!(matches!(answer(&[], || get_int("Pick", &1, &6)), Err(GameError::EndOfInput)));
+        assert!(matches!(answer(&["99", "\0"], || get_section(&Game::new(1).scorecard)), Err(GameError::EndOfInput)));
+    }
+
+    #[test]
+    fn dice_snapshot() {
+        let cases = [
+            ("unrolled", dice(&[(0, false); 5])),
+            ("rolled", dice(&[(1, false), (6, false), (3, false), (3, false), (5, false)])),
+            ("some frozen", dice(&[(2, true), (4, false), (4, true), (6, false), (1, true)])),
+            ("all frozen", dice(&[(5, true); 5])),
+            ("frozen unrolled", dice(&[(0, true), (0, false), (3, true), (0, true), (6, false)])),
+            ("twelve-sided", vec![Die { num: 12, ..Die::new(12) }, Die { num: 7, frozen: true, ..Die::new(12) }, Die::new(12)]),
+        ];
+        let rendered: String = cases
+            .iter()
+            .map(|(name, dice)| format!("{}:\n{}", name, render(|| display_dice(dice))))
+            .collect();
+        assert_snapshot("dice", &rendered);
+    }
+
+    #[test]
+    fn best_plays_list_the_open_sections_best_first() {
+        let mut game = scored_game(1, 2);
+        game.roll().unwrap();
+        game.set_dice(&dice(&[(6, false), (2, false), (6, false), (3, false), (6, false)]), 2).unwrap();
+
+        // Aces and Twos are filled, ties keep scorecard order, and sections worth nothing name no dice
+        let rendered = render(|| display_best_plays(&game));
+        assert_eq!(
+            rendered,
+            "Best plays for these dice:\n\
+             7. 3 of a Kind: 23 (dice 1, 3, 5)\n\
+             13. Chance: 23 (dice 1, 2, 3, 4, 5)\n\
+             6. Sixes: 18 (dice 1, 3, 5)\n\
+             3. Threes: 3 (dice 4)\n\
+             4. Fours: 0\n\
+             5. Fives: 0\n\
+             8. 4 of a Kind: 0\n\
+             9. YAHTZEE: 0\n\
+             10. Small Straight: 0\n\
+             11. Large Straight: 0\n\
+             12. Full House: 0\n"
+        );
+    }
+
+    #[test]
+    fn the_dice_a_section_counts_are_underlined() {
+        let dice = dice(&[(6, false), (2, false), (6, true), (1, false), (6, false)]);
+        let sixes = &Game::new(1).scorecard[5];
+        let rendered = render(|| display_marked_dice(&dice, &sixes.contributing_dice(&dice)));
+        assert_eq!(rendered, "[ 6 ] [ 2 ] < 6 > [ 1 ] [ 6 ] \n^^^^^       ^^^^^       ^^^^^\n  1     2     3     4     5\n");
+    }
+
+    #[test]
+    fn scorecard_snapshot() {
+        let mut handicapped = Game::new(3);
+        handicapped.handicap(3).unwrap();
+        let cases = [
+            ("empty", Game::new(1).scorecard, 80),
+            ("partial", scored_game(2, 5).scorecard, 80),
+            ("handicap", handicapped.scorecard, 80),
+            ("full", scored_game(4, 13).scorecard, 80),
+            ("full, narrow", scored_game(4, 13).scorecard, 40),
+            ("full, wide", scored_game(4, 13).scorecard, 200),
+        ];
+        let rendered: String = cases
+            .iter()
+            .map(|(name, scorecard, width)| format!("{}:{}\n", name, render(|| display_scorecard_in(scorecard, *width))))
+            .collect();
+        assert_snapshot("scorecard", &rendered);
+    }
+
+    #[test]
+    fn grouped_scorecard_snapshot() {
+        let game = scored_game(5, 13);
+        assert_snapshot("grouped_scorecard", &render(|| display_grouped_scorecard(&game.scorecard, &game.totals())));
+    }
+
+    #[test]
+    fn game_over_snapshot() {
+        assert_snapshot("game_over", &render(|| display_game_over(&scored_game(6, 13))));
+    }
+
+    #[test]
+    fn share_card_snapshot() {
+        // Rolling only 6s scores the most possible in Sixes, the of-a-kind sections, and Chance, scratching the rest
+        let mut sixes = Game::new(7);
+        sixes.script_dice(ScriptedDice::new(&[6]));
+        for section_i in 0..sixes.scorecard.len() {
+            sixes.roll().unwrap();
+            sixes.score(section_i).unwrap();
+        }
+        let mut odd_even = Game::new(8);
+        odd_even.add_odd_even();
+        for section_i in 0..odd_even.scorecard.len() {
+            odd_even.roll().unwrap();
+            odd_even.score(section_i).unwrap();
+        }
+
+        let cases = [("seeded", scored_game(6, 13)), ("all 6s", sixes), ("odd/even", odd_even)];
+        let rendered: String = cases
+            .iter()
+            .map(|(name, game)| format!("{}:\n{}\n", name, share::share_card(game)))
+            .collect();
+        assert_snapshot("share_card", &rendered);
+    }
+
+    #[test]
+    fn botmatch_snapshot() {
+        assert_snapshot("botmatch", &render(|| botmatch(("greedy".to_string(), "random".to_string()), 8, false, None).unwrap()));
+    }
+
+    #[test]
+    fn plays_a_scripted_game() {
+        // On a scorecard of only Chance and Aces: roll once and score each, then decline the CSV and another game
+        let options = Options { seed: Some(7), rules: RuleOptions { sections: Some(vec![1, 13]), ..RuleOptions::default() }, ..Options::default() };
+        let (game, transcript, _) = play_scripted("scripted", &["1", "3", "13", "1", "3", "1", "n", "n"], options, None);
+
+        assert!(transcript.contains("Seed: 7"));
+        assert!(transcript.contains("Turn 2 | Rolls left: 3 | Sections left: 1"));
+        assert!(transcript.contains("13. Chance: 8"), "{}", transcript);
+        assert!(transcript.contains("Game over! Total score: 9"), "{}", transcript);
+        assert_eq!(game.sections_left(), 0);
+        assert_eq!(game.total(), 9);
+    }
+
+    #[test]
+    fn a_game_of_the_upper_sections_ends_once_they_are_filled() {
+        // Roll once and score each of the six upper sections, then decline the CSV and another game
+        let args = ["--seed", "3", "--sections", "1,2,3,4,5,6"].map(str::to_string);
+        let options = parse_args(args.into_iter()).unwrap();
+        let script = ["1", "3", "1", "1", "3", "2", "1", "3", "3", "1", "3", "4", "1", "3", "5", "1", "3", "6", "n", "n"];
+        let (game, transcript, _) = play_scripted("upper", &script, options, None);
+
+        let names: Vec<&str> = game.scorecard.iter().map(|section| section.get_name()).collect();
+        assert_eq!(names, ["1. Aces", "2. Twos", "3. Threes", "4. Fours", "5. Fives", "6. Sixes"]);
+        assert_eq!(game.sections_left(), 0);
+        assert_eq!((game.turn, game.rolls_used), (7, 6));
+
+        // The game is over after six turns, with only the upper sections counted
+        let points: i32 = game.scorecard.iter().map(|section| section.get_points()).sum();
+        assert_eq!(game.total(), points + game.totals().upper_bonus);
+        assert_eq!(game.totals().lower, 0);
+        assert!(transcript.contains(&format!("Game over! Total score: {} in 6 rolls", game.total())), "{}", transcript);
+        assert!(!transcript.contains("Turn 7"), "{}", transcript);
+    }
+
+    #[test]
+    fn the_status_line_counts_turns_and_rolls() {
+        // On a scorecard of only Chance and Aces: roll twice and score Chance, then roll once and score Aces
+        let options = Options { seed: Some(7), rules: RuleOptions { sections: Some(vec![1, 13]), ..RuleOptions::default() }, ..Options::default() };
+        let script = ["1", "1", "3", "13", "1", "3", "1", "n", "n"];
+        let (game, transcript, _) = play_scripted("status", &script, options, None);
+
+        let status_lines: Vec<&str> = transcript.lines().filter(|line| line.starts_with("Turn ")).collect();
+        assert_eq!(
+            status_lines,
+            [
+                "Turn 1 | Rolls left: 3 | Sections left: 2",
+                "Turn 1 | Rolls left: 2 | Sections left: 2",
+                "Turn 1 | Rolls left: 1 | Sections left: 2",
+                "Turn 2 | Rolls left: 3 | Sections left: 1",
+                "Turn 2 | Rolls left: 2 | Sections left: 1",
+            ]
+        );
+        assert_eq!(game.turn, 3);
+        assert_eq!(game.rolls_used, 3);
+    }
+
+    #[test]
+    fn playing_again_starts_a_fresh_game_on_the_next_seed() {
+        // Two games on a scorecard of only Chance and Aces, declining the CSV after each and playing again once
+        let options = || Options { seed: Some(7), rules: RuleOptions { sections: Some(vec![1, 13]), ..RuleOptions::default() }, ..Options::default() };
+        let game_script = ["1", "3", "13", "1", "3", "1", "n"];
+        let script: Vec<&str> = [&game_script[..], &["y"], &game_script[..], &["n"]].concat();
+        let (game, transcript, scores) = play_scripted("again", &script, options(), None);
+
+        // The second game is the one a new game on seed 8 plays, with nothing carried over from the first
+        let mut fresh = with_rules(Game::new(8), &options().rules).unwrap();
+        for player_move in [Move::Roll, Move::Score(1), Move::Roll, Move::Score(0)] {
+            fresh.play(player_move).unwrap();
+        }
+        assert_eq!(game.seed, 8);
+        assert_eq!(game.total(), fresh.total());
+        assert_eq!(game.rolls_used, 2);
+        let points = |game: &Game| game.scorecard.iter().map(|section| section.get_points()).collect::<Vec<i32>>();
+        assert_eq!(points(&game), points(&fresh));
+
+        // It starts on turn 1 with every section open
+        let second = &transcript[transcript.find("Seed: 8").unwrap()..];
+        assert!(transcript.contains("Seed: 7"), "{}", transcript);
+        assert!(second.contains("Turn 1 | Rolls left: 3 | Sections left: 2"), "{}", second);
+        assert_eq!(scores.entries.len(), 2);
+    }
+
+    #[test]
+    fn free_play_keeps_a_running_total_across_scorecards() {
+        // Every roll is all 6s, so each scorecard of only Aces and Chance finishes with 30 (Chance, then a
+        // scratched Aces). The first rolls into a new one, and the second ends free play
+        let options = Options {
+            seed: Some(7),
+            rules: RuleOptions { sections: Some(vec![1, 13]), ..RuleOptions::default() },
+            play: PlayOptions { freeplay: true, ..PlayOptions::default() },
+            ..Options::default()
+        };
+        let card = ["1", "3", "13", "1", "3", "1", "n"];
+        let script: Vec<&str> = [&card[..], &["y"], &card[..], &["n"]].concat();
+        let (game, transcript, scores) = play_scripted("freeplay", &script, options, Some(&[6]));
+
+        assert!(transcript.contains("Continue on a new scorecard?"), "{}", transcript);
+        assert!(!transcript.contains("Play again?"), "{}", transcript);
+        assert!(transcript.contains("Free play total: 30 over 1 scorecards"), "{}", transcript);
+        assert!(transcript.contains("Free play total: 60 (1 scorecards finished)"), "{}", transcript);
+        assert!(transcript.contains("Free play total: 60 over 2 scorecards"), "{}", transcript);
+        assert_eq!(game.total(), 30);
+        assert_eq!(scores.entries.len(), 2);
+    }
+
+    #[test]
+    fn scoring_on_the_first_roll_uses_the_fewest_rolls() {
+        // Roll once and score each section in order, then decline the CSV and another game
+        let mut script = Vec::new();
+        let sections: Vec<String> = (1..=13).map(|number| number.to_string()).collect();
+        for section in &sections {
+            script.extend(["1", "3", section.as_str()]);
+        }
+        script.extend(["n", "n"]);
+        let (game, transcript, scores) = play_scripted("efficient", &script, Options { seed: Some(7), ..Options::default() }, None);
+
+        // Every turn needs at least one roll
+        assert_eq!(game.sections_left(), 0);
+        assert_eq!(game.rolls_used, 13);
+        assert!(transcript.contains(&format!("Total score: {} in 13 rolls", game.total())), "{}", transcript);
+        assert_eq!(scores.efficient.len(), 1);
+        assert_eq!(scores.efficient[0].rolls_used, 13);
+    }
+
+    #[test]
+    fn each_finished_game_appends_a_line() {
+        let path = std::env::temp_dir().join(format!("yahtzee-results-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // Two games from seeds, and one rolling only 6s so its later Yahtzees earn bonuses
+        let mut sixes = Game::new(3);
+        sixes.script_dice(ScriptedDice::new(&[6]));
+        for section_i in 0..sixes.scorecard.len() {
+            sixes.roll().unwrap();
+            sixes.score(section_i).unwrap();
+        }
+        let games = [scored_game(1, 13), scored_game(2, 13), sixes];
+        for game in &games {
+            record_result(&path, "Tester", game);
+        }
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let results: Vec<GameResult> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(results.len(), 3);
+        for (result, game) in results.iter().zip(&games) {
+            assert_eq!(*result, GameResult { timestamp: result.timestamp, ..game_result("Tester", game) });
+            let points: i32 = result.sections.iter().map(|section| section.points).sum();
+            assert_eq!(points + result.bonuses, result.total);
+        }
+        assert!(results[2].bonuses > 0);
+    }
+
+    // A game read from a scenario file in tests/scenarios: what to play, and what it should finish with
+    #[derive(Default)]
+    struct Scenario {
+        options: Vec<String>, // Command line options for the game's rules
+        dice: Vec<u8>, // The numbers the dice roll in order, if not from the seed
+        input: Vec<String>, // Everything typed, one answer a line
+        total: Option<i32>, // The final total
+        sections: Vec<(String, Option<i32>)>, // Sections by name, with their points (None if still open)
+    }
+
+    // Read a scenario. Blank lines and lines starting with # are skipped, and every other line is one of:
+    //   seed <u64>             the seed the dice roll from (1 if not given)
+    //   options <options...>   command line options for the game's rules, ex: options --sections 1,13
+    //   dice <numbers...>      roll these numbers instead, in order, starting over once they run out
+    //   input <answers...>     answers typed at the prompts, separated by spaces
+    //   total <points>         the final total expected
+    //   <section>: <points>    the points expected in a section, as the scorecard shows it (blank if open)
+    fn read_scenario(text: &str) -> Result<Scenario, String> {
+        let mut scenario = Scenario { options: vec!["--seed".to_string(), "1".to_string()], ..Scenario::default() };
+        for (line_i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || format!("Line {}: can't read '{}'", line_i + 1, line);
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let words = rest.split_whitespace().map(str::to_string);
+            match keyword {
+                "seed" => scenario.options[1] = rest.trim().to_string(),
+                "options" => scenario.options.extend(words),
+                "dice" => {
+                    for word in words {
+                        scenario.dice.push(word.parse::<u8>().map_err(|_| bad_line())?);
                     }
                 }
+                "input" => scenario.input.extend(words),
+                "total" => scenario.total = Some(rest.trim().parse::<i32>().map_err(|_| bad_line())?),
+                _ => {
+                    let (name, points) = line.split_once(':').ok_or_else(bad_line)?;
+                    let points = match points.trim() {
+                        "" => None,
+                        points => Some(points.parse::<i32>().map_err(|_| bad_line())?),
+                    };
+                    scenario.sections.push((name.trim().to_string(), points));
+                }
             }
-            // Exit the game
-            0 => {
-                return;
+        }
+        return Ok(scenario);
+    }
+
+    // A section's line on the scorecard, ex: "12. Full House: 25" (or "12. Full House:" when it's open)
+    fn section_line(name: &str, points: Option<i32>) -> String {
+        return match points {
+            Some(points) => format!("{}: {}", name, points),
+            None => format!("{}:", name),
+        };
+    }
+
+    // Play the scenario, returning what didn't finish as expected: the total, then the scorecard with
+    // each section that didn't match shown as it was expected (-) and as it finished (+)
+    fn check_scenario(name: &str, text: &str) -> Result<(), String> {
+        let scenario = read_scenario(text)?;
+        let options = parse_args(scenario.options.into_iter())?;
+        let input: Vec<&str> = scenario.input.iter().map(String::as_str).collect();
+        let dice = if scenario.dice.is_empty() { None } else { Some(&scenario.dice[..]) };
+        let (game, _, _) = run_scripted(&format!("scenario-{}", name), &input, options, dice)?;
+
+        let mut failures = Vec::new();
+        if let Some(total) = scenario.total {
+            if game.total() != total {
+                failures.push(format!("total: expected {}, got {}", total, game.total()));
+            }
+        }
+        for (section, _) in &scenario.sections {
+            if !game.scorecard.iter().any(|played| played.get_name() == section) {
+                failures.push(format!("there is no section '{}'", section));
             }
+        }
 
-            // Invalid menu option
-            _ => {
-                println!("Invalid choice.");
+        let mut scorecard = Vec::new();
+        let mut differs = false;
+        for section in &game.scorecard {
+            let points = if section.is_filled() { Some(section.get_points()) } else { None };
+            let expected = scenario.sections
+                .iter()
+                .find(|(name, _)| name == section.get_name())
+                .map(|(_, expected)| *expected);
+            match expected {
+                Some(expected) if expected != points => {
+                    differs = true;
+                    scorecard.push(format!("- {}", section_line(section.get_name(), expected)));
+                    scorecard.push(format!("+ {}", section_line(section.get_name(), points)));
+                }
+                _ => scorecard.push(format!("  {}", section_line(section.get_name(), points))),
             }
         }
+        if differs {
+            failures.push(format!("scorecard (- expected, + got):\n{}", scorecard.join("\n")));
+        }
+
+        if failures.is_empty() {
+            return Ok(());
+        }
+        return Err(failures.join("\n"));
     }
 
-    // If the game was finished,
-    if !empty_section(&scorecard) {
-        // Display final score
-        println!("Game over! Total score: {}", total_score);
+    #[test]
+    fn every_scenario_plays_out() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+        let mut paths: Vec<PathBuf> = std::fs
+            ::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect();
+        paths.sort();
+        assert!(paths.len() >= 5, "Only {} scenarios in {}", paths.len(), dir.display());
+
+        // Play every scenario before failing, so each one that went wrong is listed
+        let mut failed = Vec::new();
+        for path in &paths {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            let text = std::fs::read_to_string(path).unwrap();
+            if let Err(failure) = check_scenario(&name, &text) {
+                failed.push(format!("Scenario {} failed:\n{}", path.display(), failure));
+            }
+        }
+        assert!(failed.is_empty(), "{}", failed.join("\n\n"));
     }
 }
@@ -0,0 +1,34 @@
+// The chance of finishing the turn with dice that score in each section, worked out exactly
+use crate::die::Die;
+use crate::expected::tables;
+use crate::sections::{ PointSection, Scorecard };
+
+// The chance the turn ends with dice that score points in the section. The next roll keeps the
+// frozen dice (as rolling does), and any rolls after it keep whichever dice give the best chance
+pub fn completion_chance(section: &dyn PointSection, dice: &[Die], rolls_left: u8) -> f64 {
+    if rolls_left == 0 {
+        return if section.calc_score(dice) > 0 { 1.0 } else { 0.0 };
+    }
+
+    // Whether each hand scores, then the chance of getting there from each hand with more rolls
+    let tables = tables();
+    let mut hand_chances: Vec<f64> = tables
+        .hand_scores(section)
+        .iter()
+        .map(|points| if *points > 0 { 1.0 } else { 0.0 })
+        .collect();
+    for _ in 1..rolls_left {
+        hand_chances = tables.hand_values(&tables.kept_values(&hand_chances));
+    }
+    return tables.roll_value(dice, &hand_chances);
+}
+
+// The chance of scoring in every open section, by their index
+pub fn open_chances(scorecard: &Scorecard, dice: &[Die], rolls_left: u8) -> Vec<(usize, f64)> {
+    return scorecard
+        .iter()
+        .enumerate()
+        .filter(|(_, section)| !section.is_filled())
+        .map(|(section_i, section)| (section_i, completion_chance(section.as_ref(), dice, rolls_left)))
+        .collect();
+}
@@ -0,0 +1,108 @@
+// A plain text line protocol for playing from other programs, simpler than the engine's JSON: one
+// command a line (ROLL, FREEZE <die>, SCORE <section>, STATE, QUIT), each answered with one line
+use std::io::{ BufRead, Write };
+
+use crate::game::{ Game, Move, MAX_ROLLS };
+use crate::sections::{ find_sections, numbered_section };
+
+// A list of numbers, separated by commas (empty for none)
+fn number_list(numbers: impl Iterator<Item = String>) -> String {
+    return numbers.collect::<Vec<String>>().join(",");
+}
+
+// The game as a line of space separated key=value fields. Dice and sections count from 1, as in the menu
+pub fn state_line(game: &Game) -> String {
+    let dice = number_list(game.dice.iter().map(|die| die.num.to_string()));
+    let frozen = number_list(
+        game.dice
+            .iter()
+            .enumerate()
+            .filter(|(_, die)| die.frozen)
+            .map(|(die_i, _)| (die_i + 1).to_string())
+    );
+    let open = number_list(
+        game.scorecard
+            .iter()
+            .filter(|section| !section.is_filled())
+            .filter_map(|section| section.get_name().split('.').next())
+            .map(|number| number.to_string())
+    );
+    return format!(
+        "turn={} rolls={} dice={} frozen={} open={} total={} over={}",
+        game.turn,
+        game.rolls,
+        dice,
+        frozen,
+        open,
+        game.total(),
+        !game.in_progress()
+    );
+}
+
+// The index of the section picked by its number or (part of) its name, which has to match only one
+fn pick_section(game: &Game, text: &str) -> Result<usize, String> {
+    if let Ok(number) = text.parse::<usize>() {
+        return numbered_section(&game.scorecard, number).ok_or(format!("There is no section {}", number));
+    }
+    return match find_sections(&game.scorecard, text)[..] {
+        [] => Err(format!("There is no section named '{}'", text)),
+        [section_i] => Ok(section_i),
+        _ => Err(format!("'{}' matches more than one section", text)),
+    };
+}
+
+// Carry out a single command
+fn run_command(game: &mut Game, command: &str, argument: &str) -> Result<(), String> {
+    return match command {
+        "ROLL" => game.play(Move::Roll).map_err(|e| e.to_string()),
+        "FREEZE" => {
+            let die = argument
+                .parse::<usize>()
+                .ok()
+                .filter(|die| (1..=game.dice.len()).contains(die))
+                .ok_or(format!("FREEZE needs a die from 1 to {}", game.dice.len()))?;
+            if !game.in_progress() {
+                return Err("The game is over".to_string());
+            }
+            if game.rolls == MAX_ROLLS {
+                return Err("Roll before freezing dice".to_string());
+            }
+            game.toggle_freeze(die - 1).map_err(|e| e.to_string())
+        }
+        "SCORE" => {
+            let section_i = pick_section(game, argument)?;
+            game.play(Move::Score(section_i)).map_err(|e| e.to_string())
+        }
+        "STATE" => Ok(()),
+        _ => Err(format!("Unknown command '{}'", command)),
+    };
+}
+
+// Answer commands until QUIT or the input ends. Every command is answered with "OK" and the game's
+// state, or "ERROR" and why it couldn't be done; QUIT is answered with "BYE" and the final total
+pub fn run_protocol(mut game: Game, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        // Skip blank lines
+        if line.is_empty() {
+            continue;
+        }
+
+        // The command, then anything after it
+        let (command, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let command = command.to_uppercase();
+        if command == "QUIT" {
+            writeln!(output, "BYE total={}", game.total())?;
+            break;
+        }
+
+        match run_command(&mut game, &command, argument.trim()) {
+            Ok(()) => writeln!(output, "OK {}", state_line(&game))?,
+            Err(message) => writeln!(output, "ERROR {}", message)?,
+        }
+        output.flush()?;
+    }
+    return output.flush();
+}
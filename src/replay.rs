@@ -0,0 +1,244 @@
+// Replays: a game's seed, rules, and every move, so the game can be played back or checked
+use serde::{ Deserialize, Serialize };
+use std::path::{ Path, PathBuf };
+
+use crate::error::GameError;
+use crate::game::{ Game, GameEvent, Move };
+use crate::results::timestamp_now;
+use crate::sections::{ find_sections, numbered_section, UpperBonus, YAHTZEE_BONUS };
+
+// The directory finished games' replays are kept in, inside the data directory
+static REPLAY_DIR: &str = "replays";
+
+// The version of the replay format written by this version of the game
+static VERSION: u32 = 1;
+
+// Everything needed to play a game again, move for move
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32, // The replay format's version
+    pub seed: u64, // The seed the game's dice rolls came from
+    pub joker: bool, // If joker rules were used
+    pub max_holds: Option<usize>, // The most dice that could be frozen at once, if limited
+    pub odd_even: bool, // If the Evens and Odds sections were on the scorecard
+    #[serde(default)]
+    pub sum_straights: bool, // If straights scored the total of their dice (missing from older replays)
+    #[serde(default)]
+    pub max_zeros: Option<u32>, // The most sections that could be scored at 0, if limited (missing from older replays)
+    #[serde(default)]
+    pub nudges: u32, // The nudges allowed (missing from older replays)
+    #[serde(default = "usual_yahtzee_bonus")]
+    pub yahtzee_bonus: i32, // The points for each Yahtzee bonus (missing from older replays)
+    #[serde(default)]
+    pub upper_bonus: UpperBonus, // The upper total that earned the upper bonus, and its points (missing from older replays)
+    #[serde(default)]
+    pub sections: Option<Vec<u8>>, // The standard sections played, if not all of them (missing from older replays)
+    pub handicap: usize, // The number of sections filled by the handicap
+    pub moves: Vec<Move>, // Every move, in order
+    pub total: i32, // The final score recorded when the game was played
+}
+
+impl Replay {
+    // Record a game played from its start, turning its log into moves
+    pub fn new(game: &Game) -> Replay {
+        let mut moves = Vec::new();
+        let mut held: Vec<usize> = Vec::new(); // The dice frozen at this point in the turn
+
+        for event in &game.log {
+            match event {
+                GameEvent::Rolled(_) => moves.push(Move::Roll),
+                GameEvent::Froze(die_i, frozen) => {
+                    // A single freeze holds the dice already held, plus or minus this one
+                    held.retain(|i| i != die_i);
+                    if *frozen {
+                        held.push(*die_i);
+                        held.sort();
+                    }
+                    moves.push(Move::Hold(held.clone()));
+                }
+                GameEvent::Held(dice) => {
+                    held = dice.clone();
+                    moves.push(Move::Hold(held.clone()));
+                }
+                GameEvent::Scored(section_i, _) => {
+                    held.clear();
+                    moves.push(Move::Score(*section_i));
+                }
+                GameEvent::Nudged(die_i, up) => moves.push(Move::Nudge(*die_i, *up)),
+            }
+        }
+
+        return Replay {
+            version: VERSION,
+            seed: game.seed,
+            joker: game.joker,
+            max_holds: game.max_holds,
+            odd_even: game.odd_even,
+            sum_straights: game.sum_straights,
+            max_zeros: game.max_zeros,
+            nudges: game.nudges,
+            yahtzee_bonus: game.yahtzee_bonus,
+            upper_bonus: game.upper_bonus,
+            sections: game.sections.clone(),
+            handicap: game.scorecard
+                .iter()
+                .filter(|section| section.is_handicap())
+                .count(),
+            moves,
+            total: game.total(),
+        };
+    }
+
+    // The game as it was before the first move
+    pub fn start(&self) -> Result<Game, GameError> {
+        let mut game = Game::new(self.seed);
+        if let Some(sections) = &self.sections {
+            game.enable_sections(sections)?;
+        }
+        game.joker = self.joker;
+        game.max_holds = self.max_holds;
+        game.max_zeros = self.max_zeros;
+        game.set_nudges(self.nudges);
+        game.yahtzee_bonus = self.yahtzee_bonus;
+        game.upper_bonus = self.upper_bonus;
+        if self.odd_even {
+            game.add_odd_even();
+        }
+        if self.sum_straights {
+            game.add_sum_straights();
+        }
+        game.handicap(self.handicap)?;
+        return Ok(game);
+    }
+
+    // Play every move without showing anything, returning the finished game
+    pub fn play(&self) -> Result<Game, GameError> {
+        let mut game = self.start()?;
+        for (move_i, player_move) in self.moves.iter().enumerate() {
+            game.play(player_move.clone()).map_err(|e| {
+                GameError::InvalidMove(format!("Move {} ({:?}): {}", move_i + 1, player_move, e))
+            })?;
+        }
+        return Ok(game);
+    }
+}
+
+// The points for a Yahtzee bonus in replays from before it could be changed
+fn usual_yahtzee_bonus() -> i32 {
+    return YAHTZEE_BONUS;
+}
+
+// The section a move list names, by its number or (part of) its name
+fn move_section(game: &Game, text: &str) -> Result<usize, String> {
+    if let Ok(number) = text.parse::<usize>() {
+        if let Some(section_i) = numbered_section(&game.scorecard, number) {
+            return Ok(section_i);
+        }
+        return Err(format!("There is no section {}", number));
+    }
+
+    let matches = find_sections(&game.scorecard, text);
+    return match matches.as_slice() {
+        [section_i] => Ok(*section_i),
+        [] => Err(format!("No section is called '{}'", text)),
+        _ => {
+            let names: Vec<&str> = matches
+                .iter()
+                .map(|section_i| game.scorecard[*section_i].get_name())
+                .collect();
+            Err(format!("'{}' could be {}", text, names.join(", ")))
+        }
+    };
+}
+
+// The dice a move list names, counting from 1 as the menu does
+fn move_dice(words: &[&str]) -> Result<Vec<usize>, String> {
+    return words
+        .iter()
+        .map(|word| {
+            word.parse::<usize>()
+                .ok()
+                .filter(|die| *die > 0)
+                .map(|die| die - 1)
+                .ok_or(format!("'{}' isn't a die number", word))
+        })
+        .collect();
+}
+
+// Play a move list, one move per line with anything after a # ignored:
+// roll, hold <dice>, freeze <die>, reroll, nudge <die> up|down, or score <section> (by number or
+// name, as in a game)
+pub fn play_moves(game: &mut Game, moves: &str) -> Result<(), String> {
+    for (line_i, line) in moves.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, rest) = match words.split_first() {
+            Some((command, rest)) => (command.to_lowercase(), rest),
+            None => {
+                continue;
+            }
+        };
+
+        let played = match command.as_str() {
+            "roll" => game.play(Move::Roll).map_err(|e| e.to_string()),
+            "hold" => move_dice(rest).and_then(|dice| game.play(Move::Hold(dice)).map_err(|e| e.to_string())),
+            "freeze" => {
+                move_dice(rest).and_then(|dice| {
+                    match dice.as_slice() {
+                        [die_i] => game.toggle_freeze(*die_i).map_err(|e| e.to_string()),
+                        _ => Err("freeze needs one die".to_string()),
+                    }
+                })
+            }
+            "reroll" => game.reroll_all().map_err(|e| e.to_string()),
+            "nudge" => {
+                let (dice, direction) = rest.split_at(rest.len().saturating_sub(1));
+                let up = match direction {
+                    ["up"] => Ok(true),
+                    ["down"] => Ok(false),
+                    _ => Err("nudge needs a die then up or down".to_string()),
+                };
+                up.and_then(|up| {
+                    move_dice(dice).and_then(|dice| {
+                        match dice.as_slice() {
+                            [die_i] => game.play(Move::Nudge(*die_i, up)).map_err(|e| e.to_string()),
+                            _ => Err("nudge needs one die".to_string()),
+                        }
+                    })
+                })
+            }
+            "score" => {
+                move_section(game, &rest.join(" ")).and_then(|section_i| {
+                    game.play(Move::Score(section_i)).map_err(|e| e.to_string())
+                })
+            }
+            _ => Err(format!("Unknown move '{}'", command)),
+        };
+        played.map_err(|e| format!("Line {} ({}): {}", line_i + 1, line, e))?;
+    }
+    return Ok(());
+}
+
+// Where a new replay is kept in the data directory, named by the seed and when the game finished
+pub fn replay_path(data_dir: &Path, seed: u64) -> PathBuf {
+    return data_dir.join(REPLAY_DIR).join(format!("{}-{}.json", seed, timestamp_now()));
+}
+
+// Write the replay to a file, creating the directory if needed
+pub fn write_replay(path: &Path, replay: &Replay) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string(replay).map_err(|e| e.to_string())?;
+    return std::fs::write(path, json).map_err(|e| e.to_string());
+}
+
+// Read a replay from a file
+pub fn read_replay(path: &Path) -> Result<Replay, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let replay: Replay = serde_json::from_str(&json).map_err(|e| format!("Not a replay: {}", e))?;
+    if replay.version != VERSION {
+        return Err(format!("Unknown replay version {}", replay.version));
+    }
+    return Ok(replay);
+}
@@ -0,0 +1,76 @@
+// Machine-readable results of finished games, appended as one JSON object per line
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// The points earned in a single scorecard section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionResult {
+    pub name: String,
+    pub points: i32,
+}
+
+// The full result of a finished game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameResult {
+    pub player: String, // Name of the player
+    pub timestamp: u64, // When the game finished (seconds since the Unix epoch)
+    pub seed: u64, // The seed the game's dice rolls came from
+    pub sections: Vec<SectionResult>, // Every section's score, in scorecard order
+    #[serde(default)]
+    pub bonuses: i32, // The upper and Yahtzee bonuses earned (missing from older results)
+    pub total: i32, // The final game score
+    #[serde(default)]
+    pub rolls_used: u32, // The rolls made in the game (missing from older results)
+}
+
+impl GameResult {
+    // Create a result for a game finishing now
+    pub fn new(player: &str, seed: u64, sections: Vec<SectionResult>, bonuses: i32, total: i32, rolls_used: u32) -> GameResult {
+        return GameResult {
+            player: player.to_string(),
+            timestamp: timestamp_now(),
+            seed,
+            sections,
+            bonuses,
+            total,
+            rolls_used,
+        };
+    }
+}
+
+// The current time in seconds since the Unix epoch
+pub fn timestamp_now() -> u64 {
+    // A clock before 1970 isn't worth failing over, record it as 0
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+}
+
+// Format a timestamp as a date (YYYY-MM-DD, in UTC)
+pub fn format_date(timestamp: u64) -> String {
+    // Count days from 0000-03-01, so leap days fall at the end of each year
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097); // 400 year cycles
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // Months starting from March
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + (if month <= 2 { 1 } else { 0 });
+    return format!("{:04}-{:02}-{:02}", year, month, day);
+}
+
+// Append the result as a single line of JSON, creating the file if needed
+pub fn append_result(path: &Path, result: &GameResult) -> std::io::Result<()> {
+    let line = serde_json::to_string(result)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    return Ok(());
+}
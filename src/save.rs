@@ -0,0 +1,382 @@
+// Saving a game in progress to a JSON file, and loading it back
+use serde::{ Deserialize, Serialize };
+use serde_json::{ json, Value };
+use std::path::{ Path, PathBuf };
+
+use crate::achievements::Achievement;
+use crate::die::Die;
+use crate::game::{ Game, MAX_ROLLS };
+use crate::results::timestamp_now;
+use crate::sections::{ from_snapshot, SectionKind, SectionSnapshot, UpperBonus, YAHTZEE_BONUS };
+
+// The directory saves are kept in, inside the data directory
+static SAVE_DIR: &str = "saves";
+
+// The name of the save written after every turn
+static AUTOSAVE_NAME: &str = "autosave";
+
+// The version of the save format written by this version of the game
+static SAVE_VERSION: u32 = 2;
+
+// A section of the scorecard as it was saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSection {
+    pub name: String,
+    pub filled: bool,
+    pub points: i32,
+    pub handicap: bool,
+    #[serde(default)]
+    pub kind: Option<(SectionKind, u8)>, // How the section is scored (missing from older saves)
+}
+
+// Everything needed to continue a game exactly where it was left
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32, // The save format's version
+    pub saved_at: u64, // When the game was saved (seconds since the Unix epoch)
+    pub player: String, // Name of the player
+    pub total: i32, // The total score when saved, for listing saves
+    pub seed: u64, // The seed the game's dice rolls come from
+    pub rng_position: u128, // How far through the seed's rolls the game was
+    pub joker: bool, // If joker rules were being used
+    pub max_holds: Option<usize>, // The most dice that could be frozen at once, if limited
+    pub max_zeros: Option<u32>, // The most sections that could be scored at 0, if limited
+    pub nudges: u32, // The nudges allowed each game
+    pub nudges_remaining: u32, // The nudges left this game
+    pub sections: Option<Vec<u8>>, // The standard sections on the scorecard, if not all of them
+    pub odd_even: bool, // If the Evens and Odds sections were on the scorecard
+    pub sum_straights: bool, // If straights scored the total of their dice
+    pub rolls: u8, // Rolls left in the turn
+    pub yahtzees: u32, // Yahtzees rolled so far
+    pub rolls_used: u32, // Rolls made so far
+    pub achievements: Vec<(u32, Achievement)>, // Everything achieved so far, with the turn it was in
+    pub yahtzee_bonuses: u32, // Yahtzee bonuses earned so far
+    pub yahtzee_bonus: i32, // The points for each Yahtzee bonus
+    pub upper_bonus: UpperBonus, // The upper total that earns the upper bonus, and its points
+    pub turn: u32, // The turn being played
+    pub dice: Vec<Die>, // The dice, with their numbers and frozen states
+    pub scorecard: Vec<SavedSection>, // Every section, in scorecard order
+}
+
+impl SavedGame {
+    // Take a copy of the game's state
+    pub fn new(game: &Game, player: &str) -> SavedGame {
+        let scorecard = game.scorecard
+            .iter()
+            .map(|section| {
+                let snapshot = section.snapshot();
+                SavedSection {
+                    name: snapshot.0,
+                    filled: snapshot.1,
+                    points: snapshot.2,
+                    handicap: section.is_handicap(),
+                    kind: Some((snapshot.3, snapshot.4)),
+                }
+            })
+            .collect();
+
+        return SavedGame {
+            version: SAVE_VERSION,
+            saved_at: timestamp_now(),
+            player: player.to_string(),
+            total: game.total(),
+            seed: game.seed,
+            rng_position: game.rng_position(),
+            joker: game.joker,
+            max_holds: game.max_holds,
+            max_zeros: game.max_zeros,
+            nudges: game.nudges,
+            nudges_remaining: game.nudges_remaining,
+            sections: game.sections.clone(),
+            odd_even: game.odd_even,
+            sum_straights: game.sum_straights,
+            rolls: game.rolls,
+            yahtzees: game.yahtzees,
+            rolls_used: game.rolls_used,
+            achievements: game.achievements.clone(),
+            yahtzee_bonuses: game.totals().yahtzee_bonuses,
+            yahtzee_bonus: game.yahtzee_bonus,
+            upper_bonus: game.upper_bonus,
+            turn: game.turn,
+            dice: game.dice.to_vec(),
+            scorecard,
+        };
+    }
+
+    // Rebuild the game from the saved state
+    pub fn restore(&self) -> Result<Game, String> {
+        let mut game = Game::new(self.seed);
+        game.set_rng_position(self.rng_position);
+        if let Some(sections) = &self.sections {
+            game.enable_sections(sections).map_err(|e| e.to_string())?;
+        }
+        game.joker = self.joker;
+        game.max_holds = self.max_holds;
+        game.max_zeros = self.max_zeros;
+        game.nudges = self.nudges;
+        game.nudges_remaining = self.nudges_remaining;
+        game.yahtzee_bonus = self.yahtzee_bonus;
+        game.upper_bonus = self.upper_bonus;
+        if self.odd_even {
+            game.add_odd_even();
+        }
+        if self.sum_straights {
+            game.add_sum_straights();
+        }
+
+        // The saved game must fit this version's game
+        if self.rolls > MAX_ROLLS {
+            return Err(format!("The save has {} rolls left, more than {}", self.rolls, MAX_ROLLS));
+        }
+        if self.dice.len() != game.dice.len() {
+            return Err(format!("The save has {} dice, not {}", self.dice.len(), game.dice.len()));
+        }
+        if self.scorecard.len() != game.scorecard.len() {
+            return Err(
+                format!(
+                    "The save has {} sections, not {}",
+                    self.scorecard.len(),
+                    game.scorecard.len()
+                )
+            );
+        }
+        game.rolls = self.rolls;
+        game.yahtzees = self.yahtzees;
+        game.rolls_used = self.rolls_used;
+        game.achievements = self.achievements.clone();
+        game.turn = self.turn;
+        game.dice.clone_from_slice(&self.dice);
+
+        // Fill in every section that had been scored
+        for (section, saved) in game.scorecard.iter_mut().zip(&self.scorecard) {
+            // Older saves are assumed to be scored the way the section in their place is
+            let (kind, value) = saved.kind.unwrap_or(section.kind());
+            let snapshot = SectionSnapshot(
+                saved.name.clone(),
+                saved.filled & !saved.handicap,
+                saved.points,
+                kind,
+                value
+            );
+            *section = from_snapshot(&snapshot).ok_or(
+                format!("The save's section '{}' isn't on the scorecard", saved.name)
+            )?;
+
+            if saved.handicap {
+                section.set_handicap(saved.points)?;
+            }
+        }
+        game.recount_totals(self.yahtzee_bonuses);
+        return Ok(game);
+    }
+}
+
+// Why a saved game couldn't be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadError {
+    Missing(String), // There's no save there
+    NewerVersion(String), // Made by a newer version of the game
+    Unreadable(String), // Not a save this version can continue
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return match self {
+            | LoadError::Missing(message)
+            | LoadError::NewerVersion(message)
+            | LoadError::Unreadable(message) => write!(f, "{}", message),
+        };
+    }
+}
+
+// The summary of a save, read without the rest of the game so any version's saves can be listed
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SaveSummary {
+    #[serde(default)]
+    pub saved_at: u64, // When the game was saved (seconds since the Unix epoch)
+    pub player: String, // Name of the player
+    #[serde(default)]
+    pub total: i32, // The total score when saved
+}
+
+// Save names are used as file names, so they're kept simple
+pub fn valid_name(name: &str) -> bool {
+    let valid = name.chars().all(|c| c.is_alphanumeric() | (c == '-') | (c == '_'));
+    return !name.is_empty() & valid;
+}
+
+// Where a save with the given name is kept, in the data directory
+pub fn save_path(data_dir: &Path, name: &str) -> PathBuf {
+    return data_dir.join(SAVE_DIR).join(format!("{}.json", name));
+}
+
+// Where the save to load is, given either a path or the name of a save in the data directory
+pub fn find_save(data_dir: &Path, path_or_name: &str) -> PathBuf {
+    // A file there is always used, otherwise a save name is looked for in the saves
+    let path = PathBuf::from(path_or_name);
+    if !path.exists() & valid_name(path_or_name) {
+        return save_path(data_dir, path_or_name);
+    }
+    return path;
+}
+
+// Where the game is saved after every turn, in the data directory
+pub fn autosave_path(data_dir: &Path) -> PathBuf {
+    return save_path(data_dir, AUTOSAVE_NAME);
+}
+
+// Write the saved game to a file, creating the directory if needed
+pub fn write_save(path: &Path, saved: &SavedGame) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(saved).map_err(|e| e.to_string())?;
+
+    // Write to a temporary file first, so a crash never leaves half a save behind
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json).map_err(|e| e.to_string())?;
+    return std::fs::rename(&temp_path, path).map_err(|e| e.to_string());
+}
+
+// Move the autosave aside (keeping it) so the next game doesn't overwrite it
+pub fn archive_autosave(data_dir: &Path) -> Result<PathBuf, String> {
+    // Name it by when it was archived, counting up if that name is taken
+    let name = format!("{}-{}", AUTOSAVE_NAME, timestamp_now());
+    let mut archive_path = save_path(data_dir, &name);
+    let mut count = 1;
+    while archive_path.exists() {
+        count += 1;
+        archive_path = save_path(data_dir, &format!("{}-{}", name, count));
+    }
+
+    std::fs::rename(autosave_path(data_dir), &archive_path).map_err(|e| e.to_string())?;
+    return Ok(archive_path);
+}
+
+// Upgrade a save written in an older format to the current one, one version at a time
+fn migrate(mut save: Value) -> Result<Value, LoadError> {
+    // Saves from before the format was versioned are version 1
+    let version = save
+        .get("version")
+        .map_or(Some(1), |version| version.as_u64())
+        .ok_or(LoadError::Unreadable("Not a saved game: the version isn't a number".to_string()))?;
+    if version > u64::from(SAVE_VERSION) {
+        return Err(
+            LoadError::NewerVersion(
+                format!(
+                    "This save was made by a newer version of the game (save format {}, this version reads up to {})",
+                    version,
+                    SAVE_VERSION
+                )
+            )
+        );
+    }
+
+    if version < 2 {
+        migrate_from_v1(&mut save).map_err(LoadError::Unreadable)?;
+    }
+    return Ok(save);
+}
+
+// Version 1 didn't have the timestamp, total, turn, or the counts and rules added since. The rules
+// default to how the game was played without them, and the counts come from the scorecard
+fn migrate_from_v1(save: &mut Value) -> Result<(), String> {
+    let object = save.as_object_mut().ok_or("Not a saved game: not a JSON object")?;
+    let scorecard = object
+        .get("scorecard")
+        .and_then(|scorecard| scorecard.as_array())
+        .ok_or("Not a saved game: there's no scorecard")?;
+
+    // The total and turn come from the sections already filled
+    let total: i64 = scorecard
+        .iter()
+        .filter_map(|section| section.get("points")?.as_i64())
+        .sum();
+    let played = scorecard
+        .iter()
+        .filter(|section| {
+            let filled = section.get("filled").and_then(|filled| filled.as_bool());
+            let handicap = section.get("handicap").and_then(|handicap| handicap.as_bool());
+            (filled == Some(true)) & (handicap != Some(true))
+        })
+        .count() as u64;
+
+    // Rolls weren't counted, so the game is counted as using the fewest it could have: one for every
+    // turn played, and the rolls already made this turn
+    let rolls = object.get("rolls").and_then(|rolls| rolls.as_u64()).ok_or("Not a saved game: there are no rolls")?;
+    let turn = object.get("turn").and_then(|turn| turn.as_u64()).unwrap_or(played + 1);
+    let rolls_used = turn.saturating_sub(1) + u64::from(MAX_ROLLS).saturating_sub(rolls);
+
+    // Some version 1 saves already had some of these, keep those
+    object.entry("saved_at").or_insert(json!(0));
+    object.entry("total").or_insert(json!(total));
+    object.entry("turn").or_insert(json!(turn));
+    object.entry("yahtzees").or_insert(json!(0));
+    object.entry("rolls_used").or_insert(json!(rolls_used));
+    object.entry("achievements").or_insert(json!([]));
+    object.entry("yahtzee_bonuses").or_insert(json!(0));
+    object.entry("max_holds").or_insert(Value::Null);
+    object.entry("max_zeros").or_insert(Value::Null);
+    object.entry("nudges").or_insert(json!(0));
+    object.entry("nudges_remaining").or_insert(json!(0));
+    object.entry("sections").or_insert(Value::Null);
+    object.entry("odd_even").or_insert(json!(false));
+    object.entry("sum_straights").or_insert(json!(false));
+    object.entry("yahtzee_bonus").or_insert(json!(YAHTZEE_BONUS));
+    object.entry("upper_bonus").or_insert(json!(UpperBonus::default()));
+    object.insert("version".to_string(), json!(2));
+    return Ok(());
+}
+
+// Read a saved game from a file, upgrading it from an older format if needed
+pub fn read_save(path: &Path) -> Result<SavedGame, LoadError> {
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            LoadError::Missing(format!("There's no save at {}", path.display()))
+        } else {
+            LoadError::Unreadable(e.to_string())
+        }
+    })?;
+    let save: Value = serde_json
+        ::from_str(&json)
+        .map_err(|e| LoadError::Unreadable(format!("Not a saved game: {}", e)))?;
+    return serde_json
+        ::from_value(migrate(save)?)
+        .map_err(|e| LoadError::Unreadable(format!("Not a saved game: {}", e)));
+}
+
+// Every save in the data directory, by name and summary (None if it can't be read), sorted by name
+pub fn list_saves(data_dir: &Path) -> Result<Vec<(String, Option<SaveSummary>)>, String> {
+    let entries = match std::fs::read_dir(data_dir.join(SAVE_DIR)) {
+        Ok(entries) => entries,
+        // Nothing has been saved yet
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Vec::new());
+        }
+        Err(e) => {
+            return Err(e.to_string());
+        }
+    };
+
+    let mut saves = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension() != Some("json".as_ref()) {
+            continue;
+        }
+
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let summary = std::fs
+            ::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        saves.push((name, summary));
+    }
+    saves.sort_by(|a, b| a.0.cmp(&b.0));
+    return Ok(saves);
+}
+
+// Delete the save with the given name from the data directory
+pub fn delete_save(data_dir: &Path, name: &str) -> Result<(), String> {
+    return std::fs::remove_file(save_path(data_dir, name)).map_err(|e| e.to_string());
+}
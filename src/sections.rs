@@ -0,0 +1,765 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::die::Die;
+use crate::game::NUM_DICE;
+use crate::say;
+pub use yahtzee_scoring::{ FaceCounts, ScoreSummary, UpperBonus, YAHTZEE_BONUS };
+use yahtzee_scoring::{ face_score, of_a_kind_score, parity_score, straight_score, yahtzee_score, MAX_FACE, YAHTZEE_POINTS };
+
+// Why a section can't be scored again
+fn already_set(score: &Score) -> String {
+    return format!("{} was already scored ({} points)", score.name, score.points);
+}
+
+// Getters, immutable and perform the same for ALL scorecard Sections
+pub trait Section {
+    fn is_filled(&self) -> bool;
+    fn get_points(&self) -> i32;
+    fn get_name(&self) -> &'static str;
+    fn is_handicap(&self) -> bool;
+    fn print(&self, name_width: usize);
+}
+
+// The ability to calculate points from a slice of Die
+pub trait Points {
+    fn calc_score(&self, dice: &[Die]) -> i32;
+
+    // Fill with the given score, refused if the section was already scored
+    fn set_score(&mut self, score: i32) -> Result<(), String>;
+
+    // The typical points scored in this section, used for handicaps
+    fn average_score(&self) -> i32;
+
+    // The most points the section could score
+    fn max_possible_score(&self) -> i32;
+
+    // Fill with a handicap score before the game starts
+    fn set_handicap(&mut self, score: i32) -> Result<(), String>;
+
+    // Empty the section, for a new game
+    fn clear_score(&mut self);
+
+    // The Die value the section counts, if it is in the upper section
+    fn face_value(&self) -> Option<u8> {
+        return None;
+    }
+
+    // The indexes of the dice that make the section score (ex: the 6s for Sixes), to highlight them.
+    // Sections that don't say score without highlighting any
+    fn contributing_dice(&self, _dice: &[Die]) -> Vec<usize> {
+        return Vec::new();
+    }
+
+}
+
+// All Scores have these attributes and implement Section
+pub struct Score {
+    pub filled: bool, // If the score section has been filled
+    pub points: i32, // The point value of the score section
+    pub name: &'static str, // Name of the score section
+    pub handicap: bool, // If the section was filled by a handicap, not by playing
+}
+impl Score {
+    // Create an empty score section
+    pub fn new(name: &'static str) -> Score {
+        return Score {
+            filled: false,
+            points: 0,
+            name,
+            handicap: false,
+        };
+    }
+
+    // Empty the section, keeping its name
+    pub fn clear(&mut self) {
+        self.filled = false;
+        self.points = 0;
+        self.handicap = false;
+    }
+}
+impl Section for Score {
+    fn is_filled(&self) -> bool {
+        return self.filled;
+    }
+    fn get_points(&self) -> i32 {
+        return self.points;
+    }
+    fn get_name(&self) -> &'static str {
+        return self.name;
+    }
+    fn is_handicap(&self) -> bool {
+        return self.handicap;
+    }
+    fn print(&self, name_width: usize) {
+        // Display points if filled (marked if from a handicap), otherwise empty string
+        let points = if self.handicap {
+            format!("{}*", self.points)
+        } else if self.filled {
+            self.points.to_string()
+        } else {
+            " ".to_string()
+        };
+
+        // Pad so sections line up, whatever the length of their names
+        let entry = format!("{}: {}", self.name, points);
+        say!("{: <width$}", entry, width = name_width + 6);
+    }
+
+}
+
+// Count the numbers the dice show, for scoring
+pub fn counts(dice: &[Die]) -> FaceCounts {
+    return FaceCounts::new(dice.iter().map(|die| die.num));
+}
+
+// The indexes of the rolled dice showing a number that counts
+fn dice_showing(dice: &[Die], counts: impl Fn(u8) -> bool) -> Vec<usize> {
+    return dice
+        .iter()
+        .enumerate()
+        .filter(|(_, die)| (die.num != 0) & counts(die.num))
+        .map(|(die_i, _)| die_i)
+        .collect();
+}
+
+// Get points for having specific number/value
+pub struct Section1 {
+    pub score: Score, // Has a Score section
+    pub value: u8, // The Die value that counts for points
+}
+impl Points for Section1 {
+    fn calc_score(&self, dice: &[Die]) -> i32 {
+        // Only add points for the dice of the specified value
+        return face_score(&counts(dice), self.value);
+    }
+    fn set_score(&mut self, score: i32) -> Result<(), String> {
+        // Check the score hasn't already been set, keeping it if it has
+        if self.score.filled | (self.score.points != 0) {
+            return Err(already_set(&self.score));
+        }
+
+        // Fill with the given score
+        self.score.filled = true;
+        self.score.points = score;
+        return Ok(());
+    }
+    fn average_score(&self) -> i32 {
+        // Roughly what players score for each value
+        return match self.value {
+            1 => 2,
+            2 => 5,
+            3 => 9,
+            4 => 12,
+            5 => 16,
+            _ => (self.value as i32) * 3,
+        };
+    }
+    fn max_possible_score(&self) -> i32 {
+        // Every die showing the value
+        return (self.value as i32) * (NUM_DICE as i32);
+    }
+    fn face_value(&self) -> Option<u8> {
+        return Some(self.value);
+    }
+    fn contributing_dice(&self, dice: &[Die]) -> Vec<usize> {
+        return dice_showing(dice, |num| num == self.value);
+    }
+    fn set_handicap(&mut self, score: i32) -> Result<(), String> {
+        self.set_score(score)?;
+        self.score.handicap = true;
+        return Ok(());
+    }
+    fn clear_score(&mut self) {
+        self.score.clear();
+    }
+}
+// To access score's values at the top level..
+impl Section for Section1 {
+    fn get_points(&self) -> i32 {
+        return self.score.get_points();
+    }
+    fn is_filled(&self) -> bool {
+        return self.score.is_filled();
+    }
+    fn get_name(&self) -> &'static str {
+        return self.score.get_name();
+    }
+    fn is_handicap(&self) -> bool {
+        return self.score.is_handicap();
+    }
+    fn print(&self, name_width: usize) {
+        self.score.print(name_width);
+    }
+}
+
+// Get points for having # of a kind, YAHTZEE = 5 of a kind
+pub struct Section2 {
+    pub score: Score,
+    pub value: u8,
+}
+impl Points for Section2 {
+    fn calc_score(&self, dice: &[Die]) -> i32 {
+        // Every die the same is a YAHTZEE, a fixed 50 points [Hasbro Yahtzee rules]
+        let counts = counts(dice);
+        if usize::from(self.value) == NUM_DICE {
+            return yahtzee_score(&counts, self.value);
+        }
+
+        // If enough of a single type, points = dice total (so a YAHTZEE also scores as 3 or 4 of a kind)
+        return of_a_kind_score(&counts, self.value);
+    }
+    fn set_score(&mut self, score: i32) -> Result<(), String> {
+        // Check the score hasn't already been set, keeping it if it has
+        if self.score.filled | (self.score.points != 0) {
+            return Err(already_set(&self.score));
+        }
+
+        // Fill with the given score
+        self.score.filled = true;
+        self.score.points = score;
+        return Ok(());
+    }
+    fn average_score(&self) -> i32 {
+        // Roughly what players score for 3, 4, and 5 of a kind, or chance
+        return match self.value {
+            3 => 22,
+            4 => 13,
+            5 => 16,
+            _ => 22,
+        };
+    }
+    fn max_possible_score(&self) -> i32 {
+        // A YAHTZEE's fixed points, or every die showing a six
+        if usize::from(self.value) == NUM_DICE {
+            return YAHTZEE_POINTS;
+        }
+        return 6 * (NUM_DICE as i32);
+    }
+    fn contributing_dice(&self, dice: &[Die]) -> Vec<usize> {
+        // Chance takes every die
+        if self.value == 0 {
+            return dice_showing(dice, |_| true);
+        }
+
+        // Otherwise the dice showing the number there's enough of (if the section scores at all)
+        if self.calc_score(dice) == 0 {
+            return Vec::new();
+        }
+        let counts = counts(dice);
+        return dice_showing(dice, |num| counts.count(num) >= self.value);
+    }
+    fn set_handicap(&mut self, score: i32) -> Result<(), String> {
+        self.set_score(score)?;
+        self.score.handicap = true;
+        return Ok(());
+    }
+    fn clear_score(&mut self) {
+        self.score.clear();
+    }
+}
+impl Section for Section2 {
+    fn get_points(&self) -> i32 {
+        return self.score.get_points();
+    }
+    fn is_filled(&self) -> bool {
+        return self.score.is_filled();
+    }
+    fn get_name(&self) -> &'static str {
+        return self.score.get_name();
+    }
+    fn is_handicap(&self) -> bool {
+        return self.score.is_handicap();
+    }
+    fn print(&self, name_width: usize) {
+        self.score.print(name_width);
+    }
+}
+
+// Small (3), Large(4), and full/one-of-a-kind(5) straights (num = num in a row needed)
+// How a straight is scored: a fixed amount for its length, or the total of its dice (a house rule)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StraightScoring {
+    #[default]
+    Fixed,
+    DiceSum,
+}
+
+pub struct Section3 {
+    pub score: Score,
+    pub value: u8,
+    pub scoring: StraightScoring,
+}
+impl Points for Section3 {
+    fn calc_score(&self, dice: &[Die]) -> i32 {
+        // 30, 40, or 50 (depending on straight size), or the dice total
+        return straight_score(&counts(dice), self.value, self.scoring == StraightScoring::DiceSum);
+    }
+    fn set_score(&mut self, score: i32) -> Result<(), String> {
+        // Check the score hasn't already been set, keeping it if it has
+        if self.score.filled | (self.score.points != 0) {
+            return Err(already_set(&self.score));
+        }
+
+        // Fill with the given score
+        self.score.filled = true;
+        self.score.points = score;
+        return Ok(());
+    }
+    fn average_score(&self) -> i32 {
+        // Roughly what players score for each straight, longer ones are missed more often
+        return match (self.scoring, self.value) {
+            (StraightScoring::Fixed, 3) => 24,
+            (StraightScoring::Fixed, 4) => 26,
+            (StraightScoring::Fixed, _) => 20,
+            (StraightScoring::DiceSum, 3) => 14,
+            (StraightScoring::DiceSum, 4) => 12,
+            (StraightScoring::DiceSum, _) => 7,
+        };
+    }
+    fn max_possible_score(&self) -> i32 {
+        return match self.scoring {
+            // Straights are worth a fixed amount
+            StraightScoring::Fixed => (self.value as i32) * 10,
+            // The run ending in 6, with every other die showing 6
+            StraightScoring::DiceSum => {
+                let run: i32 = (7 - self.value..=6).map(i32::from).sum();
+                run + 6 * ((NUM_DICE as i32) - (self.value as i32))
+            }
+        };
+    }
+    fn contributing_dice(&self, dice: &[Die]) -> Vec<usize> {
+        // The numbers in any run long enough (a duplicate number isn't part of the run)
+        let counts = counts(dice);
+        let mut in_run = [false; MAX_FACE + 1];
+        for start in 1..=(MAX_FACE as u8 + 1).saturating_sub(self.value) {
+            let run = start..start + self.value;
+            if run.clone().all(|num| counts.count(num) > 0) {
+                for num in run {
+                    in_run[usize::from(num)] = true;
+                }
+            }
+        }
+
+        // The first die showing each of them
+        let mut contributing = Vec::new();
+        for (die_i, die) in dice.iter().enumerate() {
+            let num = usize::from(die.num);
+            if (num < in_run.len()) && in_run[num] {
+                in_run[num] = false;
+                contributing.push(die_i);
+            }
+        }
+        return contributing;
+    }
+    fn set_handicap(&mut self, score: i32) -> Result<(), String> {
+        self.set_score(score)?;
+        self.score.handicap = true;
+        return Ok(());
+    }
+    fn clear_score(&mut self) {
+        self.score.clear();
+    }
+}
+impl Section for Section3 {
+    fn get_points(&self) -> i32 {
+        return self.score.get_points();
+    }
+    fn is_filled(&self) -> bool {
+        return self.score.is_filled();
+    }
+    fn get_name(&self) -> &'static str {
+        return self.score.get_name();
+    }
+    fn is_handicap(&self) -> bool {
+        return self.score.is_handicap();
+    }
+    fn print(&self, name_width: usize) {
+        self.score.print(name_width);
+    }
+}
+
+// Get points for the total of all odd (value 1) or all even (value 0) dice
+pub struct Section4 {
+    pub score: Score,
+    pub value: u8,
+}
+impl Points for Section4 {
+    fn calc_score(&self, dice: &[Die]) -> i32 {
+        // Only add points for the dice of the right parity (unrolled dice add nothing)
+        return parity_score(dice.iter().map(|die| die.num), self.value);
+    }
+    fn set_score(&mut self, score: i32) -> Result<(), String> {
+        // Check the score hasn't already been set, keeping it if it has
+        if self.score.filled | (self.score.points != 0) {
+            return Err(already_set(&self.score));
+        }
+
+        // Fill with the given score
+        self.score.filled = true;
+        self.score.points = score;
+        return Ok(());
+    }
+    fn average_score(&self) -> i32 {
+        // Roughly what players score for evens and odds
+        return match self.value {
+            0 => 14,
+            _ => 10,
+        };
+    }
+    fn max_possible_score(&self) -> i32 {
+        // Every die showing the highest number of the parity
+        return (if self.value == 0 { 6 } else { 5 }) * (NUM_DICE as i32);
+    }
+    fn contributing_dice(&self, dice: &[Die]) -> Vec<usize> {
+        return dice_showing(dice, |num| num % 2 == self.value);
+    }
+    fn set_handicap(&mut self, score: i32) -> Result<(), String> {
+        self.set_score(score)?;
+        self.score.handicap = true;
+        return Ok(());
+    }
+    fn clear_score(&mut self) {
+        self.score.clear();
+    }
+}
+impl Section for Section4 {
+    fn get_points(&self) -> i32 {
+        return self.score.get_points();
+    }
+    fn is_filled(&self) -> bool {
+        return self.score.is_filled();
+    }
+    fn get_name(&self) -> &'static str {
+        return self.score.get_name();
+    }
+    fn is_handicap(&self) -> bool {
+        return self.score.is_handicap();
+    }
+    fn print(&self, name_width: usize) {
+        self.score.print(name_width);
+    }
+}
+
+// The kinds of section, which (with a value) decide how a section is scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SectionKind {
+    Face, // Points for a specific number (Section1)
+    OfAKind, // Points for a number of a kind (Section2)
+    Straight, // Points for a straight (Section3)
+    SumStraight, // The total of the dice for a straight (Section3, sum straights rule)
+    Chance, // Points for anything (Section2, 0 of a kind)
+    Parity, // Points for the odd or even dice (Section4)
+}
+
+// A section as a serializable tuple: (name, filled, points, kind, value)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionSnapshot(pub String, pub bool, pub i32, pub SectionKind, pub u8);
+
+/// Combination of the traits Points and Section, all structs
+///  implementing both can be in a collection of &dyn PointSections
+///
+/// ```
+/// use yahtzee::die::Die;
+/// use yahtzee::sections::{ create_scorecard, SectionKind };
+///
+/// let dice: Vec<Die> = [2, 3, 4, 5, 5].iter().map(|num| Die { num: *num, ..Die::default() }).collect();
+/// let scorecard = create_scorecard();
+/// let large = scorecard.iter().find(|section| section.kind() == (SectionKind::Straight, 4)).unwrap();
+/// assert_eq!(large.calc_score(&dice), 40);
+/// ```
+pub trait PointSection: Points + Section {
+    // The section's kind and value, which together with its name identify it
+    fn kind(&self) -> (SectionKind, u8);
+
+    // Fill with the score calculated from the dice, so the two can't differ, returning the points
+    // stored. A section that was already scored keeps its points
+    fn score_section(&mut self, dice: &[Die]) -> Result<i32, String> {
+        if self.is_filled() {
+            return Err(format!("{} was already scored ({} points)", self.get_name(), self.get_points()));
+        }
+        self.set_score(self.calc_score(dice))?;
+        return Ok(self.get_points());
+    }
+
+    // Take a snapshot of the section, without needing to know its type
+    fn snapshot(&self) -> SectionSnapshot {
+        let (kind, value) = self.kind();
+        return SectionSnapshot(
+            self.get_name().to_string(),
+            self.is_filled(),
+            self.get_points(),
+            kind,
+            value
+        );
+    }
+}
+
+// All score sections are under a shared trait
+impl PointSection for Section1 {
+    fn kind(&self) -> (SectionKind, u8) {
+        return (SectionKind::Face, self.value);
+    }
+}
+impl PointSection for Section2 {
+    fn kind(&self) -> (SectionKind, u8) {
+        // Chance is 0 of a kind
+        let kind = if self.value == 0 { SectionKind::Chance } else { SectionKind::OfAKind };
+        return (kind, self.value);
+    }
+}
+impl PointSection for Section3 {
+    fn kind(&self) -> (SectionKind, u8) {
+        let kind = match self.scoring {
+            StraightScoring::Fixed => SectionKind::Straight,
+            StraightScoring::DiceSum => SectionKind::SumStraight,
+        };
+        return (kind, self.value);
+    }
+}
+
+// An empty copy of a straight section that scores the total of its dice, None if it isn't a straight
+pub fn sum_straight(section: &dyn PointSection) -> Option<Box<dyn PointSection>> {
+    return match section.kind() {
+        (SectionKind::Straight | SectionKind::SumStraight, value) => {
+            Some(
+                Box::new(Section3 {
+                    score: Score::new(section.get_name()),
+                    value,
+                    scoring: StraightScoring::DiceSum,
+                })
+            )
+        }
+        _ => None,
+    };
+}
+impl PointSection for Section4 {
+    fn kind(&self) -> (SectionKind, u8) {
+        return (SectionKind::Parity, self.value);
+    }
+}
+
+// Rebuild a section from its snapshot, if it is one of the sections this version knows
+pub fn from_snapshot(snapshot: &SectionSnapshot) -> Option<Box<dyn PointSection>> {
+    let SectionSnapshot(name, filled, points, kind, value) = snapshot;
+
+    // Find the matching section among every one that can be on a scorecard, straights scored either way
+    let mut sections: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+    let sum_straights: Scorecard = sections
+        .iter()
+        .filter_map(|section| sum_straight(section.as_ref()))
+        .collect();
+    sections.extend(sum_straights);
+    let mut section = sections
+        .into_iter()
+        .find(|section| (section.get_name() == name) & (section.kind() == (*kind, *value)))?;
+
+    if *filled {
+        section.set_score(*points).ok()?;
+    }
+    return Some(section);
+}
+
+/// A full scorecard, every section the player can fill
+///
+/// ```
+/// use yahtzee::sections::{ create_scorecard, odd_even_sections, Scorecard };
+///
+/// let scorecard: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+/// assert_eq!(scorecard.len(), 15);
+/// assert_eq!(scorecard[14].get_name(), "15. Odds");
+/// ```
+pub type Scorecard = Vec<Box<dyn PointSection>>;
+
+// Create a scorecard with only the enabled sections of the standard scorecard, by their numbers
+// (ex: 1 to 6 for only the upper sections), kept in scorecard order
+pub fn build_scorecard(enabled: &[u8]) -> Scorecard {
+    return create_scorecard()
+        .into_iter()
+        .enumerate()
+        .filter(|(section_i, _)| enabled.contains(&((*section_i as u8) + 1)))
+        .map(|(_, section)| section)
+        .collect();
+}
+
+/// Create all the PointSections for the standard scorecard
+///
+/// ```
+/// use yahtzee::sections::create_scorecard;
+///
+/// let scorecard = create_scorecard();
+/// assert_eq!(scorecard.len(), 13);
+/// assert!(scorecard.iter().all(|section| !section.is_filled()));
+/// ```
+pub fn create_scorecard() -> Scorecard {
+    return vec![
+        // Points for a specific value
+        Box::new(Section1 {
+            score: Score::new("1. Aces"),
+            value: 1,
+        }),
+        Box::new(Section1 {
+            score: Score::new("2. Twos"),
+            value: 2,
+        }),
+        Box::new(Section1 {
+            score: Score::new("3. Threes"),
+            value: 3,
+        }),
+        Box::new(Section1 {
+            score: Score::new("4. Fours"),
+            value: 4,
+        }),
+        Box::new(Section1 {
+            score: Score::new("5. Fives"),
+            value: 5,
+        }),
+        Box::new(Section1 {
+            score: Score::new("6. Sixes"),
+            value: 6,
+        }),
+
+        // 3, 4, or 5 of a kind
+        Box::new(Section2 {
+            score: Score::new("7. 3 of a Kind"),
+            value: 3,
+        }),
+        Box::new(Section2 {
+            score: Score::new("8. 4 of a Kind"),
+            value: 4,
+        }),
+        Box::new(Section2 {
+            score: Score::new("9. YAHTZEE"),
+            value: 5,
+        }),
+
+        // Straights of 3, 4, or 5 (all different)
+        Box::new(Section3 {
+            score: Score::new("10. Small Straight"),
+            value: 3,
+            scoring: StraightScoring::Fixed,
+        }),
+        Box::new(Section3 {
+            score: Score::new("11. Large Straight"),
+            value: 4,
+            scoring: StraightScoring::Fixed,
+        }),
+        Box::new(Section3 {
+            score: Score::new("12. Full House"),
+            value: 5,
+            scoring: StraightScoring::Fixed,
+        }),
+
+        // Chance (counts up all, as a points for '0 of a kind' Section)
+        Box::new(Section2 {
+            score: Score::new("13. Chance"),
+            value: 0,
+        })
+    ];
+}
+
+// Create the sections added by the odd/even variant, which follow the standard scorecard
+pub fn odd_even_sections() -> Scorecard {
+    return vec![
+        // Sum of the even dice, and of the odd dice
+        Box::new(Section4 {
+            score: Score::new("14. Evens"),
+            value: 0,
+        }),
+        Box::new(Section4 {
+            score: Score::new("15. Odds"),
+            value: 1,
+        })
+    ];
+}
+
+// Checks if there is an empty section in the Scorecard
+pub fn empty_section(scorecard: &Scorecard) -> bool {
+    // For every score section in the scorecard,
+    for score in scorecard {
+        // Check if there is an empty section
+        if !score.is_filled() {
+            return true; // Not done with game
+        }
+    }
+
+    // If none are empty, return false, the game is over
+    return false;
+}
+
+// Work the totals out from scratch, for a scorecard filled some other way (ex: loaded from a save),
+// with each Yahtzee bonus worth the given points. The upper sections are the ones that count a single number
+pub fn summarize(scorecard: &Scorecard, yahtzee_bonuses: u32, yahtzee_bonus: i32, upper_bonus: UpperBonus) -> ScoreSummary {
+    let mut summary = ScoreSummary::default();
+    for section in scorecard {
+        summary.add(section.face_value().is_some(), section.get_points(), upper_bonus);
+    }
+    for _ in 0..yahtzee_bonuses {
+        summary.add_yahtzee_bonus(yahtzee_bonus);
+    }
+    return summary;
+}
+
+// Check running totals against ones worked out again from the scorecard, to catch the two drifting apart
+pub fn audit(scorecard: &Scorecard, summary: &ScoreSummary, yahtzee_bonus: i32, upper_bonus: UpperBonus) -> bool {
+    for section in scorecard {
+        // An open section can't have points yet
+        if !section.is_filled() & (section.get_points() != 0) {
+            return false;
+        }
+    }
+    return *summary == summarize(scorecard, summary.yahtzee_bonuses, yahtzee_bonus, upper_bonus);
+}
+
+// Numbers written as words, for finding sections named with digits (ex: "three" for 3 of a Kind)
+static NUMBER_WORDS: [(&str, &str); 6] = [
+    ("one", "1"),
+    ("two", "2"),
+    ("three", "3"),
+    ("four", "4"),
+    ("five", "5"),
+    ("six", "6"),
+];
+
+// A section's name without its number, ex: "3 of a Kind" for "7. 3 of a Kind"
+pub fn short_name(name: &str) -> &str {
+    return name.split_once(". ").map_or(name, |(_, name)| name);
+}
+
+// The index of the section with this number in its name (ex: 7 for "7. 3 of a Kind"), which is its
+// place on the standard scorecard even when sections before it are left off
+pub fn numbered_section(scorecard: &Scorecard, number: usize) -> Option<usize> {
+    let prefix = format!("{}. ", number);
+    return scorecard
+        .iter()
+        .position(|section| section.get_name().starts_with(&prefix));
+}
+
+// The indices of the sections whose names contain the text, ignoring case and the section number.
+// A name that matches exactly is the only match, and numbers written as words are tried as digits first
+pub fn find_sections(scorecard: &Scorecard, text: &str) -> Vec<usize> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    // Names without their number (ex: "3 of a kind" for "7. 3 of a Kind")
+    let names: Vec<String> = scorecard
+        .iter()
+        .map(|section| short_name(section.get_name()).to_lowercase())
+        .collect();
+    if let Some(section_i) = names.iter().position(|name| *name == text) {
+        return vec![section_i];
+    }
+
+    let mut digits = text.clone();
+    for (word, digit) in NUMBER_WORDS {
+        digits = digits.replace(word, digit);
+    }
+    for query in [digits, text] {
+        let matches: Vec<usize> = (0..names.len()).filter(|i| names[*i].contains(&query)).collect();
+        if !matches.is_empty() {
+            return matches;
+        }
+    }
+    return Vec::new();
+}
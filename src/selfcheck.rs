@@ -0,0 +1,175 @@
+// A quick check that this build scores games correctly, for `yahtzee selfcheck`: every hand in every
+// section against a plain reference, the bonuses at their thresholds, and a seeded game played through
+// the headless engine. Meant for checking a build on a new platform, without the tests or a compiler
+use crate::die::Die;
+use crate::engine::{ run_engine, Response };
+use crate::expected::tables;
+use crate::game::{ Game, Move, NUM_DICE };
+use crate::sections::{
+    audit,
+    create_scorecard,
+    odd_even_sections,
+    sum_straight,
+    ScoreSummary,
+    Scorecard,
+    SectionKind,
+    UpperBonus,
+    YAHTZEE_BONUS,
+};
+
+// The seed of the mini-game, and the total it has to finish with on every platform
+static MINI_GAME_SEED: u64 = 2024;
+static MINI_GAME_TOTAL: i32 = 59;
+
+// The outcome of one check, with what went wrong if it failed
+pub struct CheckResult {
+    pub name: &'static str,
+    pub failures: Vec<String>,
+}
+
+impl CheckResult {
+    pub fn passed(&self) -> bool {
+        return self.failures.is_empty();
+    }
+}
+
+// The points a hand (sorted or not) should score in a section, worked out directly from the rules
+// without the scoring crate
+fn reference_score(kind: SectionKind, value: u8, hand: &[u8]) -> i32 {
+    let total: i32 = hand.iter().map(|num| i32::from(*num)).sum();
+    let count = |num: u8| hand.iter().filter(|n| **n == num).count();
+    let has_run = (1..=6u8).any(|start| (start..start + value).all(|num| (num <= 6) & (count(num) > 0)));
+
+    return match kind {
+        SectionKind::Face => i32::from(value) * (count(value) as i32),
+        SectionKind::OfAKind if usize::from(value) == NUM_DICE => {
+            if (1..=6).any(|num| count(num) == NUM_DICE) { 50 } else { 0 }
+        }
+        SectionKind::OfAKind => {
+            if (1..=6).any(|num| count(num) >= usize::from(value)) { total } else { 0 }
+        }
+        SectionKind::Chance => total,
+        SectionKind::Straight => if has_run { i32::from(value) * 10 } else { 0 },
+        SectionKind::SumStraight => if has_run { total } else { 0 },
+        SectionKind::Parity => hand
+            .iter()
+            .filter(|num| *num % 2 == value)
+            .map(|num| i32::from(*num))
+            .sum(),
+    };
+}
+
+// Every different hand (from the expected value tables, so each is checked once rather than in all its
+// orders) scores the reference's points in every section that can be on a scorecard
+fn check_every_hand() -> CheckResult {
+    let tables = tables();
+    let mut failures = Vec::new();
+
+    let mut sections: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+    let sums: Scorecard = sections
+        .iter()
+        .filter_map(|section| sum_straight(section.as_ref()))
+        .collect();
+    sections.extend(sums);
+
+    for section in &sections {
+        // The tables' scores for the sections they have, worked out the same way for the others
+        let scores = match (section.kind().0, tables.section_index(section.get_name())) {
+            (SectionKind::SumStraight, _) | (_, None) => tables.hand_scores(section.as_ref()),
+            (_, Some(section_i)) => tables.scores[section_i].clone(),
+        };
+
+        let (kind, value) = section.kind();
+        for (hand_i, points) in scores.iter().enumerate() {
+            let hand = tables.hand(hand_i);
+            let expected = reference_score(kind, value, hand);
+            if *points != expected {
+                failures.push(format!("{} scored {} for {:?}, not {}", section.get_name(), points, hand, expected));
+            }
+        }
+    }
+    return CheckResult { name: "every hand in every section", failures };
+}
+
+// The upper bonus is earned exactly at its threshold (the usual one and a raised one), and a second
+// Yahtzee earns the Yahtzee bonus
+fn check_bonuses() -> CheckResult {
+    let mut failures = Vec::new();
+
+    for bonus in [UpperBonus::default(), UpperBonus { threshold: 70, points: 50 }] {
+        let mut summary = ScoreSummary::default();
+        summary.add(true, bonus.threshold - 1, bonus);
+        if summary.upper_bonus != 0 {
+            failures.push(format!("{} in the upper sections earned the bonus, it needs {}", bonus.threshold - 1, bonus.threshold));
+        }
+        summary.add(true, 1, bonus);
+        if (summary.upper_bonus != bonus.points) | (summary.total != bonus.threshold + bonus.points) {
+            failures.push(format!("{} in the upper sections earned {} bonus points, not {}", bonus.threshold, summary.upper_bonus, bonus.points));
+        }
+    }
+
+    let mut game = Game::new(1);
+    let yahtzee: Vec<Die> = (0..NUM_DICE).map(|_| Die { num: 4, ..Die::default() }).collect();
+    for section_i in [8, 12] {
+        let scored = game.set_dice(&yahtzee, 2).and_then(|_| game.score(section_i));
+        if let Err(e) = scored {
+            failures.push(format!("Couldn't score a Yahtzee: {}", e));
+        }
+    }
+    if game.totals().yahtzee_bonus != YAHTZEE_BONUS {
+        failures.push(format!("A second Yahtzee earned a {} point bonus, not {}", game.totals().yahtzee_bonus, YAHTZEE_BONUS));
+    }
+    return CheckResult { name: "upper and Yahtzee bonuses", failures };
+}
+
+// A seeded game played through the engine's JSON commands (rolling once and scoring each section in
+// order) finishes with the same total as the game played directly, and as on every other platform
+fn check_mini_game() -> CheckResult {
+    let mut failures = Vec::new();
+
+    let mut commands = format!("{{\"cmd\":\"seed\",\"seed\":{}}}\n", MINI_GAME_SEED);
+    let mut game = Game::new(MINI_GAME_SEED);
+    for section_i in 0..game.scorecard.len() {
+        commands += &format!("{{\"cmd\":\"roll\"}}\n{{\"cmd\":\"score\",\"section\":{}}}\n", section_i);
+        for player_move in [Move::Roll, Move::Score(section_i)] {
+            if let Err(e) = game.play(player_move) {
+                failures.push(format!("The game couldn't be played: {}", e));
+            }
+        }
+    }
+
+    let mut output = Vec::new();
+    if let Err(e) = run_engine(Game::new(MINI_GAME_SEED), commands.as_bytes(), &mut output) {
+        failures.push(format!("The engine stopped: {}", e));
+    }
+    let responses: Vec<Response> = String::from_utf8_lossy(&output)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    for response in &responses {
+        if let Some(error) = &response.error {
+            failures.push(format!("The engine refused command {}: {}", response.seq, error));
+        }
+    }
+
+    match responses.last() {
+        Some(response) if response.state.game_over => {
+            if response.state.total != game.total() {
+                failures.push(format!("The engine's total {} isn't the game's {}", response.state.total, game.total()));
+            }
+        }
+        _ => failures.push("The engine's game didn't finish".to_string()),
+    }
+    if !audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus) {
+        failures.push("The game's total doesn't add up from its scorecard".to_string());
+    }
+    if game.total() != MINI_GAME_TOTAL {
+        failures.push(format!("Seed {} finished with {}, not {} (the dice don't roll as on other platforms)", MINI_GAME_SEED, game.total(), MINI_GAME_TOTAL));
+    }
+    return CheckResult { name: "a seeded game through the engine", failures };
+}
+
+// Run every check
+pub fn run_checks() -> Vec<CheckResult> {
+    return vec![check_every_hand(), check_bonuses(), check_mini_game()];
+}
@@ -0,0 +1,32 @@
+// A short summary of a finished game, for sharing with friends
+use crate::game::Game;
+
+// How a section scored compared to the most it could have
+fn section_symbol(points: i32, max_points: i32) -> char {
+    if points == 0 {
+        return '⬛'; // Scratched
+    } else if points * 2 >= max_points {
+        return '🟩'; // At least half of the most possible
+    } else {
+        return '🟨'; // Something, but less than half
+    }
+}
+
+// A row of symbols for the upper and lower sections, with the total and seed
+pub fn share_card(game: &Game) -> String {
+    let mut upper = String::new();
+    let mut lower = String::new();
+
+    for section in &game.scorecard {
+        let symbol = section_symbol(section.get_points(), section.max_possible_score());
+
+        // Upper sections count a value, the rest go in the lower row
+        if section.face_value().is_some() {
+            upper.push(symbol);
+        } else {
+            lower.push(symbol);
+        }
+    }
+
+    return format!("Yahtzee {} (seed {})\n{}\n{}", game.total(), game.seed, upper, lower);
+}
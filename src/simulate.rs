@@ -0,0 +1,107 @@
+// Playing many games with a bot, to see how its scores are spread
+use serde::Serialize;
+
+use crate::bot::{ self, LocalTable, Table };
+use crate::engine::EngineState;
+use crate::game::Game;
+use crate::stats::reached_upper_bonus;
+
+// How the scores of the simulated games were spread
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Simulation {
+    pub strategy: String, // The bot that played the games
+    pub seed: u64, // The first game's seed, each game after uses the next
+    pub games: u32, // The number of games played
+    pub mean: f64,
+    pub median: f64,
+    pub min: i32,
+    pub max: i32,
+    pub std_dev: f64, // Standard deviation of the scores
+    pub yahtzees_per_game: f64, // Yahtzees rolled per game
+    pub upper_bonus_rate: f64, // The fraction of games that reached the upper bonus
+    #[serde(skip)]
+    pub scores: Vec<i32>, // Every game's score, lowest first
+}
+
+impl Simulation {
+    // A formatted report of the simulation
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "Simulated {} games with the {} bot (seeds {} to {})\n",
+            self.games,
+            self.strategy,
+            self.seed,
+            self.seed.wrapping_add(u64::from(self.games) - 1)
+        );
+        report += &format!("Mean score: {:.1}\n", self.mean);
+        report += &format!("Median score: {:.1}\n", self.median);
+        report += &format!("Lowest score: {}\n", self.min);
+        report += &format!("Highest score: {}\n", self.max);
+        report += &format!("Standard deviation: {:.1}\n", self.std_dev);
+        report += &format!("Yahtzees per game: {:.3}\n", self.yahtzees_per_game);
+        report += &format!("Upper bonus reached: {:.1}% of games", self.upper_bonus_rate * 100.0);
+        return report;
+    }
+}
+
+// Let the bot play the given number of games, each with the next seed, returning how each ended
+pub fn play_games(strategy: &str, games: u32, seed: u64) -> Result<Vec<EngineState>, String> {
+    let mut states = Vec::new();
+    for game_i in 0..games {
+        let game_seed = seed.wrapping_add(u64::from(game_i));
+        let mut player = bot
+            ::bot_by_name(strategy, game_seed)
+            .ok_or(format!("Unknown strategy '{}'", strategy))?;
+        let mut table = LocalTable { game: Game::new(game_seed) };
+        bot::play_solitaire(player.as_mut(), &mut table)?;
+        states.push(table.state()?);
+    }
+    return Ok(states);
+}
+
+// Let the bot play the given number of games, starting from the seed
+pub fn simulate(strategy: &str, games: u32, seed: u64) -> Result<Simulation, String> {
+    if games == 0 {
+        return Err("At least one game must be simulated".to_string());
+    }
+
+    let mut scores = Vec::new();
+    let mut yahtzees = 0;
+    let mut upper_bonuses = 0;
+    for state in play_games(strategy, games, seed)? {
+        scores.push(state.total);
+        yahtzees += state.yahtzees;
+        if reached_upper_bonus(&state) {
+            upper_bonuses += 1;
+        }
+    }
+    scores.sort();
+
+    // The median is the middle score, or halfway between the middle two
+    let count = scores.len();
+    let median = if count % 2 == 1 {
+        f64::from(scores[count / 2])
+    } else {
+        f64::from(scores[count / 2 - 1] + scores[count / 2]) / 2.0
+    };
+    let mean = scores.iter().map(|score| f64::from(*score)).sum::<f64>() / (count as f64);
+    let variance =
+        scores
+            .iter()
+            .map(|score| (f64::from(*score) - mean).powi(2))
+            .sum::<f64>() / (count as f64);
+
+    return Ok(Simulation {
+        strategy: strategy.to_string(),
+        seed,
+        games,
+        mean,
+        median,
+        min: scores[0],
+        max: scores[count - 1],
+        std_dev: variance.sqrt(),
+        yahtzees_per_game: f64::from(yahtzees) / f64::from(games),
+        upper_bonus_rate: f64::from(upper_bonuses) / f64::from(games),
+        scores,
+    });
+}
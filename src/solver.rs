@@ -0,0 +1,297 @@
+// The best possible play for a game of one player, worked out for every scorecard it can reach
+use serde::{ Deserialize, Serialize };
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::ai::{ choose_freezes, choose_section };
+use crate::bot::{ GameView, HoldMask, Strategy };
+use crate::expected::{ tables, Tables };
+use crate::game::NUM_DICE;
+use crate::sections::{ create_scorecard, SectionKind, UpperBonus, YAHTZEE_BONUS };
+
+// The version of the file format written by this version of the game (version 1 scored a YAHTZEE
+// as the dice total, and versions 2 and 3 didn't count the upper and Yahtzee bonuses, so their
+// solutions are worked out again)
+static VERSION: u32 = 4;
+
+// The name of a solution's file, in the data directory it's kept in
+pub static SOLUTION_FILE: &str = "solution.json";
+
+// The points still to come with perfect play, for every set of open sections on the standard scorecard,
+// every upper subtotal on the way to the upper bonus, and whether Yahtzees earn a bonus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Solution {
+    pub version: u32, // The file format's version
+    pub sections: Vec<String>, // The names of the sections it was worked out for, in order
+    pub upper_bonus: UpperBonus, // The upper bonus it was worked out for
+    pub yahtzee_bonus: i32, // The points for each Yahtzee bonus it was worked out for
+    pub values: Vec<f64>, // By state (see Solution::state), at the start of a turn
+}
+
+// What the solver needs to know about the standard scorecard's sections
+struct Layout {
+    faces: Vec<Option<u8>>, // The number each upper section counts
+    yahtzee_i: usize, // The YAHTZEE section, which earns later Yahtzees a bonus once it's scored with points
+}
+impl Layout {
+    fn standard() -> Layout {
+        let scorecard = create_scorecard();
+        return Layout {
+            faces: scorecard
+                .iter()
+                .map(|section| section.face_value())
+                .collect(),
+            yahtzee_i: scorecard
+                .iter()
+                .position(|section| section.kind() == (SectionKind::OfAKind, NUM_DICE as u8))
+                .expect("the standard scorecard has a YAHTZEE section"),
+        };
+    }
+}
+
+impl Solution {
+    // Work out every set of open sections, from the fewest open to the most, for every upper subtotal
+    // that can be reached with the rest closed
+    pub fn solve() -> Solution {
+        let scorecard = create_scorecard();
+        let sections: Vec<String> = scorecard
+            .iter()
+            .map(|section| section.get_name().to_string())
+            .collect();
+        let layout = Layout::standard();
+        let tables = tables();
+
+        // Closing a section always gives a smaller set, so it's already worked out
+        let mut solution = Solution {
+            version: VERSION,
+            sections,
+            upper_bonus: UpperBonus::default(),
+            yahtzee_bonus: YAHTZEE_BONUS,
+            values: Vec::new(),
+        };
+        solution.values = vec![0.0; solution.state_count()];
+        for open in 1..1 << layout.faces.len() {
+            // Yahtzees only earn a bonus once the YAHTZEE section is closed
+            let bonuses: &[bool] = if (open & (1 << layout.yahtzee_i)) != 0 { &[false] } else { &[false, true] };
+            for upper in reachable_uppers(&layout.faces, open, solution.upper_cap()) {
+                for bonus in bonuses {
+                    let state = State { open, upper, bonus: *bonus };
+                    let [_, two_rolls] = tables.turn_values(&final_values(tables, &solution, &layout, state));
+                    let state_i = solution.state(open, upper, *bonus);
+                    solution.values[state_i] = tables.start_value(&two_rolls);
+                }
+            }
+        }
+        return solution;
+    }
+
+    // The upper subtotal stops being counted once it reaches the bonus, anything above is the same
+    fn upper_cap(&self) -> i32 {
+        return self.upper_bonus.threshold;
+    }
+
+    // The number of states the solution has a value for
+    fn state_count(&self) -> usize {
+        return (1 << self.sections.len()) * 2 * (self.upper_cap() as usize + 1);
+    }
+
+    // The index of a state in the values: the set of open sections (section i is open when bit i is
+    // set), whether Yahtzees earn a bonus, and the upper subtotal so far
+    pub fn state(&self, open: usize, upper: i32, bonus: bool) -> usize {
+        let upper = upper.clamp(0, self.upper_cap()) as usize;
+        return (open * 2 + usize::from(bonus)) * (self.upper_cap() as usize + 1) + upper;
+    }
+
+    // The expected final score of a new game with perfect play
+    pub fn expected_score(&self) -> f64 {
+        return self.values[self.state((1 << self.sections.len()) - 1, 0, false)];
+    }
+
+    // Read a saved solution, None if it's missing or was worked out for other sections or rules
+    pub fn read(path: &Path) -> Option<Solution> {
+        let json = std::fs::read_to_string(path).ok()?;
+        let solution: Solution = serde_json::from_str(&json).ok()?;
+        let sections: Vec<&str> = create_scorecard()
+            .iter()
+            .map(|section| section.get_name())
+            .collect();
+
+        let current =
+            (solution.version == VERSION) &
+            (solution.sections == sections) &
+            (solution.upper_bonus == UpperBonus::default()) &
+            (solution.yahtzee_bonus == YAHTZEE_BONUS);
+        return if current & (solution.values.len() == solution.state_count()) { Some(solution) } else { None };
+    }
+
+    // Write the solution, replacing the file
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        return std::fs::write(path, json).map_err(|e| e.to_string());
+    }
+}
+
+// Every upper subtotal (up to the cap) the closed upper sections can add up to
+fn reachable_uppers(faces: &[Option<u8>], open: usize, cap: i32) -> Vec<i32> {
+    let mut reachable = vec![false; cap as usize + 1];
+    reachable[0] = true;
+    for (section_i, face) in faces.iter().enumerate() {
+        let face = match face {
+            Some(face) if (open & (1 << section_i)) == 0 => i32::from(*face),
+            _ => {
+                continue;
+            }
+        };
+
+        // A closed upper section scored its number from none to every die
+        let mut added = vec![false; reachable.len()];
+        for upper in (0..=cap).filter(|upper| reachable[*upper as usize]) {
+            for count in 0..=NUM_DICE as i32 {
+                added[(upper + face * count).min(cap) as usize] = true;
+            }
+        }
+        reachable = added;
+    }
+    return (0..=cap).filter(|upper| reachable[*upper as usize]).collect();
+}
+
+// Where a game is at the start of a turn
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct State {
+    open: usize, // The open sections, section i is open when bit i is set
+    upper: i32, // The upper subtotal so far
+    bonus: bool, // If Yahtzees earn a bonus (the YAHTZEE section was scored with points)
+}
+
+// What scoring the points in a section is worth: the points, the bonuses they earn, and what's still
+// to come with the section closed
+fn score_value(solution: &Solution, layout: &Layout, state: State, section_i: usize, points: i32, yahtzee: bool) -> f64 {
+    let mut value = f64::from(points);
+    let mut next_upper = state.upper;
+    if layout.faces[section_i].is_some() {
+        next_upper = (state.upper + points).min(solution.upper_cap());
+        if (state.upper < solution.upper_cap()) & (next_upper == solution.upper_cap()) {
+            value += f64::from(solution.upper_bonus.points);
+        }
+    }
+    if yahtzee & state.bonus {
+        value += f64::from(solution.yahtzee_bonus);
+    }
+    let next_bonus = state.bonus | ((section_i == layout.yahtzee_i) & (points > 0));
+    return value + solution.values[solution.state(state.open & !(1 << section_i), next_upper, next_bonus)];
+}
+
+// What every hand is worth when the rolling is done: the value of the best open section to score it in
+fn final_values(tables: &Tables, solution: &Solution, layout: &Layout, state: State) -> Vec<f64> {
+    return (0..tables.hand_count())
+        .map(|hand_i| {
+            let hand = tables.hand(hand_i);
+            let yahtzee = hand.iter().all(|num| *num == hand[0]);
+            (0..layout.faces.len())
+                .filter(|section_i| (state.open & (1 << section_i)) != 0)
+                .map(|section_i| {
+                    score_value(solution, layout, state, section_i, tables.scores[section_i][hand_i], yahtzee)
+                })
+                .fold(f64::MIN, f64::max)
+        })
+        .collect();
+}
+
+static SOLUTION: OnceLock<Solution> = OnceLock::new();
+
+// The solution, worked out the first time it's needed unless one was given already
+pub fn solution() -> &'static Solution {
+    return SOLUTION.get_or_init(Solution::solve);
+}
+
+// Use this solution (ex: one read from a file) instead of working it out, if it isn't already known
+pub fn use_solution(solution: Solution) {
+    let _ = SOLUTION.set(solution);
+}
+
+// Plays perfectly (for the most points on average) using the solution
+pub struct OptimalStrategy {
+    solution: &'static Solution,
+    layout: Layout,
+    turn: Option<(State, [Vec<f64>; 2])>, // What each set kept is worth, worked out once for each state
+}
+impl Default for OptimalStrategy {
+    fn default() -> OptimalStrategy {
+        return OptimalStrategy {
+            solution: solution(),
+            layout: Layout::standard(),
+            turn: None,
+        };
+    }
+}
+impl OptimalStrategy {
+    // Where the game is, None if the scorecard isn't the one the solution is for
+    fn state(&self, view: &GameView) -> Option<State> {
+        let names = view.scorecard.iter().map(|section| &section.name);
+        if !names.eq(&self.solution.sections) {
+            return None;
+        }
+        let open = view.scorecard
+            .iter()
+            .enumerate()
+            .filter(|(_, section)| !section.filled)
+            .fold(0, |open, (section_i, _)| open | (1 << section_i));
+        let upper = view.scorecard
+            .iter()
+            .zip(&self.layout.faces)
+            .filter(|(_, face)| face.is_some())
+            .map(|(section, _)| section.points)
+            .sum();
+        let bonus = view.scorecard[self.layout.yahtzee_i].points > 0;
+        return Some(State { open, upper, bonus });
+    }
+}
+impl Strategy for OptimalStrategy {
+    fn choose_hold(&mut self, view: &GameView) -> HoldMask {
+        // Other scorecards fall back to keeping the most common number
+        let state = match self.state(view) {
+            Some(state) => state,
+            None => {
+                return choose_freezes(&view.dice);
+            }
+        };
+
+        let tables = tables();
+        let worked_out = match &self.turn {
+            Some((turn_state, _)) => *turn_state == state,
+            None => false,
+        };
+        if !worked_out {
+            let finals = final_values(tables, self.solution, &self.layout, state);
+            self.turn = Some((state, tables.turn_values(&finals)));
+        }
+
+        let (_, kept) = self.turn.as_ref().expect("the turn's values were just worked out");
+        let rolls_left = usize::from(view.rolls_left.clamp(1, 2));
+        return tables.best_hold(&view.dice, &kept[rolls_left - 1]);
+    }
+    fn choose_section(&mut self, view: &GameView) -> usize {
+        // Other scorecards fall back to the most points
+        let state = match self.state(view) {
+            Some(state) => state,
+            None => {
+                return choose_section(&view.scorecard);
+            }
+        };
+
+        // The section worth the most points now and still to come
+        let yahtzee = view.dice.iter().all(|die| die.num == view.dice[0].num);
+        let mut best_i = None;
+        let mut best_value = f64::MIN;
+        for (section_i, section) in view.scorecard.iter().enumerate() {
+            if let Some(points) = section.potential {
+                let value = score_value(self.solution, &self.layout, state, section_i, points, yahtzee);
+                if best_i.is_none() | (value > best_value) {
+                    best_i = Some(section_i);
+                    best_value = value;
+                }
+            }
+        }
+        return best_i.expect("no open section to pick");
+    }
+}
@@ -0,0 +1,105 @@
+// Lifetime statistics, added to at the end of every finished game
+use serde::{ Deserialize, Serialize };
+use std::path::Path;
+
+use crate::engine::EngineState;
+
+// Where the lifetime statistics are kept, unless another file is given
+pub static LIFETIME_FILE: &str = "lifetime.json";
+
+// If the game's upper sections added up to enough for the upper bonus
+pub fn reached_upper_bonus(state: &EngineState) -> bool {
+    return state.totals.upper_bonus > 0;
+}
+
+// The points scored in a section across every game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionTotal {
+    pub name: String,
+    pub points: i64,
+}
+
+// Everything counted across every finished game
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    pub games: u32, // Games finished
+    pub total_points: i64, // Every game's score added together
+    pub best: i32, // The best game's score
+    pub yahtzees: u32, // Yahtzees rolled
+    pub upper_bonuses: u32, // Games where the upper sections reached the bonus
+    pub sections: Vec<SectionTotal>, // Points scored in each section, in scorecard order
+}
+
+impl LifetimeStats {
+    // Count a finished game
+    pub fn add_game(&mut self, state: &EngineState) {
+        self.games += 1;
+        self.total_points += i64::from(state.total);
+        self.best = if self.games == 1 { state.total } else { self.best.max(state.total) };
+        self.yahtzees += state.yahtzees;
+
+        if reached_upper_bonus(state) {
+            self.upper_bonuses += 1;
+        }
+
+        // Sections are matched by name, new ones are added to the end
+        for section in &state.scorecard {
+            match self.sections.iter_mut().find(|total| total.name == section.name) {
+                Some(total) => {
+                    total.points += i64::from(section.points);
+                }
+                None => {
+                    self.sections.push(SectionTotal {
+                        name: section.name.clone(),
+                        points: i64::from(section.points),
+                    });
+                }
+            }
+        }
+    }
+
+    // A formatted report of the statistics
+    pub fn report(&self) -> String {
+        if self.games == 0 {
+            return "No games have been finished yet.".to_string();
+        }
+        let games = f64::from(self.games);
+
+        let mut report = format!("Games played: {}\n", self.games);
+        report += &format!("Average score: {:.1}\n", (self.total_points as f64) / games);
+        report += &format!("Best score: {}\n", self.best);
+        report += &format!("Yahtzees rolled: {}\n", self.yahtzees);
+        report += &format!(
+            "Upper bonus reached: {} ({:.0}% of games)\n",
+            self.upper_bonuses,
+            (f64::from(self.upper_bonuses) * 100.0) / games
+        );
+
+        report += "\nAverage score per section:";
+        for section in &self.sections {
+            report += &format!("\n{: <20} {:.1}", section.name, (section.points as f64) / games);
+        }
+        return report;
+    }
+}
+
+// Read the lifetime statistics, starting from nothing if there's no file yet
+pub fn read_stats(path: &Path) -> Result<LifetimeStats, String> {
+    return match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Not a statistics file: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LifetimeStats::default()),
+        Err(e) => Err(e.to_string()),
+    };
+}
+
+// Add a finished game to the lifetime statistics in the file
+pub fn record_game(path: &Path, state: &EngineState) -> Result<(), String> {
+    let mut stats = read_stats(path)?;
+    stats.add_game(state);
+    let json = serde_json::to_string_pretty(&stats).map_err(|e| e.to_string())?;
+
+    // Write to a temporary file first, so a crash never loses the statistics
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json).map_err(|e| e.to_string())?;
+    return std::fs::rename(&temp_path, path).map_err(|e| e.to_string());
+}
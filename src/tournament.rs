@@ -0,0 +1,214 @@
+// Every strategy against every other, on the same dice, to see which plays best
+use crate::export::csv_line;
+use crate::simulate::play_games;
+
+// How many standard deviations either side of a result its 95% confidence interval reaches
+static CONFIDENCE_Z: f64 = 1.96;
+
+// How one strategy did in the tournament
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub strategy: String,
+    pub mean: f64, // Mean score of its games
+    pub mean_margin: f64, // The mean's 95% confidence interval reaches this far either side
+    pub win_rate: f64, // The fraction of games won against every other strategy (ties count half)
+    pub win_margin: f64, // The win rate's 95% confidence interval reaches this far either side
+    pub head_to_head: Vec<f64>, // Win rate against each strategy, in the order they were given
+}
+
+// The results of every pairing, best strategy first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tournament {
+    pub strategies: Vec<String>, // Every strategy, in the order they were given
+    pub games: u32, // Games played by each strategy
+    pub seed: u64, // The first game's seed, each game after uses the next
+    pub standings: Vec<Standing>, // Highest mean score first
+}
+
+// The fraction of games the first scores win against the second (a tie is half a win)
+fn win_rate(scores: &[i32], opponent_scores: &[i32]) -> f64 {
+    let points: f64 = scores
+        .iter()
+        .zip(opponent_scores)
+        .map(|(score, opponent)| {
+            if score > opponent {
+                1.0
+            } else if score == opponent {
+                0.5
+            } else {
+                0.0
+            }
+        })
+        .sum();
+    return points / (scores.len() as f64);
+}
+
+// The 95% confidence interval's reach either side of a rate over this many games
+fn rate_margin(rate: f64, games: usize) -> f64 {
+    return CONFIDENCE_Z * (rate * (1.0 - rate) / (games as f64)).sqrt();
+}
+
+// Let every strategy play the same seeded games. Pairings compare games with the same seed
+// ("duplicate" dice), which the strategies play alone since none of them look at their opponent
+pub fn run(strategies: &[String], games: u32, seed: u64) -> Result<Tournament, String> {
+    if strategies.len() < 2 {
+        return Err("A tournament needs at least two strategies".to_string());
+    }
+    if games == 0 {
+        return Err("At least one game must be played".to_string());
+    }
+
+    let mut scores = Vec::new();
+    for strategy in strategies {
+        let states = play_games(strategy, games, seed)?;
+        scores.push(states.iter().map(|state| state.total).collect::<Vec<i32>>());
+    }
+
+    let count = games as usize;
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .enumerate()
+        .map(|(strategy_i, strategy)| {
+            let mean = scores[strategy_i].iter().map(|score| f64::from(*score)).sum::<f64>() / (count as f64);
+            let variance =
+                scores[strategy_i]
+                    .iter()
+                    .map(|score| (f64::from(*score) - mean).powi(2))
+                    .sum::<f64>() / (count as f64);
+
+            // A strategy against itself always ties, so it isn't counted in its overall win rate
+            let head_to_head: Vec<f64> = scores
+                .iter()
+                .map(|opponent_scores| win_rate(&scores[strategy_i], opponent_scores))
+                .collect();
+            let others = strategies.len() - 1;
+            let win_rate =
+                head_to_head
+                    .iter()
+                    .enumerate()
+                    .filter(|(opponent_i, _)| *opponent_i != strategy_i)
+                    .map(|(_, rate)| rate)
+                    .sum::<f64>() / (others as f64);
+
+            Standing {
+                strategy: strategy.clone(),
+                mean,
+                mean_margin: (CONFIDENCE_Z * variance.sqrt()) / (count as f64).sqrt(),
+                win_rate,
+                win_margin: rate_margin(win_rate, count * others),
+                head_to_head,
+            }
+        })
+        .collect();
+    standings.sort_by(|a, b| b.mean.total_cmp(&a.mean));
+
+    return Ok(Tournament {
+        strategies: strategies.to_vec(),
+        games,
+        seed,
+        standings,
+    });
+}
+
+impl Tournament {
+    // A formatted report: the ranked table, then every head to head win rate
+    pub fn report(&self) -> String {
+        let name_width = self.strategies
+            .iter()
+            .map(|strategy| strategy.len())
+            .max()
+            .unwrap_or(0)
+            .max("Strategy".len());
+
+        let mut report = format!(
+            "Tournament of {} strategies, {} games each (seeds {} to {})\n\n",
+            self.strategies.len(),
+            self.games,
+            self.seed,
+            self.seed.wrapping_add(u64::from(self.games) - 1)
+        );
+        report += &format!(
+            "{: <6}{: <width$}  {: <16}{}\n",
+            "Rank",
+            "Strategy",
+            "Mean score",
+            "Win rate",
+            width = name_width
+        );
+        for (rank, standing) in self.standings.iter().enumerate() {
+            let mean = format!("{:.1} ± {:.1}", standing.mean, standing.mean_margin);
+            let win_rate = format!("{:.1}% ± {:.1}%", standing.win_rate * 100.0, standing.win_margin * 100.0);
+            report += &format!(
+                "{: <6}{: <width$}  {: <16}{}\n",
+                rank + 1,
+                standing.strategy,
+                mean,
+                win_rate,
+                width = name_width
+            );
+        }
+
+        // Each row's win rate against each column, in ranked order
+        report += "\nHead to head (the row's win rate against the column, ties count half)\n";
+        report += &format!("{: <width$}", "", width = name_width + 2);
+        for standing in &self.standings {
+            report += &format!("{: >width$}", standing.strategy, width = name_width + 2);
+        }
+        for standing in &self.standings {
+            report += &format!("\n{: <width$}", standing.strategy, width = name_width + 2);
+            for opponent in &self.standings {
+                let rate = if opponent.strategy == standing.strategy {
+                    "-".to_string()
+                } else {
+                    let opponent_i = self.index(&opponent.strategy);
+                    format!("{:.1}%", standing.head_to_head[opponent_i] * 100.0)
+                };
+                report += &format!("{: >width$}", rate, width = name_width + 2);
+            }
+        }
+        return report;
+    }
+
+    // The results as CSV, a row for each strategy in ranked order
+    pub fn csv(&self) -> String {
+        let mut header: Vec<String> = [
+            "Rank",
+            "Strategy",
+            "Mean",
+            "Mean 95% Margin",
+            "Win Rate",
+            "Win Rate 95% Margin",
+        ]
+            .iter()
+            .map(|field| field.to_string())
+            .collect();
+        for strategy in &self.strategies {
+            header.push(format!("Win Rate vs {}", strategy));
+        }
+
+        let mut csv = csv_line(&header);
+        for (rank, standing) in self.standings.iter().enumerate() {
+            let mut row = vec![
+                (rank + 1).to_string(),
+                standing.strategy.clone(),
+                format!("{:.3}", standing.mean),
+                format!("{:.3}", standing.mean_margin),
+                format!("{:.4}", standing.win_rate),
+                format!("{:.4}", standing.win_margin)
+            ];
+            for rate in &standing.head_to_head {
+                row.push(format!("{:.4}", rate));
+            }
+            csv += &csv_line(&row);
+        }
+        return csv;
+    }
+
+    // The place of a strategy in the order they were given
+    fn index(&self, strategy: &str) -> usize {
+        return self.strategies
+            .iter()
+            .position(|name| name == strategy)
+            .expect("every standing is for a strategy in the tournament");
+    }
+}
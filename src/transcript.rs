@@ -0,0 +1,110 @@
+// A human-readable Markdown transcript of every game, written as the game is played
+use std::fs::{ File, OpenOptions };
+use std::io::Write;
+use std::path::Path;
+
+use crate::die::Die;
+use crate::game::{ Game, GameEvent };
+use crate::results::{ format_date, timestamp_now };
+
+// Show the dice as they are displayed in the game, held dice marked with < >
+fn format_dice(dice: &[Die]) -> String {
+    let dice: Vec<String> = dice
+        .iter()
+        .map(|die| if die.frozen { format!("<{}>", die.num) } else { format!("[{}]", die.num) })
+        .collect();
+    return format!("`{}`", dice.join(" "));
+}
+
+// Die numbers as the player sees them, counting from 1
+fn format_die_numbers(dice: &Vec<usize>) -> String {
+    if dice.is_empty() {
+        return "none".to_string();
+    }
+    let numbers: Vec<String> = dice.iter().map(|i| (i + 1).to_string()).collect();
+    return numbers.join(", ");
+}
+
+// The transcript file, and how much of the current game has been written to it
+pub struct Transcript {
+    file: File, // Every game is added to the end
+    written: usize, // The number of the game's log events already written
+    turn_started: bool, // If the current turn's heading has been written
+    turn: u32, // The turn being written
+}
+
+impl Transcript {
+    // Open the transcript file, adding to it if it already exists
+    pub fn open(path: &Path) -> std::io::Result<Transcript> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        return Ok(Transcript {
+            file,
+            written: 0,
+            turn_started: false,
+            turn: 1,
+        });
+    }
+
+    // Write the heading for a new game
+    pub fn start_game(&mut self, game: &Game, player: &str) -> std::io::Result<()> {
+        self.written = game.log.len();
+        self.turn_started = false;
+        self.turn = game.turn;
+
+        writeln!(self.file, "# Yahtzee Game\n")?;
+        writeln!(self.file, "- Date: {}", format_date(timestamp_now()))?;
+        writeln!(self.file, "- Player: {}", player)?;
+        writeln!(self.file, "- Seed: {}\n", game.seed)?;
+        return self.file.flush();
+    }
+
+    // Write everything that has happened since the last update, so a crash still leaves a record
+    pub fn update(&mut self, game: &Game) -> std::io::Result<()> {
+        for event in &game.log[self.written..] {
+            if !self.turn_started {
+                writeln!(self.file, "## Turn {}\n", self.turn)?;
+                self.turn_started = true;
+            }
+
+            match event {
+                GameEvent::Rolled(dice) => writeln!(self.file, "- Rolled {}", format_dice(dice))?,
+                GameEvent::Froze(die_i, true) => writeln!(self.file, "- Froze die {}", die_i + 1)?,
+                GameEvent::Froze(die_i, false) => {
+                    writeln!(self.file, "- Unfroze die {}", die_i + 1)?
+                }
+                GameEvent::Held(dice) if dice.is_empty() => {
+                    writeln!(self.file, "- Unfroze every die")?
+                }
+                GameEvent::Held(dice) => {
+                    writeln!(self.file, "- Held dice {}", format_die_numbers(dice))?
+                }
+                GameEvent::Nudged(die_i, up) => {
+                    writeln!(self.file, "- Nudged die {} {}", die_i + 1, if *up { "up" } else { "down" })?
+                }
+                GameEvent::Scored(section_i, points) => {
+                    let name = game.scorecard[*section_i].get_name();
+                    writeln!(self.file, "- Scored **{}** for {} points\n", name, points)?;
+                    self.turn_started = false;
+                    self.turn += 1;
+                }
+            }
+        }
+        self.written = game.log.len();
+        return self.file.flush();
+    }
+
+    // Write the rest of the game and its final scorecard
+    pub fn finish_game(&mut self, game: &Game) -> std::io::Result<()> {
+        self.update(game)?;
+
+        writeln!(self.file, "## Final Scorecard\n")?;
+        writeln!(self.file, "| Section | Points |")?;
+        writeln!(self.file, "| --- | ---: |")?;
+        for section in &game.scorecard {
+            let marker = if section.is_handicap() { "*" } else { "" };
+            writeln!(self.file, "| {} | {}{} |", section.get_name(), section.get_points(), marker)?;
+        }
+        writeln!(self.file, "| **Total** | **{}** |\n", game.total())?;
+        return self.file.flush();
+    }
+}
@@ -0,0 +1,90 @@
+// Bindings for playing a game from JavaScript, when built to WebAssembly with the wasm feature (see
+// examples/web/). There's one game at a time, kept here. Dice and sections are counted from 0, and the
+// state is the same JSON the engine answers with
+use std::cell::RefCell;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::engine::EngineState;
+use crate::game::{ Game, Move, MAX_ROLLS };
+
+thread_local! {
+    // The game being played, once one is started
+    static GAME: RefCell<Option<Game>> = const { RefCell::new(None) };
+}
+
+// A section that can be scored, and what the dice would score in it
+#[derive(Serialize)]
+struct Potential {
+    section: usize,
+    name: &'static str,
+    points: i32,
+}
+
+// Play on the game, failing if one hasn't been started
+fn with_game<T>(play: impl FnOnce(&mut Game) -> Result<T, String>) -> Result<T, JsError> {
+    return GAME.with(|game| {
+        match game.borrow_mut().as_mut() {
+            Some(game) => play(game).map_err(|e| JsError::new(&e)),
+            None => Err(JsError::new("Start a game with new_game first")),
+        }
+    });
+}
+
+// Start a new game, its dice rolled from the seed
+#[wasm_bindgen]
+pub fn new_game(seed: u64) {
+    GAME.with(|game| *game.borrow_mut() = Some(Game::new(seed)));
+}
+
+// Roll the dice that aren't held
+#[wasm_bindgen]
+pub fn roll() -> Result<(), JsError> {
+    return with_game(|game| game.play(Move::Roll).map_err(|e| e.to_string()));
+}
+
+// Hold the die, or let it go if it's held
+#[wasm_bindgen]
+pub fn toggle_hold(die: usize) -> Result<(), JsError> {
+    return with_game(|game| {
+        if die >= game.dice.len() {
+            return Err(format!("There is no die {}", die));
+        }
+
+        // Hold the same dice as before, but with this one changed, so the rules on holding still apply
+        let held: Vec<usize> = (0..game.dice.len())
+            .filter(|die_i| game.dice[*die_i].frozen != (*die_i == die))
+            .collect();
+        game.play(Move::Hold(held)).map_err(|e| e.to_string())
+    });
+}
+
+// The sections the dice can be scored in, with the points each would score, as a JSON array (empty
+// before the turn's first roll)
+#[wasm_bindgen]
+pub fn potential_scores() -> Result<String, JsError> {
+    return with_game(|game| {
+        if game.rolls == MAX_ROLLS {
+            return Ok("[]".to_string());
+        }
+        let potentials: Vec<Potential> = game
+            .preview_scores()
+            .into_iter()
+            .map(|(section, points)| Potential { section, name: game.scorecard[section].get_name(), points })
+            .collect();
+        serde_json::to_string(&potentials).map_err(|e| e.to_string())
+    });
+}
+
+// Score the dice in the section, ending the turn
+#[wasm_bindgen]
+pub fn score(section: usize) -> Result<(), JsError> {
+    return with_game(|game| game.play(Move::Score(section)).map_err(|e| e.to_string()));
+}
+
+// The game's state as JSON: the dice, rolls left, scorecard, and totals
+#[wasm_bindgen]
+pub fn state_json() -> Result<String, JsError> {
+    return with_game(|game| serde_json::to_string(&EngineState::new(game)).map_err(|e| e.to_string()));
+}
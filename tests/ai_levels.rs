@@ -0,0 +1,45 @@
+// The AI levels, which have to play better as they get harder
+#![allow(clippy::needless_return)]
+
+use yahtzee::bot::AiLevel;
+use yahtzee::tournament::{ self, Standing };
+
+// The level's standing in the tournament
+fn standing(standings: &[Standing], level: AiLevel) -> &Standing {
+    return standings
+        .iter()
+        .find(|standing| standing.strategy == level.name())
+        .unwrap();
+}
+
+#[test]
+fn harder_levels_score_more() {
+    // Every level plays the same 200 seeded games
+    let levels = [AiLevel::Easy, AiLevel::Medium, AiLevel::Hard];
+    let names: Vec<String> = levels.iter().map(|level| level.name().to_string()).collect();
+    let standings = tournament::run(&names, 200, 1).unwrap().standings;
+
+    // Each level's mean score is above the next easier one's, with their 95% confidence intervals apart
+    for pair in levels.windows(2) {
+        let (easier, harder) = (standing(&standings, pair[0]), standing(&standings, pair[1]));
+        assert!(
+            harder.mean - harder.mean_margin > easier.mean + easier.mean_margin,
+            "{} {:.1} ± {:.1} isn't clearly above {} {:.1} ± {:.1}",
+            harder.strategy,
+            harder.mean,
+            harder.mean_margin,
+            easier.strategy,
+            easier.mean,
+            easier.mean_margin
+        );
+    }
+}
+
+#[test]
+fn levels_are_named() {
+    assert_eq!(AiLevel::from_name("Hard"), Some(AiLevel::Hard));
+    assert_eq!(AiLevel::from_name("extreme"), None);
+    for level in [AiLevel::Easy, AiLevel::Medium, AiLevel::Hard] {
+        assert_eq!(AiLevel::from_name(level.name()), Some(level));
+    }
+}
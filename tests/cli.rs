@@ -0,0 +1,156 @@
+// Running the yahtzee command as a player or script would, checking what it prints and how it exits
+#![allow(clippy::needless_return)]
+
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, Output, Stdio };
+
+use yahtzee::game::Game;
+use yahtzee::save::SavedGame;
+
+// An empty data directory for the test
+fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("yahtzee-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("saves")).unwrap();
+    return dir;
+}
+
+// Run yahtzee with the arguments, typing the input, with its files kept in the data directory
+fn run_in(dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yahtzee"))
+        .args(args)
+        .arg("--data-dir")
+        .arg(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    return child.wait_with_output().unwrap();
+}
+
+// Run yahtzee as above, in an empty data directory removed afterwards
+fn run(name: &str, args: &[&str], input: &str) -> Output {
+    let dir = data_dir(name);
+    let output = run_in(&dir, args, input);
+    std::fs::remove_dir_all(&dir).unwrap();
+    return output;
+}
+
+#[test]
+fn the_last_json_event_has_the_final_total() {
+    // On a scorecard of only Aces and Chance: roll once and score each, then decline the CSV and another game
+    let output = run("events", &["--json-events", "--seed", "7", "--sections", "1,13"], "1\n3\n13\n1\n3\n1\nn\nn\n");
+    assert!(output.status.success());
+
+    let printed = String::from_utf8_lossy(&output.stdout);
+    let total: i64 = printed
+        .lines()
+        .find_map(|line| line.strip_prefix("Game over! Total score: "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    // Every line written to stderr is an event, and the last is the final score
+    let events: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let last = events.last().unwrap();
+    assert_eq!(last["event"], "score");
+    assert_eq!(last["total"], total);
+    assert!(last["scorecard"].as_array().unwrap().iter().all(|section| section["filled"] == true));
+}
+
+#[test]
+fn analyzing_a_seed_twice_prints_the_same_breakdown() {
+    for level in [None, Some("easy"), Some("medium"), Some("hard")] {
+        let mut args = vec!["--analyze-seed", "5"];
+        args.extend(level.map(|level| ["--ai-level", level]).iter().flatten());
+        let (first, second) = (run("analyze", &args, ""), run("analyze", &args, ""));
+        assert!(first.status.success() & second.status.success());
+
+        // Every section's points and the total, exactly the same each time
+        let breakdown = String::from_utf8_lossy(&first.stdout);
+        assert!(breakdown.starts_with("Seed: 5\n1. Aces: "), "{}", breakdown);
+        assert!(breakdown.contains("\nTotal Score: "), "{}", breakdown);
+        assert_eq!(first.stdout, second.stdout, "{:?} played differently", level);
+    }
+}
+
+#[test]
+fn a_quiet_game_prints_only_its_total() {
+    let breakdown = run("quiet", &["--analyze-seed", "42"], "");
+    let total = String::from_utf8_lossy(&breakdown.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Total Score: "))
+        .unwrap()
+        .to_string();
+
+    // Exactly one line, the total
+    let quiet = run("quiet", &["--analyze-seed", "42", "--quiet"], "");
+    assert!(quiet.status.success());
+    assert_eq!(String::from_utf8_lossy(&quiet.stdout), format!("{}\n", total));
+
+    // With --json, one line of JSON with the same total
+    let json = run("quiet", &["--analyze-seed", "42", "--quiet", "--json"], "");
+    let printed = String::from_utf8_lossy(&json.stdout);
+    assert_eq!(printed.lines().count(), 1, "{}", printed);
+    let result: serde_json::Value = serde_json::from_str(&printed).unwrap();
+    assert_eq!(result["total"].to_string(), total);
+
+    // A game with prompts can't be quiet, it's refused as a usage mistake
+    let prompted = run("quiet", &["--seed", "42", "--quiet"], "");
+    assert_eq!(prompted.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&prompted.stdout).starts_with("--quiet is for games played without prompts"));
+}
+
+#[test]
+fn a_save_that_cant_be_loaded_exits_with_its_own_code() {
+    let dir = data_dir("load-errors");
+    let mut newer = serde_json::to_value(SavedGame::new(&Game::new(1), "Tester")).unwrap();
+    newer["version"] = serde_json::json!(99);
+    std::fs::write(dir.join("saves/newer.json"), newer.to_string()).unwrap();
+    std::fs::write(dir.join("saves/broken.json"), "{ not a save").unwrap();
+
+    // Missing, from a newer version, and unreadable
+    let codes: Vec<Option<i32>> = ["missing", "newer", "broken"]
+        .iter()
+        .map(|name| run_in(&dir, &["--load", name], "").status.code())
+        .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(codes, [Some(3), Some(4), Some(5)]);
+}
+
+#[test]
+fn a_script_continues_a_loaded_game() {
+    let dir = data_dir("script");
+    std::fs::write(dir.join("first.txt"), "roll\nscore chance\nroll\n").unwrap();
+    std::fs::write(dir.join("rest.txt"), "hold 1\nroll\nscore aces\n").unwrap();
+    std::fs::write(dir.join("whole.txt"), "roll\nscore chance\nroll\nhold 1\nroll\nscore aces\n").unwrap();
+    let script = |name: &str| dir.join(name).to_string_lossy().to_string();
+
+    // The first moves leave the game unfinished, so it's autosaved for the rest
+    let first = run_in(&dir, &["--seed", "11", "--sections", "1,13", "--script", &script("first.txt")], "");
+    assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stdout));
+    assert!(dir.join("saves/autosave.json").exists());
+    let rest = run_in(&dir, &["--load", "autosave", "--script", &script("rest.txt"), "--quiet"], "");
+    assert!(rest.status.success(), "{}", String::from_utf8_lossy(&rest.stdout));
+    assert!(!dir.join("saves/autosave.json").exists());
+
+    // It ends as the same moves played in one go
+    let whole = run_in(&dir, &["--seed", "11", "--sections", "1,13", "--script", &script("whole.txt"), "--quiet"], "");
+    assert_eq!(rest.stdout, whole.stdout);
+
+    // A move that can't be made, or a script that isn't there, exits with 6
+    std::fs::write(dir.join("bad.txt"), "roll\nscore chance\nscore chance\n").unwrap();
+    let bad = run_in(&dir, &["--seed", "11", "--script", &script("bad.txt")], "");
+    let missing = run_in(&dir, &["--seed", "11", "--script", &script("nothing.txt")], "");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(bad.status.code(), Some(6));
+    assert!(String::from_utf8_lossy(&bad.stdout).contains("Line 3"), "{}", String::from_utf8_lossy(&bad.stdout));
+    assert_eq!(missing.status.code(), Some(6));
+}
@@ -0,0 +1,41 @@
+// Describing a roll in plain English, with the names of the patterns it makes
+#![allow(clippy::needless_return)]
+
+use yahtzee::describe::{ describe, patterns };
+use yahtzee::die::Die;
+
+// Dice showing these numbers
+fn dice(nums: [u8; 5]) -> Vec<Die> {
+    return nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+#[test]
+fn a_full_house_is_described_with_its_parts() {
+    assert_eq!(
+        describe(&dice([6, 2, 6, 2, 6])),
+        "You have three 6s and a pair of 2s, that's a full house, three of a kind, and a pair."
+    );
+}
+
+#[test]
+fn every_pattern_the_dice_make_is_named() {
+    let cases: [([u8; 5], &[&str]); 6] = [
+        ([4, 4, 4, 4, 4], &["a Yahtzee", "four of a kind", "three of a kind"]),
+        ([3, 1, 3, 3, 3], &["four of a kind", "three of a kind"]),
+        ([5, 2, 5, 2, 1], &["two pairs"]),
+        ([2, 3, 1, 4, 4], &["a pair", "a run of four numbers in a row"]),
+        ([6, 2, 4, 3, 5], &["a run of five numbers in a row"]),
+        ([1, 2, 4, 5, 1], &["a pair"]),
+    ];
+    for (nums, expected) in cases {
+        assert_eq!(patterns(&dice(nums)), expected, "{:?}", nums);
+    }
+}
+
+#[test]
+fn dice_without_matches_are_listed_in_order() {
+    assert_eq!(describe(&dice([6, 1, 4, 2, 5])), "You have 1, 2, 4, 5, and 6, that's a run of three numbers in a row.");
+}
@@ -0,0 +1,81 @@
+// Rolling dice from a source of numbers given ahead of time
+#![allow(clippy::needless_return)]
+
+use rand::{ rngs::StdRng, SeedableRng };
+use yahtzee::die::{ Die, Random, ScriptedDice };
+use yahtzee::game::Game;
+use yahtzee::invariant::Strictness;
+
+// The numbers the dice show
+fn faces(dice: &[Die]) -> Vec<u8> {
+    return dice
+        .iter()
+        .map(|die| die.num)
+        .collect();
+}
+
+#[test]
+fn the_first_roll_is_the_scripted_dice() {
+    let mut game = Game::new(1);
+    game.script_dice(ScriptedDice::new(&[1, 2, 3, 4, 5]));
+
+    // Peeking shows the same roll without using it up
+    assert_eq!(faces(&game.peek_roll()), [1, 2, 3, 4, 5]);
+    game.roll().unwrap();
+    assert_eq!(faces(&game.dice), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn frozen_dice_keep_their_number() {
+    let mut game = Game::new(1);
+    game.script_dice(ScriptedDice::new(&[6, 6, 2, 6, 6, 3, 4]));
+    game.roll().unwrap();
+    game.dice[2].frozen = true;
+
+    // Only the unfrozen dice take the next numbers, and the script starts over once it runs out
+    game.roll().unwrap();
+    assert_eq!(faces(&game.dice), [3, 4, 2, 6, 6]);
+}
+
+#[test]
+fn a_reset_d8_keeps_rolling_its_own_sides() {
+    let mut die = Die::new(8);
+    die.roll(&mut ScriptedDice::new(&[8]));
+    die.frozen = true;
+    die.reset();
+    assert_eq!(die, Die { num: 0, frozen: false, sides: 8 });
+
+    // It rolls every number from 1 to 8 again, and nothing higher
+    let mut rng = StdRng::seed_from_u64(8);
+    let mut rolled = [false; 9];
+    for _ in 0..500 {
+        die.roll(&mut rng);
+        assert!((1..=8).contains(&die.num), "A d8 rolled {}", die.num);
+        rolled[usize::from(die.num)] = true;
+    }
+    assert!(rolled[1..].iter().all(|seen| *seen));
+}
+
+#[test]
+fn a_yahtzee_kept_through_a_reroll_counts_once() {
+    let mut game = Game::new(1);
+    game.script_dice(ScriptedDice::new(&[4]));
+    game.roll().unwrap();
+    assert_eq!(game.yahtzees, 1);
+
+    // Rerolling the last die to another 4 leaves the same Yahtzee
+    for die_i in 0..4 {
+        game.toggle_freeze(die_i).unwrap();
+    }
+    game.roll().unwrap();
+    assert_eq!(faces(&game.dice), [4; 5]);
+    assert_eq!(game.yahtzees, 1);
+}
+
+#[test]
+#[should_panic(expected = "A 6-sided die can't roll 7")]
+fn a_die_only_rolls_its_own_sides() {
+    let mut scripted = ScriptedDice::new(&[7]);
+    scripted.strictness = Strictness::Strict;
+    Die::default().roll(&mut scripted);
+}
@@ -0,0 +1,69 @@
+// Exporting finished scorecards as CSV, and reading the CSV back
+#![allow(clippy::needless_return)]
+
+use yahtzee::export::scorecard_csv;
+use yahtzee::game::{ Game, Move };
+
+// Split CSV into rows of fields, undoing the quoting of fields with commas or quotes in them
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    for line in csv.lines() {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted & (chars.peek() == Some(&'"')) => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        fields.push(field);
+        rows.push(fields);
+    }
+    return rows;
+}
+
+// A finished game on the seed, rolling once each turn and scoring the first open section
+fn finished_game(seed: u64) -> Game {
+    let mut game = Game::new(seed);
+    for section_i in 0..game.scorecard.len() {
+        game.play(Move::Roll).unwrap();
+        game.play(Move::Score(section_i)).unwrap();
+    }
+    return game;
+}
+
+#[test]
+fn exported_scorecards_read_back_as_the_same_rows() {
+    let (first, second) = (finished_game(1), finished_game(2));
+    let csv = scorecard_csv(&[("Smith, \"Jo\"", &first), ("Lee", &second)]);
+    let rows = parse_csv(&csv);
+
+    // The header names each player's columns, with the comma and quotes in the name kept
+    assert_eq!(rows[0], ["Section", "Smith, \"Jo\"", "Smith, \"Jo\" scratched", "Lee", "Lee scratched"]);
+
+    // A row for every section, then the totals
+    assert_eq!(rows.len(), 1 + first.scorecard.len() + 5);
+    for (row, section_i) in rows[1..].iter().zip(0..first.scorecard.len()) {
+        let mut expected = vec![first.scorecard[section_i].get_name().to_string()];
+        for game in [&first, &second] {
+            let points = game.scorecard[section_i].get_points();
+            expected.push(points.to_string());
+            expected.push((points == 0).to_string());
+        }
+        assert_eq!(*row, expected);
+    }
+    let totals = &rows[1 + first.scorecard.len()..];
+    for (total_i, row) in totals.iter().enumerate() {
+        let (name, points) = first.totals().rows()[total_i];
+        let second_points = second.totals().rows()[total_i].1;
+        assert_eq!(*row, [name.to_string(), points.to_string(), String::new(), second_points.to_string(), String::new()]);
+    }
+    assert_eq!(totals[4][1], first.total().to_string());
+}
@@ -0,0 +1,114 @@
+// Junk input (huge numbers, unicode, NULs, invalid UTF-8, very long lines) never panics whatever reads it,
+// it's always answered or rejected
+#![allow(clippy::needless_return)]
+
+use proptest::prelude::*;
+use std::io::Cursor;
+use yahtzee::engine::{ run_engine, Response };
+use yahtzee::game::Game;
+use yahtzee::sections::{ create_scorecard, find_sections, numbered_section };
+
+// The longest error the engine should give, however long the line it's rejecting
+static MAX_ERROR_LEN: usize = 200;
+
+// Play the lines through the engine, returning its responses (or None if the input couldn't be read)
+fn run(input: Vec<u8>) -> Option<Vec<Response>> {
+    let mut output = Vec::new();
+    run_engine(Game::new(1), Cursor::new(input), &mut output).ok()?;
+    let responses = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    return Some(responses);
+}
+
+// Any number, usually a small one so some commands work
+fn number() -> impl Strategy<Value = String> {
+    return prop_oneof![
+        (0u64..15).prop_map(|n| n.to_string()),
+        any::<u64>().prop_map(|n| n.to_string()),
+        any::<i64>().prop_map(|n| n.to_string()),
+        Just("1e400".to_string()),
+        Just("-0".to_string()),
+        Just("18446744073709551616".to_string())
+    ];
+}
+
+// A list of numbers, sometimes far too long
+fn numbers() -> impl Strategy<Value = String> {
+    return prop::collection::vec(number(), 0..40).prop_map(|numbers| format!("[{}]", numbers.join(",")));
+}
+
+// A command that's close to valid, with any numbers in it
+fn command() -> impl Strategy<Value = String> {
+    return prop_oneof![
+        Just(r#"{"cmd":"roll"}"#.to_string()),
+        Just(r#"{"cmd":"state"}"#.to_string()),
+        numbers().prop_map(|dice| format!(r#"{{"cmd":"hold","dice":{}}}"#, dice)),
+        numbers().prop_map(|keep| format!(r#"{{"cmd":"turn","keep":{}}}"#, keep)),
+        number().prop_map(|section| format!(r#"{{"cmd":"score","section":{}}}"#, section)),
+        (number(), any::<bool>()).prop_map(|(die, up)| format!(r#"{{"cmd":"nudge","die":{},"up":{}}}"#, die, up)),
+        number().prop_map(|seed| format!(r#"{{"cmd":"seed","seed":{}}}"#, seed)),
+        any::<String>()
+    ];
+}
+
+proptest! {
+    #[test]
+    fn the_engine_answers_any_bytes(lines in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..100), 0..20)) {
+        // Input that isn't UTF-8 can't be read at all, stopping the engine with an error instead
+        if let Some(responses) = run(lines.join(&b'\n')) {
+            for response in responses {
+                prop_assert!(response.error.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn the_engine_answers_every_command(commands in prop::collection::vec(command(), 0..60)) {
+        let input = commands.join("\n");
+        let responses = run(input.clone().into_bytes()).unwrap();
+
+        // Every line that isn't blank gets a response, and the game stays playable
+        let lines = input.lines().filter(|line| !line.trim().is_empty()).count();
+        prop_assert_eq!(responses.len(), lines);
+        for response in responses {
+            prop_assert!(response.state.rolls_left <= 3);
+            prop_assert!(response.error.map_or(0, |error| error.len()) <= MAX_ERROR_LEN);
+        }
+    }
+
+    #[test]
+    fn any_text_finds_real_sections(text in any::<String>()) {
+        let scorecard = create_scorecard();
+        for section_i in find_sections(&scorecard, &text) {
+            prop_assert!(section_i < scorecard.len());
+        }
+        if let Ok(number) = text.trim().parse::<usize>() {
+            prop_assert_eq!(numbered_section(&scorecard, number).is_some(), (1..=13).contains(&number));
+        }
+    }
+}
+
+#[test]
+fn a_very_long_line_gets_a_short_error() {
+    let mut line = "\0é🎲".repeat(100_000);
+    line.push('\n');
+    let responses = run(line.into_bytes()).unwrap();
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].error.as_ref().unwrap().len() <= MAX_ERROR_LEN);
+}
+
+#[test]
+fn a_huge_list_of_the_same_die_holds_only_it() {
+    let dice = vec!["4"; 100_000].join(",");
+    let input = format!("{{\"cmd\":\"roll\"}}\n{{\"cmd\":\"hold\",\"dice\":[{}]}}\n", dice);
+    let responses = run(input.into_bytes()).unwrap();
+    assert_eq!(responses[1].error, None);
+    let frozen: Vec<bool> = responses[1].state.dice
+        .iter()
+        .map(|die| die.frozen)
+        .collect();
+    assert_eq!(frozen, [false, false, false, false, true]);
+}
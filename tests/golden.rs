@@ -0,0 +1,193 @@
+// Whole games played from a fixed seed (or fixed dice) and a fixed script of moves, checked against the
+// exact scorecard and totals they finish with. A change to the rules anywhere changes one of these
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::ScriptedDice;
+use yahtzee::game::{ Game, Move };
+use yahtzee::sections::{ ScoreSummary, UpperBonus };
+
+// The points every section finished with, then the subtotals, bonuses, and total
+fn assert_final(game: &Game, points: &[i32], totals: ScoreSummary) {
+    assert!(!game.in_progress());
+    assert_eq!(game.scorecard.len(), points.len());
+    for (section, points) in game.scorecard.iter().zip(points) {
+        assert!(section.is_filled(), "{}", section.get_name());
+        assert_eq!(section.get_points(), *points, "{}", section.get_name());
+    }
+
+    let summary = game.totals();
+    assert_eq!(summary.upper, totals.upper, "upper subtotal");
+    assert_eq!(summary.upper_bonus, totals.upper_bonus, "upper bonus");
+    assert_eq!(summary.lower, totals.lower, "lower subtotal");
+    assert_eq!(summary.yahtzee_bonus, totals.yahtzee_bonus, "Yahtzee bonus");
+    assert_eq!(summary.yahtzee_bonuses, totals.yahtzee_bonuses, "Yahtzee bonuses");
+    assert_eq!(summary.total, totals.total, "total");
+    assert_eq!(game.total(), totals.total);
+}
+
+// Play the game rolling these hands in order, each turn rolling once and scoring the hand in the section
+fn play_hands(mut game: Game, turns: &[([u8; 5], usize)]) -> Game {
+    let faces: Vec<u8> = turns
+        .iter()
+        .flat_map(|(hand, _)| *hand)
+        .collect();
+    game.script_dice(ScriptedDice::new(&faces));
+    for (_, section_i) in turns {
+        game.play(Move::Roll).unwrap();
+        game.play(Move::Score(*section_i)).unwrap();
+    }
+    return game;
+}
+
+#[test]
+fn a_seeded_game() {
+    // Keep the most promising dice for a couple of rolls, scoring in a mixed order
+    let mut game = Game::new(2024);
+    let turns: [(&[&[usize]], usize); 13] = [
+        (&[&[0, 1], &[0, 1, 2]], 12),
+        (&[&[]], 0),
+        (&[&[2, 3]], 5),
+        (&[], 6),
+        (&[&[0], &[0, 4]], 1),
+        (&[&[1, 2, 3]], 9),
+        (&[&[4], &[3, 4]], 3),
+        (&[], 11),
+        (&[&[0, 1, 2, 3]], 10),
+        (&[&[2]], 2),
+        (&[&[0, 1], &[0, 1]], 7),
+        (&[&[3, 4], &[2, 3, 4]], 4),
+        (&[&[1]], 8),
+    ];
+    for (holds, section_i) in turns {
+        game.play(Move::Roll).unwrap();
+        for held in holds {
+            game.play(Move::Hold(held.to_vec())).unwrap();
+            game.play(Move::Roll).unwrap();
+        }
+        game.play(Move::Score(section_i)).unwrap();
+    }
+
+    let totals = ScoreSummary { upper: 13, upper_bonus: 0, lower: 46, yahtzee_bonus: 0, yahtzee_bonuses: 0, total: 59 };
+    assert_final(&game, &[0, 0, 3, 4, 0, 6, 0, 0, 0, 30, 0, 0, 16], totals);
+}
+
+#[test]
+fn a_game_earning_the_upper_bonus() {
+    let game = play_hands(Game::new(1), &[
+        ([1, 1, 1, 2, 3], 0),
+        ([2, 2, 2, 4, 5], 1),
+        ([3, 3, 3, 1, 2], 2),
+        ([4, 4, 4, 1, 2], 3),
+        ([5, 5, 5, 1, 2], 4),
+        // Three of every number is exactly enough for the bonus
+        ([6, 6, 6, 1, 2], 5),
+        ([6, 6, 6, 5, 4], 6),
+        ([5, 5, 5, 5, 1], 7),
+        ([2, 2, 2, 2, 2], 8),
+        ([1, 2, 3, 6, 6], 9),
+        ([3, 4, 5, 6, 6], 10),
+        ([2, 3, 4, 5, 6], 11),
+        ([6, 6, 5, 5, 4], 12),
+    ]);
+
+    let totals = ScoreSummary { upper: 63, upper_bonus: 35, lower: 244, yahtzee_bonus: 0, yahtzee_bonuses: 0, total: 342 };
+    assert_final(&game, &[3, 6, 9, 12, 15, 18, 27, 21, 50, 30, 40, 50, 26], totals);
+}
+
+#[test]
+fn a_game_short_of_a_raised_upper_bonus() {
+    // The same game as above, but 63 in the upper sections isn't enough anymore
+    let mut game = Game::new(1);
+    game.upper_bonus = UpperBonus { threshold: 70, points: 50 };
+    let game = play_hands(game, &[
+        ([1, 1, 1, 2, 3], 0),
+        ([2, 2, 2, 4, 5], 1),
+        ([3, 3, 3, 1, 2], 2),
+        ([4, 4, 4, 1, 2], 3),
+        ([5, 5, 5, 1, 2], 4),
+        ([6, 6, 6, 1, 2], 5),
+        ([6, 6, 6, 5, 4], 6),
+        ([5, 5, 5, 5, 1], 7),
+        ([2, 2, 2, 2, 2], 8),
+        ([1, 2, 3, 6, 6], 9),
+        ([3, 4, 5, 6, 6], 10),
+        ([2, 3, 4, 5, 6], 11),
+        ([6, 6, 5, 5, 4], 12),
+    ]);
+
+    let totals = ScoreSummary { upper: 63, upper_bonus: 0, lower: 244, yahtzee_bonus: 0, yahtzee_bonuses: 0, total: 307 };
+    assert_final(&game, &[3, 6, 9, 12, 15, 18, 27, 21, 50, 30, 40, 50, 26], totals);
+}
+
+#[test]
+fn a_game_earning_a_raised_upper_bonus() {
+    let mut game = Game::new(1);
+    game.upper_bonus = UpperBonus { threshold: 70, points: 50 };
+    let game = play_hands(game, &[
+        ([1, 1, 1, 1, 3], 0),
+        ([2, 2, 2, 4, 5], 1),
+        ([3, 3, 3, 1, 2], 2),
+        ([4, 4, 4, 1, 2], 3),
+        ([5, 5, 5, 1, 2], 4),
+        // An extra 1 and an extra 6 reach exactly 70
+        ([6, 6, 6, 6, 2], 5),
+        ([6, 6, 6, 5, 4], 6),
+        ([5, 5, 5, 5, 1], 7),
+        ([2, 2, 2, 2, 2], 8),
+        ([1, 2, 3, 6, 6], 9),
+        ([3, 4, 5, 6, 6], 10),
+        ([2, 3, 4, 5, 6], 11),
+        ([6, 6, 5, 5, 4], 12),
+    ]);
+
+    let totals = ScoreSummary { upper: 70, upper_bonus: 50, lower: 244, yahtzee_bonus: 0, yahtzee_bonuses: 0, total: 364 };
+    assert_final(&game, &[4, 6, 9, 12, 15, 24, 27, 21, 50, 30, 40, 50, 26], totals);
+}
+
+#[test]
+fn a_game_with_a_bonus_yahtzee() {
+    let game = play_hands(Game::new(1), &[
+        ([4, 4, 4, 4, 4], 8),
+        // Another Yahtzee after the YAHTZEE section scored earns the bonus, wherever it's scored
+        ([4, 4, 4, 4, 4], 3),
+        ([1, 1, 2, 3, 5], 0),
+        ([2, 3, 2, 6, 6], 1),
+        ([3, 1, 1, 6, 5], 2),
+        ([5, 5, 1, 2, 3], 4),
+        ([6, 6, 6, 1, 2], 5),
+        ([1, 1, 1, 2, 3], 6),
+        ([3, 3, 3, 3, 2], 7),
+        ([4, 5, 6, 1, 1], 9),
+        ([1, 2, 3, 4, 4], 10),
+        ([1, 2, 3, 4, 5], 11),
+        ([1, 2, 2, 3, 3], 12),
+    ]);
+
+    let totals = ScoreSummary { upper: 57, upper_bonus: 0, lower: 203, yahtzee_bonus: 100, yahtzee_bonuses: 1, total: 360 };
+    assert_final(&game, &[2, 4, 3, 20, 10, 18, 8, 14, 50, 30, 40, 50, 11], totals);
+    assert_eq!(game.yahtzees, 2);
+}
+
+#[test]
+fn a_game_scratching_every_lower_section() {
+    // Chance scores any dice, so it's left off the scorecard for every lower section to be scratched
+    let mut game = Game::new(1);
+    game.enable_sections(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]).unwrap();
+    let game = play_hands(game, &[
+        ([1, 1, 1, 1, 2], 0),
+        ([2, 2, 2, 2, 1], 1),
+        ([3, 3, 3, 3, 1], 2),
+        ([4, 4, 4, 4, 1], 3),
+        ([5, 5, 5, 5, 1], 4),
+        ([6, 6, 6, 6, 1], 5),
+        ([1, 1, 2, 2, 6], 6),
+        ([1, 1, 2, 2, 6], 7),
+        ([1, 1, 2, 2, 6], 8),
+        ([1, 1, 2, 2, 6], 9),
+        ([1, 1, 2, 2, 6], 10),
+        ([1, 1, 2, 2, 6], 11),
+    ]);
+
+    let totals = ScoreSummary { upper: 84, upper_bonus: 35, lower: 0, yahtzee_bonus: 0, yahtzee_bonuses: 0, total: 119 };
+    assert_final(&game, &[4, 8, 12, 16, 20, 24, 0, 0, 0, 0, 0, 0], totals);
+}
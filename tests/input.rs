@@ -0,0 +1,13 @@
+// Reading input that can time out, as the demo does
+#![allow(clippy::needless_return)]
+
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use yahtzee::input::read_line_timeout;
+
+#[test]
+fn waiting_for_input_before_reading_it_in_the_background_is_an_error() {
+    let waited = read_line_timeout(Duration::from_millis(1));
+    assert_eq!(waited.unwrap_err().kind(), ErrorKind::Other);
+}
@@ -0,0 +1,51 @@
+// Recovering from broken invariants instead of panicking, as release builds do
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::{ Die, ScriptedDice };
+use yahtzee::game::{ Game, MAX_ROLLS };
+use yahtzee::histogram::Histogram;
+use yahtzee::invariant::Strictness;
+
+#[test]
+fn too_many_rolls_are_cut_back_to_a_turn() {
+    let mut game = Game::new(1);
+    game.strictness = Strictness::Recover;
+    game.rolls = 200;
+
+    game.roll().unwrap();
+    assert_eq!(game.rolls, MAX_ROLLS - 1);
+
+    // The warning is kept for the player's program to report, once
+    assert_eq!(game.take_warnings(), ["200 rolls left, a turn only has 3"]);
+    assert!(game.take_warnings().is_empty());
+}
+
+#[test]
+fn a_face_a_die_cant_show_is_rolled_as_its_nearest_side() {
+    let mut game = Game::new(1);
+    game.strictness = Strictness::Recover;
+    game.script_dice(ScriptedDice::new(&[7, 2, 2, 2, 2]));
+
+    game.roll().unwrap();
+    assert_eq!(game.dice.iter().map(|die| die.num).collect::<Vec<u8>>(), [6, 2, 2, 2, 2]);
+    assert_eq!(game.warnings, ["A 6-sided die can't roll 7"]);
+}
+
+#[test]
+#[should_panic(expected = "200 rolls left, a turn only has 3")]
+fn a_strict_game_panics_on_too_many_rolls() {
+    let mut game = Game::new(1);
+    game.strictness = Strictness::Strict;
+    game.rolls = 200;
+    let _ = game.roll();
+}
+
+#[test]
+fn a_die_without_sides_is_an_ordinary_die() {
+    assert_eq!(Die::new(0).sides, 6);
+}
+
+#[test]
+fn buckets_without_width_are_one_wide() {
+    assert_eq!(Histogram::new(&[10, 12], 0), Histogram::new(&[10, 12], 1));
+}
@@ -0,0 +1,101 @@
+// Rules every section's scoring keeps, checked against generated hands
+#![allow(clippy::needless_return)]
+
+use proptest::prelude::*;
+use yahtzee::die::Die;
+use yahtzee::sections::{ create_scorecard, odd_even_sections, sum_straight, Scorecard, SectionKind };
+
+// Every section that can be on a scorecard, including the straights scored by the sum straights rule
+fn every_section() -> Scorecard {
+    let mut sections: Scorecard = create_scorecard().into_iter().chain(odd_even_sections()).collect();
+    let sum_straights: Scorecard = sections
+        .iter()
+        .filter_map(|section| sum_straight(section.as_ref()))
+        .collect();
+    sections.extend(sum_straights);
+    return sections;
+}
+
+// Dice showing these numbers
+fn dice(nums: &[u8]) -> Vec<Die> {
+    return nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+// Five rolled dice
+fn hand() -> impl Strategy<Value = Vec<u8>> {
+    return prop::collection::vec(1u8..=6, 5);
+}
+
+// A hand, and the same hand in another order
+fn shuffled_hand() -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    return hand().prop_flat_map(|nums| (Just(nums.clone()), Just(nums).prop_shuffle()));
+}
+
+proptest! {
+    #[test]
+    fn scores_are_never_negative(nums in hand()) {
+        for section in &every_section() {
+            prop_assert!(section.calc_score(&dice(&nums)) >= 0, "{}", section.get_name());
+        }
+    }
+
+    #[test]
+    fn upper_sections_score_at_most_five_of_their_number(nums in hand()) {
+        for section in &every_section() {
+            if let Some(face) = section.face_value() {
+                prop_assert!(section.calc_score(&dice(&nums)) <= 5 * i32::from(face), "{}", section.get_name());
+            }
+        }
+    }
+
+    #[test]
+    fn of_a_kind_scores_at_most_the_dice_total(nums in hand()) {
+        let total: i32 = nums.iter().map(|num| i32::from(*num)).sum();
+        for section in &every_section() {
+            // A YAHTZEE is a fixed 50 instead
+            if (section.kind().0 == SectionKind::OfAKind) & (section.kind().1 < 5) {
+                prop_assert!(section.calc_score(&dice(&nums)) <= total, "{}", section.get_name());
+            }
+        }
+    }
+
+    #[test]
+    fn longer_straights_also_score_as_shorter_ones(nums in hand()) {
+        let dice = dice(&nums);
+        let sections = every_section();
+        for longer in &sections {
+            for shorter in &sections {
+                let (kind, length) = longer.kind();
+                let (shorter_kind, shorter_length) = shorter.kind();
+                let straights = (kind == shorter_kind) & ((kind == SectionKind::Straight) | (kind == SectionKind::SumStraight));
+                if straights & (shorter_length < length) & (longer.calc_score(&dice) > 0) {
+                    prop_assert!(shorter.calc_score(&dice) > 0, "{} but not {}", longer.get_name(), shorter.get_name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_order_of_the_dice_never_matters((nums, shuffled) in shuffled_hand()) {
+        for section in &every_section() {
+            prop_assert_eq!(section.calc_score(&dice(&nums)), section.calc_score(&dice(&shuffled)), "{}", section.get_name());
+        }
+    }
+
+    #[test]
+    fn another_die_never_lowers_of_a_kind_or_chance(nums in prop::collection::vec(1u8..=6, 4), extra in 1u8..=6) {
+        let mut more = nums.clone();
+        more.push(extra);
+        for section in &every_section() {
+            if matches!(section.kind().0, SectionKind::OfAKind | SectionKind::Chance) {
+                prop_assert!(
+                    section.calc_score(&dice(&more)) >= section.calc_score(&dice(&nums)),
+                    "{}", section.get_name()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Driving a whole game through the line protocol, as another program would
+#![allow(clippy::needless_return)]
+
+use std::collections::HashMap;
+use std::io::{ BufRead, BufReader, Write };
+use std::process::{ Command, Stdio };
+
+// The key=value fields of a response line, after its first word
+fn fields(response: &str) -> HashMap<String, String> {
+    return response
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|field| field.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+}
+
+#[test]
+fn a_script_plays_a_whole_game() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yahtzee"))
+        .args(["--protocol", "--seed", "11"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = child.stdin.take().unwrap();
+    let mut output = BufReader::new(child.stdout.take().unwrap()).lines();
+    let mut send = |command: &str| -> String {
+        writeln!(input, "{}", command).unwrap();
+        return output.next().unwrap().unwrap();
+    };
+
+    // A mistake is answered, and doesn't stop the game
+    assert!(send("SCORE chance").starts_with("ERROR Roll before"));
+    assert_eq!(send("JUMP"), "ERROR Unknown command 'JUMP'");
+
+    // Every turn: roll, keep the first die and roll again, then score the first open section
+    let mut turns = 0;
+    loop {
+        let state = fields(&send("STATE"));
+        if state["over"] == "true" {
+            break;
+        }
+        assert!(send("ROLL").starts_with("OK "));
+        assert_eq!(fields(&send("FREEZE 1"))["frozen"], "1");
+        assert!(send("ROLL").starts_with("OK "));
+
+        let open = state["open"].split(',').next().unwrap().to_string();
+        let scored = fields(&send(&format!("SCORE {}", open)));
+        assert!(!scored["open"].split(',').any(|number| number == open));
+        turns += 1;
+    }
+    assert_eq!(turns, 13);
+
+    // The final total is read back when quitting
+    let total = fields(&send("STATE"))["total"].clone();
+    assert_eq!(send("QUIT"), format!("BYE total={}", total));
+    assert!(child.wait().unwrap().success());
+}
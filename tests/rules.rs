@@ -0,0 +1,141 @@
+// The optional rules a game can be played with
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::{ Die, ScriptedDice };
+use yahtzee::error::GameError;
+use yahtzee::game::{ Game, Move };
+use yahtzee::sections::audit;
+
+// Which dice are frozen
+fn frozen(game: &Game) -> Vec<bool> {
+    return game.dice.iter().map(|die| die.frozen).collect();
+}
+
+// Unfrozen dice showing the numbers
+fn dice(nums: [u8; 5]) -> Vec<Die> {
+    return nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+#[test]
+fn a_handicap_fills_the_lowest_value_sections() {
+    let mut game = Game::new(1);
+    game.handicap(6).unwrap();
+
+    // Aces to Fives and 4 of a Kind average the least, Fives tying YAHTZEE but coming first on the scorecard
+    let handicapped: Vec<usize> = (0..game.scorecard.len()).filter(|i| game.scorecard[*i].is_handicap()).collect();
+    assert_eq!(handicapped, [0, 1, 2, 3, 4, 7]);
+    let points: Vec<i32> = handicapped.iter().map(|i| game.scorecard[*i].get_points()).collect();
+    assert_eq!(points, [2, 5, 9, 12, 16, 13]);
+    assert_eq!((game.totals().upper, game.totals().lower, game.total()), (44, 13, 57));
+    assert_eq!(game.zeros_scored(), 0);
+
+    // At least one section is left to play
+    assert!(matches!(Game::new(1).handicap(13), Err(GameError::InvalidMove(_))));
+}
+
+#[test]
+fn a_handicapped_game_audits_clean_to_the_end() {
+    let mut game = Game::new(2);
+    game.handicap(4).unwrap();
+    assert!(audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus));
+
+    // Play the open sections in order, checking the running totals after each
+    while let Some(section_i) = (0..game.scorecard.len()).find(|i| !game.scorecard[*i].is_filled()) {
+        game.play(Move::Roll).unwrap();
+        game.play(Move::Score(section_i)).unwrap();
+        assert!(audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus));
+    }
+    let points: i32 = game.scorecard.iter().map(|section| section.get_points()).sum();
+    assert_eq!(game.total(), points + game.totals().upper_bonus + game.totals().yahtzee_bonus);
+}
+
+#[test]
+fn a_joker_yahtzee_must_be_scored_in_its_upper_section() {
+    let mut game = Game::new(1);
+    game.joker = true;
+    game.script_dice(ScriptedDice::new(&[4]));
+    game.play(Move::Roll).unwrap();
+
+    // Five 4s can only go in Fours while it's open, where they score 20
+    assert_eq!(game.forced_section(), Some(3));
+    assert!(matches!(game.score(8), Err(GameError::InvalidMove(_))));
+    assert!(matches!(game.score(12), Err(GameError::InvalidMove(_))));
+    game.score(3).unwrap();
+    assert_eq!(game.scorecard[3].get_points(), 20);
+    assert_eq!(game.total(), 20);
+
+    // With Fours filled, the next Yahtzee of 4s can go anywhere
+    game.play(Move::Roll).unwrap();
+    assert_eq!(game.forced_section(), None);
+    game.score(8).unwrap();
+    assert_eq!(game.scorecard[8].get_points(), 50);
+}
+
+#[test]
+fn no_more_than_the_max_holds_can_be_frozen() {
+    let mut game = Game::new(1);
+    game.max_holds = Some(2);
+    game.play(Move::Roll).unwrap();
+
+    // A third freeze is refused, leaving the dice as they were
+    game.toggle_freeze(0).unwrap();
+    game.toggle_freeze(1).unwrap();
+    assert!(matches!(game.toggle_freeze(2), Err(GameError::InvalidMove(_))));
+    assert!(matches!(game.play(Move::Hold(vec![0, 1, 2])), Err(GameError::InvalidMove(_))));
+    assert_eq!(frozen(&game), [true, true, false, false, false]);
+
+    // Unfreezing a die makes room for another
+    game.toggle_freeze(0).unwrap();
+    game.toggle_freeze(2).unwrap();
+    assert_eq!(frozen(&game), [false, true, true, false, false]);
+}
+
+#[test]
+fn a_second_zero_is_blocked_while_other_sections_score() {
+    let mut game = Game::new(1);
+    game.max_zeros = Some(1);
+
+    // The first zero is allowed
+    game.play(Move::Roll).unwrap();
+    game.set_dice(&dice([1, 1, 2, 3, 4]), 2).unwrap();
+    assert!(!game.zero_blocked(11));
+    game.score(11).unwrap();
+    assert_eq!(game.zeros_scored(), 1);
+
+    // A second isn't, while another open section would score points
+    game.play(Move::Roll).unwrap();
+    game.set_dice(&dice([2, 2, 3, 3, 5]), 2).unwrap();
+    assert!(game.zero_blocked(0));
+    assert!(matches!(game.score(0), Err(GameError::InvalidMove(_))));
+    assert!(!game.scorecard[0].is_filled());
+    assert!(!game.zero_blocked(1));
+    game.score(1).unwrap();
+    assert_eq!(game.zeros_scored(), 1);
+}
+
+#[test]
+fn a_nudge_bumps_one_die_once_a_game() {
+    let mut game = Game::new(1);
+    game.set_nudges(1);
+    assert!(matches!(game.nudge(0, true), Err(GameError::InvalidMove(_))));
+
+    // A die can't go past its sides, and a refused nudge isn't used up
+    game.play(Move::Roll).unwrap();
+    game.set_dice(&dice([3, 3, 3, 6, 1]), 2).unwrap();
+    assert!(matches!(game.nudge(3, true), Err(GameError::InvalidMove(_))));
+    assert!(matches!(game.nudge(4, false), Err(GameError::InvalidMove(_))));
+    assert_eq!(game.nudges_remaining, 1);
+
+    // Bumping the 1 up to a 2 uses the game's only nudge
+    game.play(Move::Nudge(4, true)).unwrap();
+    assert_eq!(game.dice.iter().map(|die| die.num).collect::<Vec<u8>>(), [3, 3, 3, 6, 2]);
+    assert_eq!(game.nudges_remaining, 0);
+    assert!(matches!(game.nudge(4, true), Err(GameError::InvalidMove(_))));
+
+    // A new game gets its nudge back
+    game.restart();
+    assert_eq!(game.nudges_remaining, 1);
+}
@@ -0,0 +1,77 @@
+// Saving a game and loading it back, and loading saves written by older versions
+#![allow(clippy::needless_return)]
+
+use std::path::Path;
+
+use yahtzee::game::{ Game, Move };
+use yahtzee::save::{ read_save, LoadError, SavedGame };
+use yahtzee::sections::SectionSnapshot;
+
+// Every section's snapshot, to compare scorecards
+fn snapshots(game: &Game) -> Vec<SectionSnapshot> {
+    return game.scorecard.iter().map(|section| section.snapshot()).collect();
+}
+
+#[test]
+fn a_game_saved_mid_turn_loads_where_it_was_left() {
+    // Score a turn, then roll and freeze some dice in the next
+    let mut game = Game::new(42);
+    for player_move in [Move::Roll, Move::Score(12), Move::Roll, Move::Hold(vec![0, 2])] {
+        game.play(player_move).unwrap();
+    }
+
+    // Saved to JSON and read back, as it is in a save file
+    let json = serde_json::to_string(&SavedGame::new(&game, "Tester")).unwrap();
+    let saved: SavedGame = serde_json::from_str(&json).unwrap();
+    let mut loaded = saved.restore().unwrap();
+
+    assert_eq!(loaded.dice, game.dice);
+    assert_eq!(loaded.dice.iter().map(|die| die.frozen).collect::<Vec<bool>>(), [true, false, true, false, false]);
+    assert_eq!(loaded.rolls, game.rolls);
+    assert_eq!(loaded.turn, 2);
+    assert_eq!(snapshots(&loaded), snapshots(&game));
+    assert_eq!(loaded.totals(), game.totals());
+
+    // The next roll is the one the game would have rolled
+    assert_eq!(loaded.peek_roll(), game.peek_roll());
+    game.play(Move::Roll).unwrap();
+    loaded.play(Move::Roll).unwrap();
+    assert_eq!(loaded.dice, game.dice);
+}
+
+#[test]
+fn a_version_1_save_is_upgraded_on_load() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/save_v1.json");
+    let saved = read_save(&path).unwrap();
+    assert_eq!(saved.version, 2);
+    assert_eq!(saved.total, 18);
+    assert_eq!(saved.turn, 3);
+    assert_eq!(saved.rolls_used, 3);
+
+    // It continues as the game it was saved from, with the rules that game was played by
+    let mut game = Game::new(42);
+    for player_move in [Move::Roll, Move::Score(12), Move::Roll, Move::Score(0), Move::Roll, Move::Hold(vec![1, 3])] {
+        game.play(player_move).unwrap();
+    }
+    let mut loaded = saved.restore().unwrap();
+    assert_eq!(snapshots(&loaded), snapshots(&game));
+    assert_eq!(loaded.totals(), game.totals());
+    assert_eq!(loaded.dice, game.dice);
+    assert_eq!(loaded.upper_bonus, game.upper_bonus);
+    assert_eq!(loaded.yahtzee_bonus, game.yahtzee_bonus);
+    loaded.play(Move::Roll).unwrap();
+    game.play(Move::Roll).unwrap();
+    assert_eq!(loaded.dice, game.dice);
+}
+
+#[test]
+fn a_save_from_a_newer_version_is_refused() {
+    let mut saved = serde_json::to_value(SavedGame::new(&Game::new(1), "Tester")).unwrap();
+    saved["version"] = serde_json::json!(99);
+    let path = std::env::temp_dir().join(format!("yahtzee-newer-save-{}.json", std::process::id()));
+    std::fs::write(&path, saved.to_string()).unwrap();
+
+    let loaded = read_save(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(loaded, Err(LoadError::NewerVersion(_))), "{:?}", loaded);
+}
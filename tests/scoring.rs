@@ -0,0 +1,311 @@
+// The scoring rules, checked through the sections of the standard scorecard
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::Die;
+use yahtzee::expected::tables;
+use yahtzee::game::Game;
+use std::collections::HashMap;
+
+use yahtzee::sections::{ audit, create_scorecard, find_sections, from_snapshot, odd_even_sections, Scorecard, SectionKind, SectionSnapshot };
+
+// Each section's name, the dice, and the points they score there
+static CASES: [(&str, [u8; 5], i32); 29] = [
+    // The upper sections count only their own number
+    ("Aces", [1, 1, 2, 3, 1], 3),
+    ("Twos", [2, 5, 2, 6, 2], 6),
+    ("Threes", [3, 1, 4, 3, 6], 6),
+    ("Fours", [1, 2, 3, 5, 6], 0),
+    ("Fives", [5, 5, 5, 5, 2], 20),
+    ("Sixes", [6, 1, 6, 2, 6], 18),
+    // n of a kind scores the dice total, and more than needed still counts
+    ("3 of a Kind", [2, 2, 2, 5, 6], 17),
+    ("3 of a Kind", [2, 2, 3, 5, 6], 0),
+    ("3 of a Kind", [6, 6, 6, 6, 1], 25),
+    ("4 of a Kind", [3, 3, 3, 3, 5], 17),
+    ("4 of a Kind", [3, 3, 3, 5, 5], 0),
+    ("4 of a Kind", [5, 5, 5, 5, 5], 25),
+    ("YAHTZEE", [6, 6, 6, 6, 6], 50),
+    ("YAHTZEE", [6, 6, 6, 6, 5], 0),
+    // Straights are runs of 3, 4, and 5 numbers in any order, duplicates don't break them
+    ("Small Straight", [1, 2, 3, 3, 6], 30),
+    ("Small Straight", [6, 5, 4, 1, 1], 30),
+    ("Small Straight", [1, 2, 4, 5, 1], 0),
+    ("Large Straight", [2, 3, 4, 5, 5], 40),
+    ("Large Straight", [3, 4, 5, 6, 1], 40),
+    ("Large Straight", [1, 2, 3, 5, 6], 0),
+    // Full House is this scorecard's run of 5, so a pair and three of a kind don't score
+    ("Full House", [1, 2, 3, 4, 5], 50),
+    ("Full House", [6, 4, 5, 3, 2], 50),
+    ("Full House", [3, 3, 2, 2, 2], 0),
+    // Chance takes anything
+    ("Chance", [1, 2, 3, 4, 6], 16),
+    ("Chance", [6, 6, 6, 6, 6], 30),
+    // The odd/even sections count the dice of their parity
+    ("Evens", [2, 3, 4, 5, 6], 12),
+    ("Evens", [1, 3, 5, 5, 1], 0),
+    ("Odds", [2, 3, 4, 5, 6], 8),
+    ("Odds", [1, 3, 5, 5, 1], 15),
+];
+
+// Each section's name, the dice, and the indexes of the dice that make it score
+static CONTRIBUTING: [(&str, [u8; 5], &[usize]); 12] = [
+    ("Sixes", [6, 2, 6, 1, 6], &[0, 2, 4]),
+    ("Aces", [6, 2, 6, 3, 6], &[]),
+    // n of a kind counts the dice of the kind, but only once there are enough of them
+    ("3 of a Kind", [4, 2, 4, 4, 5], &[0, 2, 3]),
+    ("4 of a Kind", [4, 2, 4, 4, 5], &[]),
+    ("YAHTZEE", [3, 3, 3, 3, 3], &[0, 1, 2, 3, 4]),
+    // A straight counts one die for each number in the run, a duplicate isn't needed
+    ("Small Straight", [1, 2, 3, 3, 6], &[0, 1, 2]),
+    ("Small Straight", [1, 2, 3, 4, 6], &[0, 1, 2, 3]),
+    ("Large Straight", [5, 4, 3, 2, 2], &[0, 1, 2, 3]),
+    ("Large Straight", [1, 2, 3, 5, 6], &[]),
+    ("Chance", [1, 2, 3, 4, 6], &[0, 1, 2, 3, 4]),
+    ("Evens", [2, 3, 4, 5, 6], &[0, 2, 4]),
+    ("Odds", [2, 3, 4, 5, 6], &[1, 3]),
+];
+
+// Every section that can be on a scorecard
+fn every_section() -> Scorecard {
+    return create_scorecard().into_iter().chain(odd_even_sections()).collect();
+}
+
+// Dice showing these numbers
+fn dice(nums: [u8; 5]) -> Vec<Die> {
+    return nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+// The points the dice score in the section with this name
+fn points(scorecard: &Scorecard, name: &str, dice: &[Die]) -> i32 {
+    let section_i = find_sections(scorecard, name)[0];
+    return scorecard[section_i].calc_score(dice);
+}
+
+#[test]
+fn yahtzee_scores_in_every_of_a_kind_section() {
+    let scorecard = create_scorecard();
+    let yahtzee = dice([4, 4, 4, 4, 4]);
+    assert_eq!(points(&scorecard, "3 of a Kind", &yahtzee), 20);
+    assert_eq!(points(&scorecard, "4 of a Kind", &yahtzee), 20);
+    assert_eq!(points(&scorecard, "YAHTZEE", &yahtzee), 50);
+}
+
+#[test]
+fn four_of_a_kind_also_scores_as_three_of_a_kind() {
+    let scorecard = create_scorecard();
+    let four = dice([4, 4, 2, 4, 4]);
+    assert_eq!(points(&scorecard, "3 of a Kind", &four), 18);
+    assert_eq!(points(&scorecard, "4 of a Kind", &four), 18);
+    assert_eq!(points(&scorecard, "YAHTZEE", &four), 0);
+}
+
+#[test]
+fn unrolled_dice_are_not_a_yahtzee() {
+    let scorecard = create_scorecard();
+    assert_eq!(points(&scorecard, "YAHTZEE", &dice([0; 5])), 0);
+}
+
+#[test]
+fn evens_and_odds_are_only_on_the_odd_even_scorecard() {
+    let mut game = Game::new(1);
+    assert!(find_sections(&game.scorecard, "Evens").is_empty());
+    assert!(find_sections(&game.scorecard, "Odds").is_empty());
+
+    game.add_odd_even();
+    let roll = dice([2, 4, 6, 1, 3]);
+    assert_eq!(points(&game.scorecard, "Evens", &roll), 12);
+    assert_eq!(points(&game.scorecard, "Odds", &roll), 4);
+}
+
+#[test]
+fn yahtzee_bonus_can_be_changed() {
+    let mut game = Game::new(1);
+    game.yahtzee_bonus = 50;
+    let yahtzee = dice([4, 4, 4, 4, 4]);
+
+    // The first Yahtzee fills the YAHTZEE section, the second earns the bonus
+    game.set_dice(&yahtzee, 2).unwrap();
+    game.score(find_sections(&game.scorecard, "YAHTZEE")[0]).unwrap();
+    game.set_dice(&yahtzee, 2).unwrap();
+    game.score(find_sections(&game.scorecard, "Chance")[0]).unwrap();
+
+    assert_eq!(game.totals().yahtzee_bonus, 50);
+    assert_eq!(game.total(), 50 + 20 + 50);
+}
+
+#[test]
+fn scoring_a_section_fills_it_with_the_dice_points() {
+    let mut scorecard = create_scorecard();
+    let section_i = find_sections(&scorecard, "Sixes")[0];
+    assert_eq!(scorecard[section_i].score_section(&dice([6, 1, 6, 2, 6])), Ok(18));
+    assert!(scorecard[section_i].is_filled());
+    assert_eq!(scorecard[section_i].get_points(), 18);
+}
+
+#[test]
+fn a_scored_section_is_not_scored_again() {
+    let mut scorecard = create_scorecard();
+    let section_i = find_sections(&scorecard, "Chance")[0];
+    scorecard[section_i].score_section(&dice([1, 2, 3, 4, 6])).unwrap();
+
+    // The second score is refused, and the first one kept
+    assert!(scorecard[section_i].score_section(&dice([6, 6, 6, 6, 6])).is_err());
+    assert!(scorecard[section_i].set_score(30).is_err());
+    assert_eq!(scorecard[section_i].get_points(), 16);
+}
+
+#[test]
+fn a_corrupted_total_fails_the_audit() {
+    let mut game = Game::new(3);
+    for section_i in 0..game.scorecard.len() {
+        game.roll().unwrap();
+        game.score(section_i).unwrap();
+    }
+    assert!(audit(&game.scorecard, &game.totals(), game.yahtzee_bonus, game.upper_bonus));
+
+    // The grand total, or a subtotal, drifting from the scorecard is caught
+    let mut total = game.totals();
+    total.total += 1;
+    assert!(!audit(&game.scorecard, &total, game.yahtzee_bonus, game.upper_bonus));
+    let mut lower = game.totals();
+    lower.lower -= 5;
+    assert!(!audit(&game.scorecard, &lower, game.yahtzee_bonus, game.upper_bonus));
+}
+
+#[test]
+fn every_section_scores_its_cases() {
+    let scorecard = every_section();
+    for (name, nums, expected) in CASES {
+        assert_eq!(points(&scorecard, name, &dice(nums)), expected, "{} with {:?}", name, nums);
+    }
+}
+
+#[test]
+fn every_section_reports_the_dice_it_counts() {
+    let scorecard = every_section();
+    for (name, nums, expected) in CONTRIBUTING {
+        let section_i = find_sections(&scorecard, name)[0];
+        assert_eq!(scorecard[section_i].contributing_dice(&dice(nums)), expected, "{} with {:?}", name, nums);
+    }
+}
+
+#[test]
+fn every_section_is_rebuilt_from_its_snapshot() {
+    // Half the sections filled, so both filled and open ones are rebuilt
+    let mut scorecard = create_scorecard();
+    let roll = dice([3, 3, 3, 4, 5]);
+    for section in scorecard.iter_mut().step_by(2) {
+        section.score_section(&roll).unwrap();
+    }
+
+    let snapshots: Vec<SectionSnapshot> = scorecard.iter().map(|section| section.snapshot()).collect();
+    assert_eq!(snapshots.len(), 13);
+    for (section_i, snapshot) in snapshots.iter().enumerate() {
+        // No two sections have the same snapshot
+        assert_eq!(snapshots.iter().filter(|other| *other == snapshot).count(), 1, "{:?}", snapshot);
+
+        let rebuilt = from_snapshot(snapshot).unwrap();
+        assert_eq!(rebuilt.snapshot(), *snapshot);
+        assert_eq!(rebuilt.calc_score(&roll), scorecard[section_i].calc_score(&roll), "{:?}", snapshot);
+    }
+}
+
+#[test]
+fn unrolled_dice_score_nothing() {
+    let scorecard = every_section();
+    for section in &scorecard {
+        assert_eq!(section.calc_score(&dice([0; 5])), 0, "{}", section.get_name());
+        assert!(section.contributing_dice(&dice([0; 5])).is_empty(), "{}", section.get_name());
+    }
+}
+
+// The points n of a kind (or Chance, 0 of a kind) scored when the dice were counted with a HashMap,
+// before a YAHTZEE became a fixed 50
+fn hashmap_of_a_kind(nums: &[u8], kind: u8) -> i32 {
+    let mut counts: HashMap<u8, u8> = HashMap::new();
+    for num in nums {
+        *counts.entry(*num).or_insert(0) += 1;
+    }
+    let most = counts.values().copied().max().unwrap_or(0);
+    if most < kind {
+        return 0;
+    }
+    return if kind == 5 { 50 } else { nums.iter().map(|num| i32::from(*num)).sum() };
+}
+
+#[test]
+fn of_a_kind_scores_every_roll_as_the_hashmap_count_did() {
+    let scorecard = create_scorecard();
+    let counting: Vec<_> = scorecard
+        .iter()
+        .filter(|section| matches!(section.kind().0, SectionKind::OfAKind | SectionKind::Chance))
+        .collect();
+    assert_eq!(counting.len(), 4);
+
+    // Every ordered roll of five dice, 6^5 of them
+    for roll in 0..6u32.pow(5) {
+        let nums: Vec<u8> = (0..5).map(|die_i| (roll / 6u32.pow(die_i) % 6) as u8 + 1).collect();
+        let dice = dice(nums.clone().try_into().unwrap());
+        for section in &counting {
+            let expected = hashmap_of_a_kind(&nums, section.kind().1);
+            assert_eq!(section.calc_score(&dice), expected, "{} with {:?}", section.get_name(), nums);
+        }
+    }
+}
+
+#[test]
+fn the_score_table_agrees_with_scoring_every_hand() {
+    let tables = tables();
+    let scorecard = every_section();
+    let mut hands_seen = vec![false; tables.hand_count()];
+
+    // Every different hand of five dice, highest number first so they aren't in the table's order
+    for a in 1..=6 {
+        for b in 1..=a {
+            for c in 1..=b {
+                for d in 1..=c {
+                    for e in 1..=d {
+                        let hand = dice([a, b, c, d, e]);
+                        let hand_i = tables.hand_of(&hand).unwrap();
+                        hands_seen[hand_i] = true;
+                        for section in &scorecard {
+                            let section_i = tables.section_index(section.get_name()).unwrap();
+                            assert_eq!(
+                                tables.scores[section_i][hand_i],
+                                section.calc_score(&hand),
+                                "{} with {:?}",
+                                section.get_name(),
+                                [a, b, c, d, e]
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // There are 252 hands, and each has its own place in the table
+    assert_eq!(tables.hand_count(), 252);
+    assert!(hands_seen.iter().all(|seen| *seen));
+    assert_eq!(tables.hand_of(&dice([0, 1, 2, 3, 4])), None);
+}
+
+#[test]
+fn sections_are_found_by_part_of_their_name() {
+    let scorecard = create_scorecard();
+    assert_eq!(find_sections(&scorecard, "full"), [11]);
+    assert_eq!(find_sections(&scorecard, "three"), [6]);
+    assert_eq!(find_sections(&scorecard, "YAH"), [8]);
+
+    // A name matching several sections gives them all to choose from, unless it's one's whole name
+    assert_eq!(find_sections(&scorecard, "straight"), [9, 10]);
+    assert_eq!(find_sections(&scorecard, "of a kind"), [6, 7]);
+    assert_eq!(find_sections(&scorecard, "large straight"), [10]);
+
+    // Nothing is found for a name no section has
+    assert!(find_sections(&scorecard, "pair").is_empty());
+    assert!(find_sections(&scorecard, " ").is_empty());
+}
@@ -0,0 +1,28 @@
+// The checks `yahtzee selfcheck` runs, which have to pass on the platforms the tests run on
+#![allow(clippy::needless_return)]
+
+use std::process::Command;
+use std::time::{ Duration, Instant };
+
+use yahtzee::selfcheck::run_checks;
+
+#[test]
+fn every_check_passes() {
+    for result in run_checks() {
+        assert!(result.passed(), "{}: {:?}", result.name, result.failures);
+    }
+}
+
+#[test]
+fn the_subcommand_passes_quickly() {
+    let start = Instant::now();
+    let output = Command::new(env!("CARGO_BIN_EXE_yahtzee")).arg("selfcheck").output().unwrap();
+    let elapsed = start.elapsed();
+
+    let printed = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", printed);
+    assert!(printed.contains("Self-check: 3 of 3 checks passed"), "{}", printed);
+
+    // Well under a second, even in the debug build the tests use
+    assert!(elapsed < Duration::from_secs(1), "took {:?}", elapsed);
+}
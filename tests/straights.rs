@@ -0,0 +1,130 @@
+// Every straight section checked against every possible hand, and the hands known to be tricky
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::Die;
+use yahtzee::game::Game;
+use yahtzee::sections::{ create_scorecard, find_sections, sum_straight, PointSection, Scorecard, SectionKind };
+
+// The straights, scored for a fixed amount and as the sum straights rule scores them
+fn straight_sections() -> Scorecard {
+    let fixed: Scorecard = create_scorecard()
+        .into_iter()
+        .filter(|section| section.kind().0 == SectionKind::Straight)
+        .collect();
+    let sums: Scorecard = fixed
+        .iter()
+        .filter_map(|section| sum_straight(section.as_ref()))
+        .collect();
+    return fixed.into_iter().chain(sums).collect();
+}
+
+// Dice showing these numbers
+fn dice(nums: &[u8]) -> Vec<Die> {
+    return nums
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+}
+
+// The points a straight section should score, worked out the slow and obvious way: look for every
+// number of the run, from every number it could start on
+fn reference_score(section: &dyn PointSection, nums: &[u8]) -> i32 {
+    let (kind, length) = section.kind();
+    let found = (1..=6u8).any(|start| (start..start + length).all(|num| (num <= 6) & nums.contains(&num)));
+    if !found {
+        return 0;
+    }
+    return match kind {
+        SectionKind::SumStraight => nums.iter().map(|num| i32::from(*num)).sum(),
+        _ => i32::from(length) * 10,
+    };
+}
+
+// Every ordered hand of five dice showing the given numbers
+fn every_hand(numbers: std::ops::RangeInclusive<u8>) -> Vec<[u8; 5]> {
+    let faces: Vec<u8> = numbers.collect();
+    let mut hands = vec![[0; 5]];
+    for die_i in 0..5 {
+        hands = hands
+            .iter()
+            .flat_map(|hand| {
+                faces.iter().map(move |face| {
+                    let mut hand = *hand;
+                    hand[die_i] = *face;
+                    hand
+                })
+            })
+            .collect();
+    }
+    return hands;
+}
+
+#[test]
+fn every_rolled_hand_matches_the_reference() {
+    let hands = every_hand(1..=6);
+    assert_eq!(hands.len(), 7776);
+    for section in &straight_sections() {
+        for hand in &hands {
+            assert_eq!(section.calc_score(&dice(hand)), reference_score(section.as_ref(), hand), "{} {:?}", section.get_name(), hand);
+        }
+    }
+}
+
+#[test]
+fn hands_with_unrolled_dice_match_the_reference() {
+    // Unrolled dice (0) shouldn't be mixed with rolled ones, but they never count toward a run
+    for section in &straight_sections() {
+        for hand in &every_hand(0..=6) {
+            assert_eq!(section.calc_score(&dice(hand)), reference_score(section.as_ref(), hand), "{} {:?}", section.get_name(), hand);
+        }
+    }
+}
+
+// The points each straight (runs of 3, 4, and 5, then the same summed) scores for the hand
+fn scores(nums: &[u8]) -> Vec<i32> {
+    return straight_sections()
+        .iter()
+        .map(|section| section.calc_score(&dice(nums)))
+        .collect();
+}
+
+#[test]
+fn a_duplicate_number_shortens_the_run() {
+    assert_eq!(scores(&[1, 2, 3, 4, 4]), [30, 40, 0, 14, 14, 0]);
+    assert_eq!(scores(&[3, 3, 4, 4, 5]), [30, 0, 0, 19, 0, 0]);
+}
+
+#[test]
+fn a_one_and_a_six_dont_join_up() {
+    assert_eq!(scores(&[5, 6, 1, 2, 3]), [30, 0, 0, 17, 0, 0]);
+    assert_eq!(scores(&[6, 1, 2, 3, 4]), [30, 40, 0, 16, 16, 0]);
+    assert_eq!(scores(&[1, 6, 3, 4, 5]), [30, 40, 0, 19, 19, 0]);
+}
+
+#[test]
+fn two_to_five_is_a_run_of_four_with_anything() {
+    for last in 1..=6 {
+        let expected = if (last == 1) | (last == 6) { 50 } else { 0 };
+        let total = 14 + i32::from(last);
+        let sum_expected = if expected > 0 { total } else { 0 };
+        assert_eq!(scores(&[2, 3, 4, 5, last]), [30, 40, expected, total, total, sum_expected], "2-3-4-5-{}", last);
+    }
+}
+
+#[test]
+fn unrolled_dice_dont_fill_a_gap() {
+    assert_eq!(scores(&[1, 2, 0, 4, 5]), [0, 0, 0, 0, 0, 0]);
+    assert_eq!(scores(&[0, 2, 3, 4, 5]), [30, 40, 0, 14, 14, 0]);
+    assert_eq!(scores(&[0, 0, 0, 0, 0]), [0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn the_sum_straights_rule_scores_the_dice_total() {
+    let mut game = Game::new(1);
+    game.set_dice(&dice(&[1, 2, 3, 4, 5]), 2).unwrap();
+    let large = find_sections(&game.scorecard, "Large Straight")[0];
+    assert_eq!(game.potential(large), 40);
+
+    game.add_sum_straights();
+    assert_eq!(game.potential(large), 15);
+}
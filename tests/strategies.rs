@@ -0,0 +1,30 @@
+// The computer strategies, compared on the same seeded games
+#![allow(clippy::needless_return)]
+
+use yahtzee::tournament::{ self, Standing };
+
+// The strategy's standing in the tournament
+fn standing<'a>(standings: &'a [Standing], strategy: &str) -> &'a Standing {
+    return standings
+        .iter()
+        .find(|standing| standing.strategy == strategy)
+        .unwrap();
+}
+
+#[test]
+fn expected_value_beats_greedy() {
+    // Both play the same 500 seeded games
+    let names = ["greedy".to_string(), "ev".to_string()];
+    let standings = tournament::run(&names, 500, 1).unwrap().standings;
+
+    // The expected value strategy's mean is above greedy's, with their 95% confidence intervals apart
+    let (greedy, ev) = (standing(&standings, "greedy"), standing(&standings, "ev"));
+    assert!(
+        ev.mean - ev.mean_margin > greedy.mean + greedy.mean_margin,
+        "ev {:.1} ± {:.1} isn't clearly above greedy {:.1} ± {:.1}",
+        ev.mean,
+        ev.mean_margin,
+        greedy.mean,
+        greedy.mean_margin
+    );
+}
@@ -0,0 +1,112 @@
+// The moves that make up a turn: rolling, holding, and rerolling seeded dice
+#![allow(clippy::needless_return)]
+
+use yahtzee::die::Die;
+use yahtzee::error::GameError;
+use yahtzee::game::{ Game, Move, MAX_ROLLS };
+
+#[test]
+fn rerolling_all_unfreezes_and_rolls_every_die() {
+    let mut game = Game::new(5);
+    game.roll().unwrap();
+    game.toggle_freeze(0).unwrap();
+    game.toggle_freeze(3).unwrap();
+    game.reroll_all().unwrap();
+
+    // The same seed rolling every die twice rolls the same dice, so none were kept
+    let mut unfrozen = Game::new(5);
+    unfrozen.roll().unwrap();
+    unfrozen.roll().unwrap();
+    assert_eq!(game.dice, unfrozen.dice);
+    assert!(game.dice.iter().all(|die| !die.frozen));
+    assert_eq!(game.rolls, MAX_ROLLS - 2);
+    assert_eq!(game.rolls_used, 2);
+
+    // It can't be done without a roll left
+    game.reroll_all().unwrap();
+    assert!(matches!(game.reroll_all(), Err(GameError::InvalidMove(_))));
+    assert_eq!(game.rolls, 0);
+}
+
+#[test]
+fn a_turn_keeps_the_held_dice_and_rolls_the_rest() {
+    let mut game = Game::new(9);
+    game.turn(&[]).unwrap();
+    let first = game.dice.clone();
+    game.turn(&[0, 2]).unwrap();
+
+    // The kept dice are frozen as they were, the rest roll as holding them and rolling would
+    let mut held = Game::new(9);
+    for player_move in [Move::Roll, Move::Hold(vec![0, 2]), Move::Roll] {
+        held.play(player_move).unwrap();
+    }
+    assert_eq!((game.dice[0].num, game.dice[2].num), (first[0].num, first[2].num));
+    assert_eq!(game.dice.iter().map(|die| die.frozen).collect::<Vec<bool>>(), [true, false, true, false, false]);
+    assert_eq!(game.dice, held.dice);
+    assert_eq!(game.rolls, MAX_ROLLS - 2);
+
+    // Nothing can be kept before the first roll
+    let mut fresh = Game::new(9);
+    assert!(matches!(fresh.turn(&[0]), Err(GameError::InvalidMove(_))));
+    assert_eq!(fresh.rolls, MAX_ROLLS);
+}
+
+#[test]
+fn set_dice_are_scored_as_if_rolled() {
+    let mut game = Game::new(1);
+    let mut dice: Vec<Die> = [6, 6, 6, 6, 1]
+        .iter()
+        .map(|num| Die { num: *num, ..Die::default() })
+        .collect();
+    dice[4].frozen = true;
+    game.set_dice(&dice, 1).unwrap();
+    assert_eq!(game.dice[..], dice[..]);
+    assert_eq!(game.rolls, 1);
+
+    // The set dice are scored without rolling first
+    game.play(Move::Score(7)).unwrap();
+    assert_eq!(game.scorecard[7].get_points(), 25);
+
+    // Only a full set of dice showing one of their sides can be set, after at least one roll
+    dice[0].num = 7;
+    assert!(matches!(game.set_dice(&dice, 1), Err(GameError::InvalidMove(_))));
+    assert!(matches!(game.set_dice(&dice[1..], 1), Err(GameError::InvalidMove(_))));
+    dice[0].num = 6;
+    assert!(matches!(game.set_dice(&dice, MAX_ROLLS), Err(GameError::InvalidMove(_))));
+}
+
+#[test]
+fn every_die_frozen_refuses_to_roll() {
+    let mut game = Game::new(3);
+    game.roll().unwrap();
+    game.play(Move::Hold(vec![0, 1, 2, 3, 4])).unwrap();
+    let dice = game.dice.clone();
+
+    // The roll isn't used up, and the dice stay as they were
+    assert!(matches!(game.roll(), Err(GameError::InvalidMove(_))));
+    assert_eq!(game.rolls, MAX_ROLLS - 1);
+    assert_eq!(game.rolls_used, 1);
+    assert_eq!(game.dice, dice);
+
+    // Unfreezing one lets it roll again
+    game.toggle_freeze(4).unwrap();
+    game.roll().unwrap();
+    assert_eq!(game.rolls, MAX_ROLLS - 2);
+}
+
+#[test]
+fn restoring_a_snapshot_gives_back_a_nudge_made_since() {
+    let mut game = Game::new(4);
+    game.set_nudges(1);
+    game.roll().unwrap();
+    let snapshot = game.snapshot_turn();
+    let dice = game.dice.clone();
+
+    // Nudge whichever way the first die can go
+    game.nudge(0, dice[0].num < 6).unwrap();
+    assert_eq!(game.nudges_remaining, 0);
+
+    game.restore_turn(&snapshot).unwrap();
+    assert_eq!(game.nudges_remaining, 1);
+    assert_eq!(game.dice, dice);
+}
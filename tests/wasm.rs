@@ -0,0 +1,38 @@
+// The WebAssembly bindings, played natively (only built with the wasm feature: cargo test --features wasm)
+#![cfg(feature = "wasm")]
+#![allow(clippy::needless_return)]
+
+use serde_json::Value;
+use yahtzee::wasm::{ new_game, potential_scores, roll, score, state_json, toggle_hold };
+
+// The game's state, as JavaScript would read it
+fn state() -> Value {
+    return serde_json::from_str(&state_json().unwrap()).unwrap();
+}
+
+#[test]
+fn a_turn_is_played_through_the_bindings() {
+    new_game(7);
+    assert_eq!(state()["rolls_left"], 3);
+    assert_eq!(potential_scores().unwrap(), "[]");
+
+    // Roll, hold the first die and roll the rest, then score the best section
+    roll().unwrap();
+    toggle_hold(0).unwrap();
+    let held = state()["dice"][0].clone();
+    assert_eq!(held["frozen"], true);
+    roll().unwrap();
+    assert_eq!(state()["dice"][0]["num"], held["num"]);
+
+    let potentials: Value = serde_json::from_str(&potential_scores().unwrap()).unwrap();
+    let best = potentials
+        .as_array()
+        .unwrap()
+        .iter()
+        .max_by_key(|potential| potential["points"].as_i64().unwrap())
+        .unwrap();
+    score(best["section"].as_u64().unwrap() as usize).unwrap();
+
+    assert_eq!(state()["total"], best["points"]);
+    assert_eq!(state()["rolls_left"], 3);
+}